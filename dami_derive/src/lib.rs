@@ -0,0 +1,109 @@
+//! `#[derive(DataFrameRow)]` for [dami](https://docs.rs/dami)
+//!
+//! The derive generates a builder holding one `Vec` per field, rows are pushed field by field into
+//! the vectors and each vector becomes a Series named after its field, without going through serde.
+//!
+//! Use it through the `derive` feature of dami
+//! ```
+//! use dami::prelude::*;
+//!
+//! #[derive(DataFrameRow)]
+//! struct Point {
+//!     x: f64,
+//!     y: f64,
+//! }
+//! let df = DataFrame::from_rows(vec![Point { x: 1.0, y: 2.0 }]).unwrap();
+//! assert_eq!(df.columns(), vec!["x", "y"]);
+//! ```
+extern crate proc_macro;
+
+use proc_macro::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields};
+
+/// Derive `dami::core::records::DataFrameRow` for a struct with named fields
+///
+/// Every field becomes a column named after the field, field types must implement
+/// `dami::core::records::ColumnType` (`bool`, `i32`, `i64`, `f32`, `f64`, `String` and `&'static str`)
+#[proc_macro_derive(DataFrameRow)]
+pub fn derive_dataframe_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> Result<proc_macro2::TokenStream, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "DataFrameRow cannot be derived for generic structs",
+        ));
+    }
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(Error::new_spanned(
+                    &input.ident,
+                    "DataFrameRow can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &input.ident,
+                "DataFrameRow can only be derived for structs",
+            ))
+        }
+    };
+    let vis = &input.vis;
+    let name = &input.ident;
+    let builder = format_ident!("{}DataFrameBuilder", name);
+    let idents = fields
+        .iter()
+        .map(|f| f.ident.clone().unwrap())
+        .collect::<Vec<_>>();
+    let types = fields.iter().map(|f| &f.ty);
+    // Fields of other types would become object Series which DataFrames drop
+    let assertions = fields.iter().map(|f| {
+        let ty = &f.ty;
+        quote_spanned! {ty.span()=>
+            const _: fn() = || {
+                fn assert_column_type<T: ::dami::core::records::ColumnType>() {}
+                assert_column_type::<#ty>();
+            };
+        }
+    });
+    let names = idents.iter().map(|f| f.unraw().to_string());
+    Ok(quote! {
+        #(#assertions)*
+        #[doc(hidden)]
+        #[derive(Default)]
+        #vis struct #builder {
+            #(#idents: ::std::vec::Vec<#types>,)*
+        }
+        impl ::dami::core::records::ColumnBuilder<#name> for #builder {
+            fn push(&mut self, row: #name) {
+                #(self.#idents.push(row.#idents);)*
+            }
+            fn finish(
+                self,
+            ) -> ::std::result::Result<::dami::core::dataframe::DataFrame, ::dami::enums::DataFrameErrors>
+            {
+                let mut df = ::dami::core::dataframe::DataFrame::new();
+                #({
+                    let mut series = ::dami::core::series::Series::from(self.#idents);
+                    series.set_name(#names);
+                    df.add_series(series, true)?;
+                })*
+                ::std::result::Result::Ok(df)
+            }
+        }
+        impl ::dami::core::records::DataFrameRow for #name {
+            type Builder = #builder;
+        }
+    })
+}
@@ -6,6 +6,12 @@ pub mod series;
 
 pub mod dataframe;
 
-mod block_manager;
+pub(crate) mod block_manager;
 
-mod index;
+pub mod display;
+
+mod serialize;
+
+pub mod records;
+
+pub mod index;
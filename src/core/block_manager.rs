@@ -6,19 +6,20 @@ mod stats;
 
 mod ops;
 use crate::core::block_manager::manager::Block;
+use crate::core::display::{self, Options};
+use crate::core::index::date_time::format_timestamp;
 use crate::core::series::Series;
 use crate::enums::DataFrameErrors::KeyError;
 use crate::enums::{DataFrameErrors, DataTypes};
 use crate::prelude::DataFrame;
 use ndarray::{Array1, Array2};
-use prettytable::evcxr::EvcxrDisplay;
 use prettytable::format::consts::FORMAT_CLEAN;
 use prettytable::{Cell, Row, Table};
-use serde::export::Formatter;
 use std::any::Any;
 use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
 use std::fmt;
+use std::fmt::Formatter;
 
 #[derive(Default)]
 pub struct BlockManager {
@@ -33,24 +34,44 @@ pub struct BlockManager {
     // The DataFrame index
     index: Vec<String>,
 }
+/// How cells are turned into text when displaying or exporting a DataFrame
+pub(crate) struct CellFormat {
+    /// Number of digits after the decimal point of floats
+    pub precision: usize,
+    /// Strings longer than this number of characters are cut and end with `...`
+    pub max_width: Option<usize>,
+}
+impl Default for CellFormat {
+    /// Format cells using the current [display options](../display/index.html)
+    fn default() -> Self {
+        let options = display::options();
+        CellFormat {
+            precision: options.precision(),
+            max_width: options.max_colwidth(),
+        }
+    }
+}
+impl CellFormat {
+    fn float(&self, value: f64) -> String {
+        format!("{:.*}", self.precision, value)
+    }
+    pub(crate) fn string(&self, value: &str) -> String {
+        match self.max_width {
+            Some(width) if value.chars().count() > width => {
+                value.chars().take(width).collect::<String>() + "..."
+            }
+            _ => value.to_string(),
+        }
+    }
+}
 impl fmt::Debug for BlockManager {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let table = self.real_formatter(true);
-        let mut tbl = table.to_string();
-        if self.len >= 50 {
-            tbl += &format!("\n[{} rows x {} columns]", self.len, self.names.len());
-        }
-        write!(f, "{}", tbl)
+        write!(f, "{}", self.real_formatter(true))
     }
 }
 impl fmt::Display for BlockManager {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        let table = self.real_formatter(false);
-        let mut tbl = table.to_string();
-        if self.len >= 50 {
-            tbl += &format!("\n{}[rows x {} columns]", self.len, self.names.len());
-        }
-        write!(f, "{}", tbl)
+        write!(f, "{}", self.real_formatter(false))
     }
 }
 impl BlockManager {
@@ -305,6 +326,17 @@ impl BlockManager {
                     self.blocks.insert(DataTypes::STR, Box::new(block));
                 }
             }
+            DataTypes::DATETIME => {
+                if let Some(block) = self.blocks.get_mut(dtype) {
+                    let series = other.downcast::<Series<i64>>().unwrap();
+                    block.downcast_mut::<Block<i64>>().unwrap().push(*series);
+                } else {
+                    let series = other.downcast::<Series<i64>>().unwrap();
+                    let mut block = Block::default();
+                    block.push(*series);
+                    self.blocks.insert(DataTypes::DATETIME, Box::new(block));
+                }
+            }
             DataTypes::OBJECT => {
                 let names = self.names.pop().unwrap();
                 self.values.remove(&names);
@@ -316,139 +348,216 @@ impl BlockManager {
         }
     }
 
-    fn real_formatter(&self, debug: bool) -> Table {
-        let mut table = Table::new();
-        if self.len < 10 {
-            self.format(0, self.len, true, &mut table);
-        } else {
-            self.format(0, 5, true, &mut table);
-            table.add_row(Row::new(vec![Cell::new("...."); self.values.len() + 1]));
-            let last_five = self.len - 5;
-            self.format(last_five, self.len, false, &mut table);
-        }
-        if debug {
+    /// Format the DataFrame as it is printed, truncated following the display options
+    ///
+    /// The dtype of each column is added when `debug` is set or the options ask for it and the
+    /// shape of the frame is added when rows or columns were elided
+    fn real_formatter(&self, debug: bool) -> String {
+        let options = display::options();
+        let rows = self.display_rows();
+        let columns = display::visible(self.names.len(), options.max_columns());
+        let mut table = self.table(rows.iter().copied(), &columns, &options);
+        if debug || options.show_dtypes() {
             table.add_empty_row();
-            let mut row_dtypes = vec![Cell::new("types")];
-            for i in &self.names {
-                row_dtypes.push(Cell::new(&format!("{:?}", self.values.get(i).unwrap())));
+            let mut row_dtypes = vec![];
+            if options.show_index() {
+                row_dtypes.push(Cell::new("types"));
+            }
+            for column in &columns {
+                row_dtypes.push(match column {
+                    Some(j) => Cell::new(&format!("{:?}", self.values[&self.names[*j]])),
+                    None => Cell::new("...."),
+                });
             }
             table.add_row(Row::new(row_dtypes));
         }
-        table
+        let mut tbl = table.to_string();
+        if rows.contains(&None) || columns.contains(&None) {
+            tbl += &format!("\n[{} rows x {} columns]", self.len, self.names.len());
+        }
+        tbl
     }
-    #[allow(clippy::similar_names, unused_assignments)]
-    fn format(&self, start: usize, end: usize, add_titles: bool, table: &mut Table) {
+    /// Build a table of `rows` holding the columns at the positions in `columns`
+    ///
+    /// `None` in either marks the place of elided rows or columns
+    fn table<I: Iterator<Item = Option<usize>>>(
+        &self,
+        rows: I,
+        columns: &[Option<usize>],
+        options: &Options,
+    ) -> Table {
+        let mut table = Table::new();
         table.set_format(*FORMAT_CLEAN);
-        if add_titles {
-            let mut title = vec![Cell::new(" ")];
-            for i in &self.names {
-                title.push(Cell::new(i))
-            }
-            table.set_titles(Row::new(title));
+        let mut title = vec![];
+        if options.show_index() {
+            title.push(Cell::new(" "));
         }
-        for i in start..end {
-            let mut row = vec![];
-            let mut f64_counter = 0;
-            let mut f32_counter = 0;
-            let mut i64_counter = 0;
-            let mut i32_counter = 0;
-            let mut string_counter = 0;
-            let mut str_counter = 0;
-            let mut bool_counter = 0;
-
-            for j in &self.names {
-                // Counters
-                // Because some things need order
-
-                let value = self.values.get(j).unwrap();
-                let dtype = self.blocks.get(value).unwrap();
-                // Okay.
-                // Time for magic
-                // Dereference box any to a block of different types and get the value at X[i]
-                match value {
-                    DataTypes::F64 => {
-                        let block = dtype.downcast_ref::<Block<f64>>().unwrap();
-                        row.push(Cell::new(&format!(
-                            "{:0<3.3}",
-                            block.get_value_at(f64_counter, i)
-                        )));
-                        f64_counter += 1;
-                    }
-                    DataTypes::F32 => {
-                        let block = dtype.downcast_ref::<Block<f32>>().unwrap();
-                        row.push(Cell::new(&format!(
-                            "{:0<3.3}",
-                            block.get_value_at(f32_counter, i)
-                        )));
-                        f32_counter += 1;
-                    }
-                    DataTypes::I64 => {
-                        let block = dtype.downcast_ref::<Block<i64>>().unwrap();
-                        row.push(Cell::new(&format!(
-                            "{}",
-                            block.get_value_at(i64_counter, i)
-                        )));
-                        i64_counter += 1;
+        for column in columns {
+            title.push(match column {
+                Some(j) => Cell::new(&self.names[*j]),
+                None => Cell::new("...."),
+            });
+        }
+        let width = title.len();
+        table.set_titles(Row::new(title));
+        let format = CellFormat::default();
+        for row in rows {
+            match row {
+                Some(i) => {
+                    let values = self.format_row(i, &format);
+                    let mut cells = vec![];
+                    if options.show_index() {
+                        cells.push(Cell::new(&values[0]));
                     }
-                    DataTypes::I32 => {
-                        let block = dtype.downcast_ref::<Block<i32>>().unwrap();
-                        row.push(Cell::new(&format!(
-                            "{:?}",
-                            block.get_value_at(i32_counter, i)
-                        )));
-                        i32_counter += 1;
+                    for column in columns {
+                        cells.push(match column {
+                            Some(j) => Cell::new(&values[j + 1]),
+                            None => Cell::new("...."),
+                        });
                     }
-                    DataTypes::STRING => {
-                        let block = dtype.downcast_ref::<Block<String>>().unwrap();
-                        let value_at = block.get_value_at(string_counter, i);
-                        if value_at.len() < 30 {
-                            row.push(Cell::new(value_at.as_str()));
-                        } else {
-                            row.push(Cell::new(&(value_at[0..30].to_string() + "...")));
-                        }
+                    table.add_row(Row::new(cells))
+                }
+                None => table.add_row(Row::new(vec![Cell::new("...."); width])),
+            };
+        }
+        table
+    }
+    /// The rows shown when printing the DataFrame, `None` marks the place of the elided rows
+    ///
+    /// All rows are shown if there are at most `max_rows` of the display options, otherwise the
+    /// first and last rows
+    pub(crate) fn display_rows(&self) -> Vec<Option<usize>> {
+        display::visible(self.len, display::options().max_rows())
+    }
+    /// Table of the rows from `start` to `end` used by `head` and `tail`
+    fn rows_table(&self, start: usize, end: usize) -> Table {
+        let options = display::options();
+        let columns = display::visible(self.names.len(), options.max_columns());
+        self.table((start..end).map(Some), &columns, &options)
+    }
+    /// Format the index label followed by the value of each column at row `i`
+    ///
+    /// `object` columns are formatted as empty strings
+    #[allow(clippy::similar_names)]
+    pub(crate) fn format_row(&self, i: usize, format: &CellFormat) -> Vec<String> {
+        let mut row = vec![self.index[i].clone()];
+        let mut f64_counter = 0;
+        let mut f32_counter = 0;
+        let mut i64_counter = 0;
+        let mut i32_counter = 0;
+        let mut string_counter = 0;
+        let mut str_counter = 0;
+        let mut bool_counter = 0;
+        let mut datetime_counter = 0;
 
-                        string_counter += 1;
-                    }
-                    DataTypes::STR => {
-                        let block = dtype.downcast_ref::<Block<&'static str>>().unwrap();
-                        row.push(Cell::new(block.get_value_at(str_counter, i)));
-                        str_counter += 1;
-                    }
-                    DataTypes::BOOL => {
-                        let block = dtype.downcast_ref::<Block<bool>>().unwrap();
-                        row.push(Cell::new(&format!(
-                            "{}",
-                            block.get_value_at(bool_counter, i)
-                        )));
-                        bool_counter += 1;
-                    }
-                    _ => continue,
+        for j in &self.names {
+            // Counters
+            // Because some things need order
+
+            let value = self.values.get(j).unwrap();
+            let dtype = self.blocks.get(value).unwrap();
+            // Okay.
+            // Time for magic
+            // Dereference box any to a block of different types and get the value at X[i]
+            match value {
+                DataTypes::F64 => {
+                    let block = dtype.downcast_ref::<Block<f64>>().unwrap();
+                    row.push(format.float(block.get_value_at(f64_counter, i)));
+                    f64_counter += 1;
+                }
+                DataTypes::F32 => {
+                    let block = dtype.downcast_ref::<Block<f32>>().unwrap();
+                    row.push(format.float(f64::from(block.get_value_at(f32_counter, i))));
+                    f32_counter += 1;
+                }
+                DataTypes::I64 => {
+                    let block = dtype.downcast_ref::<Block<i64>>().unwrap();
+                    row.push(format!("{}", block.get_value_at(i64_counter, i)));
+                    i64_counter += 1;
+                }
+                DataTypes::I32 => {
+                    let block = dtype.downcast_ref::<Block<i32>>().unwrap();
+                    row.push(format!("{:?}", block.get_value_at(i32_counter, i)));
+                    i32_counter += 1;
+                }
+                DataTypes::STRING => {
+                    let block = dtype.downcast_ref::<Block<String>>().unwrap();
+                    row.push(format.string(&block.get_value_at(string_counter, i)));
+                    string_counter += 1;
+                }
+                DataTypes::STR => {
+                    let block = dtype.downcast_ref::<Block<&'static str>>().unwrap();
+                    row.push(format.string(block.get_value_at(str_counter, i)));
+                    str_counter += 1;
+                }
+                DataTypes::BOOL => {
+                    let block = dtype.downcast_ref::<Block<bool>>().unwrap();
+                    row.push(format!("{}", block.get_value_at(bool_counter, i)));
+                    bool_counter += 1;
+                }
+                DataTypes::DATETIME => {
+                    let block = dtype.downcast_ref::<Block<i64>>().unwrap();
+                    row.push(format_timestamp(block.get_value_at(datetime_counter, i)));
+                    datetime_counter += 1;
                 }
+                DataTypes::OBJECT => row.push(String::new()),
             }
-            row.insert(0, Cell::new(&self.index[i]));
-            table.add_row(Row::new(row));
         }
+        row
     }
     /// Get the series at the col X
+    ///
+    /// The block is looked up using the dtype of the column since two blocks
+    /// may hold the same rust type (eg `i64` and `datetime` columns)
     pub fn get<T>(&self, col: &str) -> Option<Series<T>>
     where
         T: Clone + Default + 'static,
     {
-        for blocks in self.blocks.values() {
-            if let Some(block) = blocks.downcast_ref::<Block<T>>() {
-                return Some(block.get_series_at_name(col));
-            };
+        let dtype = self.values.get(col)?;
+        self.blocks
+            .get(dtype)?
+            .downcast_ref::<Block<T>>()
+            .map(|block| block.get_series_at_name(col))
+    }
+    /// Remove the series at column `col` returning it
+    ///
+    /// Returns `None` and leaves the block manager untouched if the column doesn't exist
+    /// or isn't of type `T`
+    pub fn pop<T>(&mut self, col: &str) -> Option<Series<T>>
+    where
+        T: Clone + Default + 'static,
+    {
+        let dtype = self.values.get(col)?;
+        let block = self.blocks.get_mut(dtype)?.downcast_mut::<Block<T>>()?;
+        let position = block.names.iter().position(|f| f == col)?;
+        block.names.remove(position);
+        let series = block.data.remove(position);
+        if block.data.is_empty() {
+            self.blocks.remove(dtype);
         }
-        None
+        self.values.remove(col);
+        self.names.retain(|f| f != col);
+        if self.names.is_empty() {
+            self.len = 0;
+            self.index.clear();
+        }
+        Some(series)
+    }
+    /// Get the column names in the order they were inserted
+    pub fn names(&self) -> Vec<String> {
+        self.names.clone()
+    }
+    /// Get the index of the block manager
+    pub fn index(&self) -> Vec<String> {
+        self.index.clone()
+    }
+    /// Get the number of rows held by the block manager
+    pub fn len(&self) -> usize {
+        self.len
     }
     fn reindex(&mut self, new_names: Vec<String>) {
         self.names = new_names;
     }
-    pub fn head_evcxr(&self, n: usize) {
-        let mut table = Table::new();
-        self.format(0, n, true, &mut table);
-        table.evcxr_display();
-    }
     #[allow(clippy::needless_pass_by_value)]
     pub fn mask<T, F>(&mut self, value: T, cond: F)
     where
@@ -461,12 +570,6 @@ impl BlockManager {
             };
         }
     }
-    pub fn tail_evcxr(&self, n: usize) {
-        let mut table = Table::new();
-        let start = self.len - n;
-        self.format(start, self.len, true, &mut table);
-        table.evcxr_display();
-    }
     pub fn to_ndarray<T>(&self) -> Option<Array2<T>>
     where
         T: Clone + Default + 'static,
@@ -479,15 +582,10 @@ impl BlockManager {
         None
     }
     pub fn head(&self, n: usize) {
-        let mut table = Table::new();
-        self.format(0, n, true, &mut table);
-        println!("{}", table.to_string());
+        println!("{}", self.rows_table(0, n).to_string());
     }
     pub fn tail(&self, n: usize) {
-        let mut table = Table::new();
-        let start = self.len - n;
-        self.format(start, self.len, true, &mut table);
-        println!("{}", table.to_string());
+        println!("{}", self.rows_table(self.len - n, self.len).to_string());
     }
     pub fn transform<T, P, F>(&self, func: F, axis: bool) -> Option<DataFrame>
     where
@@ -539,6 +637,10 @@ impl Clone for BlockManager {
                     let block = i.1.downcast_ref::<Block<String>>().unwrap();
                     block_mgr.extend_from_block(block.clone())
                 }
+                DataTypes::DATETIME => {
+                    let block = i.1.downcast_ref::<Block<i64>>().unwrap();
+                    block_mgr.extend_from_block(block.clone())
+                }
                 _ => continue,
             }
         }
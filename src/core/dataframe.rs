@@ -1,6 +1,7 @@
 //!  DataFrame functionalities.
 //!
-use crate::core::block_manager::BlockManager;
+use crate::core::block_manager::{BlockManager, CellFormat};
+use crate::core::index::date_time::{format_timestamp, NAT};
 use crate::core::series::Series;
 use crate::enums::{DataFrameErrors, DataTypes};
 use ndarray::{Array1, Array2};
@@ -214,6 +215,10 @@ impl DataFrame {
     {
         self.block.clone().combine(other, func)
     }
+    /// Get the column names of the DataFrame in the order they were added
+    pub fn columns(&self) -> Vec<String> {
+        self.block.names()
+    }
     /// Get the DataTypes  of the underlying block.
     pub fn dtypes(&self) -> HashMap<String, DataTypes, RandomState> {
         self.block.dtypes()
@@ -255,6 +260,97 @@ impl DataFrame {
     {
         self.block.get(col)
     }
+    /// Get the row labels of the DataFrame
+    pub fn get_index(&self) -> Vec<String> {
+        self.block.index()
+    }
+    /// The rows shown when printing the DataFrame, `None` marks the place of elided rows
+    pub(crate) fn display_rows(&self) -> Vec<Option<usize>> {
+        self.block.display_rows()
+    }
+    /// Format the index label and the values at row `i` the way they are printed
+    pub(crate) fn format_row(&self, i: usize, format: &CellFormat) -> Vec<String> {
+        self.block.format_row(i, format)
+    }
+    /// Get the values of column `col` formatted as strings, used by the text writers
+    ///
+    /// `NaN`s and missing datetimes are formatted as empty strings, `None` is returned if the
+    /// column doesn't exist or has an `object` dtype
+    pub(crate) fn get_strings(&self, col: &str) -> Option<Vec<String>> {
+        fn display<T: ToString + Clone + Default + 'static>(
+            df: &DataFrame,
+            col: &str,
+        ) -> Option<Vec<String>> {
+            Some(
+                df.get::<T>(col)?
+                    .into_iter()
+                    .map(|f| f.to_string())
+                    .collect(),
+            )
+        }
+        match self.dtypes().get(col)? {
+            DataTypes::BOOL => display::<bool>(self, col),
+            DataTypes::I32 => display::<i32>(self, col),
+            DataTypes::I64 => display::<i64>(self, col),
+            DataTypes::STRING => display::<String>(self, col),
+            DataTypes::STR => display::<&str>(self, col),
+            DataTypes::F32 => Some(
+                self.get::<f32>(col)?
+                    .into_iter()
+                    .map(|f| {
+                        if f.is_nan() {
+                            String::new()
+                        } else {
+                            f.to_string()
+                        }
+                    })
+                    .collect(),
+            ),
+            DataTypes::F64 => Some(
+                self.get::<f64>(col)?
+                    .into_iter()
+                    .map(|f| {
+                        if f.is_nan() {
+                            String::new()
+                        } else {
+                            f.to_string()
+                        }
+                    })
+                    .collect(),
+            ),
+            DataTypes::DATETIME => Some(
+                self.get::<i64>(col)?
+                    .into_iter()
+                    .map(|f| {
+                        if f == NAT {
+                            String::new()
+                        } else {
+                            format_timestamp(f)
+                        }
+                    })
+                    .collect(),
+            ),
+            DataTypes::OBJECT => None,
+        }
+    }
+    /// Get the values of numeric column `col` as `f64`s, used by the plots
+    ///
+    /// `None` is returned if the column doesn't exist or isn't an integer or float column
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn get_floats(&self, col: &str) -> Option<Vec<f64>> {
+        match self.dtypes().get(col)? {
+            DataTypes::F64 => Some(self.get::<f64>(col)?.into_iter().collect()),
+            DataTypes::F32 => Some(self.get::<f32>(col)?.into_iter().map(f64::from).collect()),
+            DataTypes::I64 => Some(
+                self.get::<i64>(col)?
+                    .into_iter()
+                    .map(|f| f as f64)
+                    .collect(),
+            ),
+            DataTypes::I32 => Some(self.get::<i32>(col)?.into_iter().map(f64::from).collect()),
+            _ => None,
+        }
+    }
     /// Prints the first `n` elements of the series
     ///
     /// # Example
@@ -283,9 +379,20 @@ impl DataFrame {
     pub fn head(&self, n: usize) {
         self.block.head(n);
     }
-    /// Similar to [`head`](#method.head) but prints to an ecvxr environment
+    /// Similar to [`head`](#method.head) but shows a styled HTML table in an evcxr environment
+    ///
+    /// Every row is shown if `n` is greater than the number of rows.
+    /// See [`evcxr_display`](#method.evcxr_display)
     pub fn head_ecvxr(&self, n: usize) {
-        self.block.head_evcxr(n)
+        self.evcxr_display_rows(0, n.min(self.len()))
+    }
+    /// Returns `true` if the DataFrame contains no rows
+    pub fn is_empty(&self) -> bool {
+        self.block.len() == 0
+    }
+    /// Returns the number of rows in the DataFrame
+    pub fn len(&self) -> usize {
+        self.block.len()
     }
     /// Replace values where condition is True
     /// # Syntax
@@ -302,6 +409,26 @@ impl DataFrame {
     {
         self.block.mask(value, cond)
     }
+    /// Remove a column from the DataFrame and return it as a Series
+    ///
+    /// # Returns
+    /// * `Some(Series<T>)`: If the column exists and is of type `T`
+    /// * `None`: Otherwise, the DataFrame is left unchanged
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use ndarray::Array2;
+    /// let mut df = DataFrame::from(Array2::<f64>::ones((3, 2)));
+    /// let series = df.pop::<f64>("0").unwrap();
+    /// assert_eq!(series.len(), 3);
+    /// assert_eq!(df.columns(), vec!["1".to_string()]);
+    /// ```
+    pub fn pop<T>(&mut self, col: &str) -> Option<Series<T>>
+    where
+        T: Clone + Default + 'static,
+    {
+        self.block.pop(col)
+    }
     /// Prints the last `n` elements of the DataFrame
     /// # Panics
     /// If `n` is larger than items in the DataFrame
@@ -330,9 +457,12 @@ impl DataFrame {
     pub fn tail(&self, n: usize) {
         self.block.tail(n);
     }
-    /// Similar to [`tail`](#method.tail) but prints formatted output in a evcxr environment.
+    /// Similar to [`tail`](#method.tail) but shows a styled HTML table in an evcxr environment.
+    ///
+    /// Every row is shown if `n` is greater than the number of rows.
+    /// See [`evcxr_display`](#method.evcxr_display)
     pub fn tail_evcxr(&self, n: usize) {
-        self.block.tail_evcxr(n)
+        self.evcxr_display_rows(self.len().saturating_sub(n), self.len())
     }
     /// Call `func` on the DataFrame. Producing a DataFrame with transformed values
     ///
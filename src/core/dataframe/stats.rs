@@ -177,63 +177,3 @@ impl DataFrame {
         self.block.cov(min_periods)
     }
 }
-
-impl DataFrame {
-    /// # Requires Feature
-    ///  > * `stats`
-    ///
-    /// Plot data in the series
-    ///
-    /// This function provides higher order methods for plotting data in a series
-    /// The series should contain numerical data (usize, floats,ints) ie anything implementing the Num trait
-    ///
-    /// # Arguments
-    /// * `kind`: The type of plot to draw.
-    /// > Supported plots are
-    /// > > * "hist"-> histogram
-    ///
-    /// > > * "h_hist" -> horizontal histogram
-    ///
-    /// > > * "bar"->bar graph
-    ///
-    /// > > * "box"->box plot
-    ///
-    /// > > * "scatter" -> scatter graph
-    ///
-    /// > > * "line" -> line graph
-    ///
-    /// If the string passed to `kind` argument doesn't match the above values. A line plot is drown
-    ///
-    /// # Note
-    /// This is backed by [plotly.js] using the [plotly] crate, so the resulting graph is opened in
-    /// your default browser.
-    ///
-    /// [plotly.js]: https://plot.ly/javascript/
-    /// [plotly]: https://docs.rs/plotly
-    pub fn plot(&self, kind: &str) {
-        self.block.plot(kind)
-    }
-    /// Plot a graph into a jupyter notebook using rust repl environment which can be downloaded and installed
-    /// from  [here](https://github.com/google/evcxr)
-    ///
-    /// Plots supported are the same as `plot()` method
-    ///
-    /// # READ THIS!
-    /// You should have already `set up a conda environment` this [website](https://shahinrostami.com/posts/programming/rust-notebooks/setup-anaconda-jupyter-and-rust/)
-    /// has some nice instructions on how to do this.
-    ///
-    /// > This embeds a whole HTML file ( about 10kb) to the notebook. And this grows linearly for every graph rendered.
-    ///
-    /// To make it work run
-    /// ```bash
-    /// $ jupyter labextension install jupyterlab-plotly@4.8.2
-    /// ```
-    /// to install plotly extension in your `conda environment`
-    /// # Tip
-    /// This is a large library and pulls in a lot of dependencies. It takes long to compile
-    /// so it is recommended that you use your jupyter environment configured to use sccache.
-    /// Instructions for setting that up can be found [here](https://github.com/google/evcxr/tree/master/evcxr_jupyter)
-    pub fn plot_evcxr(&self, kind: &str) {
-        self.block.plot_evcxr(kind)
-    }
-}
@@ -0,0 +1,180 @@
+//! Options controlling how DataFrames and Series are printed
+//!
+//! The options set by [`set_options`] are used by every thread, [`with_options`] overrides them on
+//! the current thread while a closure runs. They are honoured by the `Display` and `Debug`
+//! implementations of DataFrames and Series and by the styled HTML tables shown by evcxr in
+//! Jupyter notebooks, see `DataFrame::evcxr_display` and `Series::evcxr_display`.
+//!
+//! # Example
+//! ```
+//! use dami::core::display::{self, Options};
+//! use dami::prelude::*;
+//! use ndarray::Array2;
+//!
+//! let df = DataFrame::from(Array2::<f64>::zeros((100, 40)));
+//! let mut options = Options::new();
+//! options.set_max_rows(Some(6)).set_max_columns(Some(4)).set_precision(1);
+//! display::with_options(options, || {
+//!     let text = df.to_string();
+//!     assert!(text.contains("0.0"));
+//!     assert!(text.ends_with("[100 rows x 40 columns]"));
+//! });
+//! ```
+//!
+//! [`set_options`]: fn.set_options.html
+//! [`with_options`]: fn.with_options.html
+use std::cell::RefCell;
+use std::sync::RwLock;
+
+mod evcxr;
+
+lazy_static! {
+    static ref OPTIONS: RwLock<Options> = RwLock::new(Options::default());
+}
+thread_local! {
+    static LOCAL_OPTIONS: RefCell<Option<Options>> = RefCell::new(None);
+}
+
+/// Display options for DataFrames and Series
+///
+/// The defaults are
+/// * `max_rows`: `Some(10)`, longer frames show their first and last five rows
+/// * `max_columns`: `Some(20)`, wider frames show their first and last ten columns
+/// * `precision`: `3` digits after the decimal point of floats, the precision DataFrames have always
+///  been printed with. Series used to be printed with `4` digits, set the precision to `4` to keep
+///  printing them that way
+/// * `max_colwidth`: `Some(30)` characters, longer strings are cut and end with `...`
+/// * `show_dtypes`: `false`, `Debug` always shows the dtypes
+/// * `show_index`: `true`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Options {
+    max_rows: Option<usize>,
+    max_columns: Option<usize>,
+    precision: usize,
+    max_colwidth: Option<usize>,
+    show_dtypes: bool,
+    show_index: bool,
+}
+impl Default for Options {
+    fn default() -> Self {
+        Options {
+            max_rows: Some(10),
+            max_columns: Some(20),
+            precision: 3,
+            max_colwidth: Some(30),
+            show_dtypes: false,
+            show_index: true,
+        }
+    }
+}
+impl Options {
+    /// Create the default display options
+    pub fn new() -> Options {
+        Self::default()
+    }
+    /// The maximum number of rows printed before the middle rows are elided, `None` prints all rows
+    pub const fn max_rows(&self) -> Option<usize> {
+        self.max_rows
+    }
+    /// Set the maximum number of rows printed before the middle rows are elided
+    pub fn set_max_rows(&mut self, rows: Option<usize>) -> &mut Self {
+        self.max_rows = rows;
+        self
+    }
+    /// The maximum number of columns printed before the middle columns are elided, `None` prints
+    /// all columns
+    pub const fn max_columns(&self) -> Option<usize> {
+        self.max_columns
+    }
+    /// Set the maximum number of columns printed before the middle columns are elided
+    pub fn set_max_columns(&mut self, columns: Option<usize>) -> &mut Self {
+        self.max_columns = columns;
+        self
+    }
+    /// The number of digits printed after the decimal point of floats
+    pub const fn precision(&self) -> usize {
+        self.precision
+    }
+    /// Set the number of digits printed after the decimal point of floats
+    pub fn set_precision(&mut self, precision: usize) -> &mut Self {
+        self.precision = precision;
+        self
+    }
+    /// The maximum number of characters of a value, longer values are cut and end with `...`
+    pub const fn max_colwidth(&self) -> Option<usize> {
+        self.max_colwidth
+    }
+    /// Set the maximum number of characters of a value, `None` never cuts values
+    pub fn set_max_colwidth(&mut self, width: Option<usize>) -> &mut Self {
+        self.max_colwidth = width;
+        self
+    }
+    /// Whether `Display` adds a row with the dtype of each column
+    pub const fn show_dtypes(&self) -> bool {
+        self.show_dtypes
+    }
+    /// Set whether `Display` adds a row with the dtype of each column
+    pub fn set_show_dtypes(&mut self, show: bool) -> &mut Self {
+        self.show_dtypes = show;
+        self
+    }
+    /// Whether the row labels are printed
+    pub const fn show_index(&self) -> bool {
+        self.show_index
+    }
+    /// Set whether the row labels are printed
+    pub fn set_show_index(&mut self, show: bool) -> &mut Self {
+        self.show_index = show;
+        self
+    }
+}
+/// Set the display options used by every thread
+pub fn set_options(options: Options) {
+    *OPTIONS.write().unwrap() = options;
+}
+/// Restore the default display options
+pub fn reset_options() {
+    set_options(Options::default());
+}
+/// The display options of the current thread
+///
+/// These are the options passed to [`with_options`](fn.with_options.html) when called inside its
+/// closure, otherwise the options set by [`set_options`](fn.set_options.html)
+pub fn options() -> Options {
+    LOCAL_OPTIONS
+        .with(|local| local.borrow().clone())
+        .unwrap_or_else(|| OPTIONS.read().unwrap().clone())
+}
+/// Run `func` with `options` as the display options of the current thread
+///
+/// The previous options are restored when `func` returns, other threads are not affected
+pub fn with_options<R, F: FnOnce() -> R>(options: Options, func: F) -> R {
+    /// Restores the previous options even if `func` panics
+    struct Restore(Option<Options>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            LOCAL_OPTIONS.with(|local| *local.borrow_mut() = previous);
+        }
+    }
+    let _restore = Restore(LOCAL_OPTIONS.with(|local| local.borrow_mut().replace(options)));
+    func()
+}
+/// The positions shown out of `len` rows or columns, `None` marks the place of the elided ones
+///
+/// Everything is shown if there are at most `max` items otherwise `max` items split between the
+/// start and the end
+pub(crate) fn visible(len: usize, max: Option<usize>) -> Vec<Option<usize>> {
+    match max {
+        Some(max) if len > max => {
+            let head = (max + 1) / 2;
+            let tail = max / 2;
+            (0..head)
+                .map(Some)
+                .chain(std::iter::once(None))
+                .chain((len - tail..len).map(Some))
+                .collect()
+        }
+        _ => (0..len).map(Some).collect(),
+    }
+}
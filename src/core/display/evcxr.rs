@@ -0,0 +1,250 @@
+//! Styled HTML tables shown by [evcxr](https://github.com/google/evcxr) in Jupyter notebooks
+//!
+//! evcxr calls the `evcxr_display` method of a value when it is the result of a cell, these tables
+//! follow the display options and mark the dtype of each column, elided rows and columns and
+//! missing values.
+use crate::core::block_manager::CellFormat;
+use crate::core::dataframe::DataFrame;
+use crate::core::display::{self, visible};
+use crate::core::index::date_time::format_timestamp;
+use crate::core::series::Series;
+use crate::enums::DataTypes;
+use crate::io::html::escape_html;
+use std::any::Any;
+use std::fmt;
+use std::fmt::Write;
+
+/// Styles of the tables, scoped to the `dami` class so the notebook is not affected
+const STYLE: &str = "<style>
+.dami{border-collapse:collapse;font-size:12px;font-family:sans-serif}
+.dami th,.dami td{padding:4px 8px;border-bottom:1px solid #e0e0e0;text-align:left;white-space:nowrap}
+.dami thead th{border-bottom:2px solid #9e9e9e;vertical-align:bottom}
+.dami tbody th{color:#616161;font-weight:bold}
+.dami tbody tr:hover{background:#f5f5f5}
+.dami td.dami-num{text-align:right;font-variant-numeric:tabular-nums}
+.dami td.dami-missing{color:#c62828;background:#ffebee;font-style:italic}
+.dami tr.dami-elided td,.dami tr.dami-elided th,.dami .dami-elided{color:#9e9e9e;text-align:center}
+.dami-dtype{display:inline-block;margin-top:2px;padding:0 4px;border-radius:3px;font-size:10px;font-weight:normal;color:#fff}
+.dami-dtype-num{background:#1565c0}
+.dami-dtype-bool{background:#6a1b9a}
+.dami-dtype-str{background:#2e7d32}
+.dami-dtype-datetime{background:#ef6c00}
+.dami-dtype-object{background:#616161}
+.dami-shape{margin:4px 0;color:#616161;font-size:11px;font-family:sans-serif}
+</style>
+";
+
+/// Whether values of `dtype` are right aligned
+fn is_numeric(dtype: &DataTypes) -> bool {
+    matches!(
+        dtype,
+        DataTypes::I32 | DataTypes::I64 | DataTypes::F32 | DataTypes::F64
+    )
+}
+/// A badge showing `dtype`
+fn badge(dtype: &DataTypes) -> String {
+    let kind = match dtype {
+        DataTypes::I32 | DataTypes::I64 | DataTypes::F32 | DataTypes::F64 => "num",
+        DataTypes::BOOL => "bool",
+        DataTypes::STRING | DataTypes::STR => "str",
+        DataTypes::DATETIME => "datetime",
+        DataTypes::OBJECT => "object",
+    };
+    format!(
+        "<span class=\"dami-dtype dami-dtype-{}\">{:?}</span>",
+        kind, dtype
+    )
+}
+/// A `<td>` holding `value` formatted for `dtype`, missing values are highlighted
+fn cell(html: &mut String, value: &str, dtype: &DataTypes) {
+    let missing = match dtype {
+        DataTypes::F32 | DataTypes::F64 => value == "NaN",
+        DataTypes::DATETIME => value == "NaT",
+        _ => false,
+    };
+    let class = if missing {
+        " class=\"dami-missing\""
+    } else if is_numeric(dtype) {
+        " class=\"dami-num\""
+    } else {
+        ""
+    };
+    writeln!(html, "      <td{}>{}</td>", class, escape_html(value)).unwrap();
+}
+/// Print `html` between the markers evcxr looks for
+fn print_html(html: &str) {
+    println!("EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT", html);
+}
+
+impl DataFrame {
+    /// Render the DataFrame as the styled HTML table shown in evcxr
+    ///
+    /// Rows and columns are elided and values formatted following the
+    /// [display options](../display/index.html). The dtype of each column is shown under its
+    /// name, numbers are right aligned and `NaN`s and missing datetimes are highlighted
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// let df = DataFrame::from(ndarray::arr2(&[[1.5, f64::NAN]]));
+    /// let html = df.to_styled_html();
+    /// assert!(html.contains("<td class=\"dami-num\">1.500</td>"));
+    /// assert!(html.contains("<td class=\"dami-missing\">NaN</td>"));
+    /// ```
+    pub fn to_styled_html(&self) -> String {
+        self.styled_html(&self.display_rows())
+    }
+    /// Show the DataFrame as a styled HTML table in an evcxr notebook
+    ///
+    /// evcxr calls this when a DataFrame is the result of a cell, so the results of methods
+    /// like [`corr`](#method.corr) are shown as tables too. See
+    /// [`to_styled_html`](#method.to_styled_html)
+    pub fn evcxr_display(&self) {
+        print_html(&self.to_styled_html());
+    }
+    /// Show the rows from `start` to `end` as a styled HTML table in an evcxr notebook
+    pub(crate) fn evcxr_display_rows(&self, start: usize, end: usize) {
+        let rows = (start..end).map(Some).collect::<Vec<Option<usize>>>();
+        print_html(&self.styled_html(&rows));
+    }
+    /// Render `rows` as a styled HTML table, `None` marks the place of elided rows
+    fn styled_html(&self, rows: &[Option<usize>]) -> String {
+        let options = display::options();
+        let format = CellFormat::default();
+        let names = self.columns();
+        let dtypes = self.dtypes();
+        let columns = visible(names.len(), options.max_columns());
+        let mut html = String::from("<div>\n");
+        html.push_str(STYLE);
+        html.push_str("<table class=\"dataframe dami\">\n  <thead>\n    <tr>\n");
+        if options.show_index() {
+            html.push_str("      <th></th>\n");
+        }
+        for column in &columns {
+            match column {
+                Some(j) => writeln!(
+                    html,
+                    "      <th>{}<br>{}</th>",
+                    escape_html(&names[*j]),
+                    badge(&dtypes[&names[*j]])
+                )
+                .unwrap(),
+                None => html.push_str("      <th class=\"dami-elided\">&hellip;</th>\n"),
+            }
+        }
+        html.push_str("    </tr>\n  </thead>\n  <tbody>\n");
+        for row in rows {
+            match row {
+                Some(i) => {
+                    let values = self.format_row(*i, &format);
+                    html.push_str("    <tr>\n");
+                    if options.show_index() {
+                        writeln!(html, "      <th>{}</th>", escape_html(&values[0])).unwrap();
+                    }
+                    for column in &columns {
+                        match column {
+                            Some(j) => cell(&mut html, &values[j + 1], &dtypes[&names[*j]]),
+                            None => {
+                                html.push_str("      <td class=\"dami-elided\">&hellip;</td>\n")
+                            }
+                        }
+                    }
+                    html.push_str("    </tr>\n");
+                }
+                None => {
+                    html.push_str("    <tr class=\"dami-elided\">\n");
+                    let width = columns.len() + usize::from(options.show_index());
+                    for _ in 0..width {
+                        html.push_str("      <td>&vellip;</td>\n");
+                    }
+                    html.push_str("    </tr>\n");
+                }
+            }
+        }
+        html.push_str("  </tbody>\n</table>\n");
+        writeln!(
+            html,
+            "<p class=\"dami-shape\">{} rows &times; {} columns</p>",
+            self.len(),
+            names.len()
+        )
+        .unwrap();
+        html.push_str("</div>");
+        html
+    }
+}
+
+impl<T: fmt::Debug + Clone + Default + 'static> Series<T> {
+    /// Render the Series as the styled HTML table shown in evcxr
+    ///
+    /// Values are elided and formatted following the [display options](../display/index.html),
+    /// the dtype is shown under the name of the Series, numbers are right aligned, datetimes are
+    /// formatted as dates and `NaN`s and `NaT`s are highlighted
+    /// # Example
+    /// ```
+    /// use dami::core::index::date_time::NAT;
+    /// use dami::enums::DataTypes;
+    /// use dami::prelude::*;
+    /// let series = Series::from(vec![1.0, f64::NAN]);
+    /// let html = series.to_styled_html();
+    /// assert!(html.contains("<td class=\"dami-num\">1.000</td>"));
+    /// assert!(html.contains("<td class=\"dami-missing\">NaN</td>"));
+    ///
+    /// let mut dates = Series::from(vec![0_i64, NAT]);
+    /// dates.set_dtype(DataTypes::DATETIME);
+    /// let html = dates.to_styled_html();
+    /// assert!(html.contains("<td>1970-01-01 00:00:00</td>"));
+    /// assert!(html.contains("<td class=\"dami-missing\">NaT</td>"));
+    /// ```
+    pub fn to_styled_html(&self) -> String {
+        let options = display::options();
+        let format = CellFormat::default();
+        let dtype = self.get_dtype();
+        let index = self.get_index();
+        let mut html = String::from("<div>\n");
+        html.push_str(STYLE);
+        html.push_str("<table class=\"dataframe dami\">\n  <thead>\n    <tr>\n");
+        if options.show_index() {
+            html.push_str("      <th></th>\n");
+        }
+        writeln!(
+            html,
+            "      <th>{}<br>{}</th>",
+            escape_html(&self.get_name()),
+            badge(&dtype)
+        )
+        .unwrap();
+        html.push_str("    </tr>\n  </thead>\n  <tbody>\n");
+        for row in visible(self.len(), options.max_rows()) {
+            if let Some(i) = row {
+                html.push_str("    <tr>\n");
+                if options.show_index() {
+                    writeln!(html, "      <th>{}</th>", escape_html(&index[i])).unwrap();
+                }
+                let value: &dyn Any = &self[i];
+                let value = match value.downcast_ref::<i64>() {
+                    Some(timestamp) if dtype == DataTypes::DATETIME => format_timestamp(*timestamp),
+                    _ => format!("{:.*?}", options.precision(), &self[i]),
+                };
+                cell(&mut html, &format.string(&value), &dtype);
+                html.push_str("    </tr>\n");
+            } else {
+                html.push_str("    <tr class=\"dami-elided\">\n");
+                if options.show_index() {
+                    html.push_str("      <td>&vellip;</td>\n");
+                }
+                html.push_str("      <td>&vellip;</td>\n    </tr>\n");
+            }
+        }
+        html.push_str("  </tbody>\n</table>\n");
+        writeln!(html, "<p class=\"dami-shape\">length {}</p>", self.len()).unwrap();
+        html.push_str("</div>");
+        html
+    }
+    /// Show the Series as a styled HTML table in an evcxr notebook
+    ///
+    /// evcxr calls this when a Series is the result of a cell, so the results of methods like
+    /// `describe` are shown as tables too. See [`to_styled_html`](#method.to_styled_html)
+    pub fn evcxr_display(&self) {
+        print_html(&self.to_styled_html());
+    }
+}
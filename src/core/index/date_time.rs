@@ -1,11 +1,62 @@
 #![allow(dead_code)]
 //! The DateTimeIndex module
-use chrono::{DateTime, Datelike, NaiveDateTime};
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime};
 use ndarray::Array1;
 use prettytable::format::consts::FORMAT_CLEAN;
 use prettytable::{Cell, Row, Table};
 use std::fmt;
 use std::ops::{Index, IndexMut};
+/// Sentinel used for missing (Not a Time) values in datetime Series and indexes
+pub const NAT: i64 = i64::MIN;
+/// Format seconds elapsed since the epoch as a `%Y-%m-%d %H:%M:%S` string
+///
+/// [`NAT`](constant.NAT.html) values are formatted as `NaT` and timestamps outside the range
+/// of dates chrono can represent are formatted as the raw number of seconds
+/// # Example
+/// ```
+/// use dami::core::index::date_time::{format_timestamp, NAT};
+/// assert_eq!(format_timestamp(0), "1970-01-01 00:00:00");
+/// assert_eq!(format_timestamp(NAT), "NaT");
+/// assert_eq!(format_timestamp(i64::MAX), i64::MAX.to_string());
+/// ```
+pub fn format_timestamp(timestamp: i64) -> String {
+    if timestamp == NAT {
+        return "NaT".to_string();
+    }
+    NaiveDateTime::from_timestamp_opt(timestamp, 0).map_or_else(
+        || timestamp.to_string(),
+        |date| date.format("%Y-%m-%d %H:%M:%S").to_string(),
+    )
+}
+/// Formats tried by [`parse_timestamp`](fn.parse_timestamp.html) in order
+const DATETIME_FORMATS: [&str; 4] = [
+    "%Y-%m-%d %H:%M:%S%.f",
+    "%Y-%m-%dT%H:%M:%S%.f",
+    "%Y-%m-%d %H:%M",
+    "%Y/%m/%d %H:%M:%S",
+];
+const DATE_FORMATS: [&str; 3] = ["%Y-%m-%d", "%Y/%m/%d", "%d.%m.%Y"];
+/// Parse a date like string into seconds elapsed since the unix epoch
+///
+/// RFC 3339 strings and the common `YYYY-MM-DD[ HH:MM[:SS]]` layouts are understood, strings
+/// that cannot be parsed return [`NAT`](constant.NAT.html)
+pub fn parse_timestamp(value: &str) -> i64 {
+    let value = value.trim();
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return date.timestamp();
+    }
+    for fmt in &DATETIME_FORMATS {
+        if let Ok(date) = NaiveDateTime::parse_from_str(value, fmt) {
+            return date.timestamp();
+        }
+    }
+    for fmt in &DATE_FORMATS {
+        if let Ok(date) = NaiveDate::parse_from_str(value, fmt) {
+            return date.and_hms(0, 0, 0).timestamp();
+        }
+    }
+    NAT
+}
 /// A struct that holds Date and Time indexes
 pub struct DateTimeIndex {
     index: Array1<i64>,
@@ -0,0 +1,780 @@
+//! Build DataFrames from Rust structs and read rows back into structs
+//!
+//! There are two ways to move between structs and DataFrames
+//! * Through serde: [`DataFrame::from_records`] accepts any `Serialize` struct and
+//! [`DataFrame::to_records`] and [`DataFrame::rows`] deserialize rows into any `Deserialize` struct
+//! * Through the [`DataFrameRow`] trait: with the `derive` feature `#[derive(DataFrameRow)]` generates a
+//! [`ColumnBuilder`] which pushes fields straight into typed columns without going through serde,
+//! rows are added using [`DataFrame::from_rows`]
+//!
+//! Each field of the struct becomes a column named after the field, in declaration order.
+//!
+//! [`DataFrame::from_records`]: ../dataframe/struct.DataFrame.html#method.from_records
+//! [`DataFrame::to_records`]: ../dataframe/struct.DataFrame.html#method.to_records
+//! [`DataFrame::rows`]: ../dataframe/struct.DataFrame.html#method.rows
+//! [`DataFrame::from_rows`]: ../dataframe/struct.DataFrame.html#method.from_rows
+//! [`DataFrameRow`]: trait.DataFrameRow.html
+//! [`ColumnBuilder`]: trait.ColumnBuilder.html
+//! [`ColumnType`]: trait.ColumnType.html
+use crate::core::dataframe::DataFrame;
+use crate::core::index::date_time::{format_timestamp, NAT};
+use crate::core::series::Series;
+use crate::enums::{DataFrameErrors, DataTypes};
+use serde::de::value::{Error as ValueError, MapDeserializer, StringDeserializer};
+use serde::de::{DeserializeOwned, Deserializer, IntoDeserializer, Visitor};
+use serde::ser::{Error as SerError, Impossible, SerializeStruct, Serializer};
+use serde::{forward_to_deserialize_any, Serialize};
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Errors that may occur when converting between structs and DataFrames
+pub enum RecordErrors {
+    /// A record could not be serialized or a row could not be deserialized
+    Serde(ValueError),
+    /// A field holds values of types that cannot share a column, eg strings and numbers
+    MixedTypes(String),
+    /// The columns could not be added to a DataFrame
+    DataFrame(DataFrameErrors),
+}
+impl fmt::Debug for RecordErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serde(err) => write!(f, "{}", err),
+            Self::MixedTypes(field) => write!(
+                f,
+                "Field {} holds values of different types which cannot be stored in one column",
+                field
+            ),
+            Self::DataFrame(err) => write!(f, "{:?}", err),
+        }
+    }
+}
+impl From<ValueError> for RecordErrors {
+    fn from(err: ValueError) -> Self {
+        Self::Serde(err)
+    }
+}
+impl From<DataFrameErrors> for RecordErrors {
+    fn from(err: DataFrameErrors) -> Self {
+        Self::DataFrame(err)
+    }
+}
+
+/// Types whose values can be added to a DataFrame as rows
+///
+/// This is implemented by `#[derive(DataFrameRow)]` (requires the `derive` feature) for structs with
+/// named fields, every field type must implement [`ColumnType`]
+#[cfg_attr(
+    feature = "derive",
+    doc = r#"
+# Example
+```
+use dami::prelude::*;
+
+#[derive(DataFrameRow)]
+struct Trade {
+    ticker: String,
+    price: f64,
+    volume: i64,
+}
+let df = DataFrame::from_rows(vec![
+    Trade { ticker: "ABC".to_string(), price: 10.5, volume: 100 },
+    Trade { ticker: "XYZ".to_string(), price: 3.25, volume: 40 },
+])
+.unwrap();
+assert_eq!(df.columns(), vec!["ticker", "price", "volume"]);
+```
+"#
+)]
+pub trait DataFrameRow: Sized {
+    /// The builder collecting rows of this type into columns
+    type Builder: ColumnBuilder<Self> + Default;
+}
+/// Field types `#[derive(DataFrameRow)]` can store in a typed column
+///
+/// Other types would be stored in an `object` Series which a DataFrame cannot hold, so the derive
+/// rejects them at compile time
+pub trait ColumnType: Clone + Default + 'static {}
+impl ColumnType for bool {}
+impl ColumnType for i32 {}
+impl ColumnType for i64 {}
+impl ColumnType for f32 {}
+impl ColumnType for f64 {}
+impl ColumnType for String {}
+impl ColumnType for &'static str {}
+/// Collects rows into one typed column per field
+pub trait ColumnBuilder<R> {
+    /// Push the fields of `row` to the end of their columns
+    fn push(&mut self, row: R);
+    /// Create a DataFrame from the columns
+    /// # Errors
+    /// * If the columns cannot be added to a DataFrame
+    fn finish(self) -> Result<DataFrame, DataFrameErrors>;
+}
+
+/// The value of a field or of a cell of a DataFrame
+#[derive(Clone, Debug)]
+enum Cell {
+    Bool(bool),
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    Str(String),
+    DateTime(i64),
+    Null,
+}
+
+/// Serializes a single field value into a cell
+struct CellSerializer;
+
+fn unsupported(kind: &str) -> ValueError {
+    ValueError::custom(format!(
+        "{} values cannot be stored in a DataFrame column",
+        kind
+    ))
+}
+
+impl Serializer for CellSerializer {
+    type Ok = Cell;
+    type Error = ValueError;
+    type SerializeSeq = Impossible<Cell, ValueError>;
+    type SerializeTuple = Impossible<Cell, ValueError>;
+    type SerializeTupleStruct = Impossible<Cell, ValueError>;
+    type SerializeTupleVariant = Impossible<Cell, ValueError>;
+    type SerializeMap = Impossible<Cell, ValueError>;
+    type SerializeStruct = Impossible<Cell, ValueError>;
+    type SerializeStructVariant = Impossible<Cell, ValueError>;
+
+    fn serialize_bool(self, v: bool) -> Result<Cell, ValueError> {
+        Ok(Cell::Bool(v))
+    }
+    fn serialize_i8(self, v: i8) -> Result<Cell, ValueError> {
+        Ok(Cell::I32(i32::from(v)))
+    }
+    fn serialize_i16(self, v: i16) -> Result<Cell, ValueError> {
+        Ok(Cell::I32(i32::from(v)))
+    }
+    fn serialize_i32(self, v: i32) -> Result<Cell, ValueError> {
+        Ok(Cell::I32(v))
+    }
+    fn serialize_i64(self, v: i64) -> Result<Cell, ValueError> {
+        Ok(Cell::I64(v))
+    }
+    fn serialize_u8(self, v: u8) -> Result<Cell, ValueError> {
+        Ok(Cell::I32(i32::from(v)))
+    }
+    fn serialize_u16(self, v: u16) -> Result<Cell, ValueError> {
+        Ok(Cell::I32(i32::from(v)))
+    }
+    fn serialize_u32(self, v: u32) -> Result<Cell, ValueError> {
+        Ok(Cell::I64(i64::from(v)))
+    }
+    fn serialize_u64(self, v: u64) -> Result<Cell, ValueError> {
+        i64::try_from(v)
+            .map(Cell::I64)
+            .map_err(|_| ValueError::custom(format!("{} does not fit in an i64 column", v)))
+    }
+    fn serialize_f32(self, v: f32) -> Result<Cell, ValueError> {
+        Ok(Cell::F32(v))
+    }
+    fn serialize_f64(self, v: f64) -> Result<Cell, ValueError> {
+        Ok(Cell::F64(v))
+    }
+    fn serialize_char(self, v: char) -> Result<Cell, ValueError> {
+        Ok(Cell::Str(v.to_string()))
+    }
+    fn serialize_str(self, v: &str) -> Result<Cell, ValueError> {
+        Ok(Cell::Str(v.to_string()))
+    }
+    fn serialize_bytes(self, _: &[u8]) -> Result<Cell, ValueError> {
+        Err(unsupported("Byte"))
+    }
+    fn serialize_none(self) -> Result<Cell, ValueError> {
+        Ok(Cell::Null)
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Cell, ValueError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Cell, ValueError> {
+        Ok(Cell::Null)
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Cell, ValueError> {
+        Ok(Cell::Null)
+    }
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<Cell, ValueError> {
+        Ok(Cell::Str(variant.to_string()))
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Cell, ValueError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Cell, ValueError> {
+        Err(unsupported("Enum"))
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, ValueError> {
+        Err(unsupported("Sequence"))
+    }
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, ValueError> {
+        Err(unsupported("Tuple"))
+    }
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, ValueError> {
+        Err(unsupported("Tuple struct"))
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, ValueError> {
+        Err(unsupported("Enum"))
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, ValueError> {
+        Err(unsupported("Map"))
+    }
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStruct, ValueError> {
+        Err(unsupported("Nested struct"))
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, ValueError> {
+        Err(unsupported("Enum"))
+    }
+}
+
+/// Serializes a record (a struct with named fields) into its field names and cells
+struct RecordSerializer;
+
+/// The fields of a record collected by [`RecordSerializer`]
+struct RecordFields {
+    fields: Vec<(&'static str, Cell)>,
+}
+impl SerializeStruct for RecordFields {
+    type Ok = Vec<(&'static str, Cell)>;
+    type Error = ValueError;
+    fn serialize_field<T: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), ValueError> {
+        self.fields.push((key, value.serialize(CellSerializer)?));
+        Ok(())
+    }
+    fn end(self) -> Result<Self::Ok, ValueError> {
+        Ok(self.fields)
+    }
+}
+
+fn not_a_record() -> ValueError {
+    ValueError::custom("Records must be structs with named fields")
+}
+
+impl Serializer for RecordSerializer {
+    type Ok = Vec<(&'static str, Cell)>;
+    type Error = ValueError;
+    type SerializeSeq = Impossible<Self::Ok, ValueError>;
+    type SerializeTuple = Impossible<Self::Ok, ValueError>;
+    type SerializeTupleStruct = Impossible<Self::Ok, ValueError>;
+    type SerializeTupleVariant = Impossible<Self::Ok, ValueError>;
+    type SerializeMap = Impossible<Self::Ok, ValueError>;
+    type SerializeStruct = RecordFields;
+    type SerializeStructVariant = Impossible<Self::Ok, ValueError>;
+
+    fn serialize_bool(self, _: bool) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_i8(self, _: i8) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_i16(self, _: i16) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_i32(self, _: i32) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_i64(self, _: i64) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_u8(self, _: u8) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_u16(self, _: u16) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_u32(self, _: u32) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_u64(self, _: u64) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_f32(self, _: f32) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_f64(self, _: f64) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_char(self, _: char) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_str(self, _: &str) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_bytes(self, _: &[u8]) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_none(self) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_some<T: ?Sized + Serialize>(self, value: &T) -> Result<Self::Ok, ValueError> {
+        value.serialize(self)
+    }
+    fn serialize_unit(self) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+    ) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_newtype_struct<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &T,
+    ) -> Result<Self::Ok, ValueError> {
+        value.serialize(self)
+    }
+    fn serialize_newtype_variant<T: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: &T,
+    ) -> Result<Self::Ok, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleStruct, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_tuple_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeTupleVariant, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, ValueError> {
+        Err(not_a_record())
+    }
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, ValueError> {
+        Ok(RecordFields {
+            fields: Vec::with_capacity(len),
+        })
+    }
+    fn serialize_struct_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        _: &'static str,
+        _: usize,
+    ) -> Result<Self::SerializeStructVariant, ValueError> {
+        Err(not_a_record())
+    }
+}
+
+/// Deserializes a cell into a field value
+struct CellDeserializer(Cell);
+
+impl IntoDeserializer<'_, ValueError> for Cell {
+    type Deserializer = CellDeserializer;
+    fn into_deserializer(self) -> CellDeserializer {
+        CellDeserializer(self)
+    }
+}
+impl<'de> Deserializer<'de> for CellDeserializer {
+    type Error = ValueError;
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        match self.0 {
+            Cell::Bool(v) => visitor.visit_bool(v),
+            Cell::I32(v) => visitor.visit_i32(v),
+            Cell::I64(v) => visitor.visit_i64(v),
+            Cell::F32(v) => visitor.visit_f32(v),
+            Cell::F64(v) => visitor.visit_f64(v),
+            Cell::Str(v) => visitor.visit_string(v),
+            Cell::DateTime(NAT) | Cell::Null => visitor.visit_none(),
+            Cell::DateTime(v) => visitor.visit_string(format_timestamp(v)),
+        }
+    }
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, ValueError> {
+        match self.0 {
+            Cell::Null | Cell::DateTime(NAT) => visitor.visit_none(),
+            Cell::F32(v) if v.is_nan() => visitor.visit_none(),
+            Cell::F64(v) if v.is_nan() => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, ValueError> {
+        match self.0 {
+            Cell::Str(v) => {
+                let deserializer: StringDeserializer<ValueError> = v.into_deserializer();
+                deserializer.deserialize_enum(name, variants, visitor)
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, ValueError> {
+        visitor.visit_newtype_struct(self)
+    }
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Turn the cells of a field into a column of `df`
+#[allow(clippy::cast_precision_loss)]
+fn add_cells(df: &mut DataFrame, name: &str, cells: Vec<Cell>) -> Result<(), RecordErrors> {
+    let (mut bools, mut ints, mut longs, mut floats, mut doubles, mut strings, mut nulls) =
+        (false, false, false, false, false, false, false);
+    for cell in &cells {
+        match cell {
+            Cell::Bool(_) => bools = true,
+            Cell::I32(_) => ints = true,
+            Cell::I64(_) | Cell::DateTime(_) => longs = true,
+            Cell::F32(_) => floats = true,
+            Cell::F64(_) => doubles = true,
+            Cell::Str(_) => strings = true,
+            Cell::Null => nulls = true,
+        }
+    }
+    let numeric = ints || longs || floats || doubles;
+    if (bools && (numeric || strings || nulls)) || (strings && numeric) {
+        return Err(RecordErrors::MixedTypes(name.to_string()));
+    }
+    macro_rules! add {
+        ($values:expr) => {{
+            let mut series = Series::from($values);
+            series.set_name(name);
+            df.add_series(series, true)?;
+        }};
+    }
+    if bools {
+        add!(cells
+            .into_iter()
+            .map(|f| matches!(f, Cell::Bool(true)))
+            .collect::<Vec<bool>>());
+    } else if strings {
+        // Missing strings are empty strings
+        add!(cells
+            .into_iter()
+            .map(|f| match f {
+                Cell::Str(v) => v,
+                _ => String::new(),
+            })
+            .collect::<Vec<String>>());
+    } else if floats && !(ints || longs || doubles) {
+        add!(cells
+            .into_iter()
+            .map(|f| match f {
+                Cell::F32(v) => v,
+                _ => f32::NAN,
+            })
+            .collect::<Vec<f32>>());
+    } else if ints && !(longs || doubles || floats || nulls) {
+        add!(cells
+            .into_iter()
+            .map(|f| match f {
+                Cell::I32(v) => v,
+                _ => 0,
+            })
+            .collect::<Vec<i32>>());
+    } else if (ints || longs) && !(doubles || floats || nulls) {
+        add!(cells
+            .into_iter()
+            .map(|f| match f {
+                Cell::I32(v) => i64::from(v),
+                Cell::I64(v) => v,
+                _ => 0,
+            })
+            .collect::<Vec<i64>>());
+    } else {
+        // Integers holding missing values and columns of missing values are floats
+        add!(cells
+            .into_iter()
+            .map(|f| match f {
+                Cell::I32(v) => f64::from(v),
+                Cell::I64(v) => v as f64,
+                Cell::F32(v) => f64::from(v),
+                Cell::F64(v) => v,
+                _ => f64::NAN,
+            })
+            .collect::<Vec<f64>>());
+    }
+    Ok(())
+}
+
+/// Read the columns of `df` into cells, `object` columns are skipped
+fn frame_cells(df: &DataFrame) -> Vec<(String, Vec<Cell>)> {
+    let dtypes = df.dtypes();
+    let mut columns = vec![];
+    for name in df.columns() {
+        let cells = match dtypes[&name] {
+            DataTypes::BOOL => df
+                .get::<bool>(&name)
+                .unwrap()
+                .into_iter()
+                .map(Cell::Bool)
+                .collect(),
+            DataTypes::I32 => df
+                .get::<i32>(&name)
+                .unwrap()
+                .into_iter()
+                .map(Cell::I32)
+                .collect(),
+            DataTypes::I64 => df
+                .get::<i64>(&name)
+                .unwrap()
+                .into_iter()
+                .map(Cell::I64)
+                .collect(),
+            DataTypes::F32 => df
+                .get::<f32>(&name)
+                .unwrap()
+                .into_iter()
+                .map(Cell::F32)
+                .collect(),
+            DataTypes::F64 => df
+                .get::<f64>(&name)
+                .unwrap()
+                .into_iter()
+                .map(Cell::F64)
+                .collect(),
+            DataTypes::STRING => df
+                .get::<String>(&name)
+                .unwrap()
+                .into_iter()
+                .map(Cell::Str)
+                .collect(),
+            DataTypes::STR => df
+                .get::<&str>(&name)
+                .unwrap()
+                .into_iter()
+                .map(|f| Cell::Str(f.to_string()))
+                .collect(),
+            DataTypes::DATETIME => df
+                .get::<i64>(&name)
+                .unwrap()
+                .into_iter()
+                .map(Cell::DateTime)
+                .collect(),
+            DataTypes::OBJECT => continue,
+        };
+        columns.push((name, cells));
+    }
+    columns
+}
+
+/// An iterator deserializing the rows of a DataFrame into `T`
+///
+/// Created by [`DataFrame::rows`](../dataframe/struct.DataFrame.html#method.rows)
+pub struct Rows<T> {
+    columns: Vec<(String, Vec<Cell>)>,
+    row: usize,
+    len: usize,
+    marker: PhantomData<T>,
+}
+impl<T: DeserializeOwned> Iterator for Rows<T> {
+    type Item = Result<T, RecordErrors>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.row >= self.len {
+            return None;
+        }
+        let row = self.row;
+        self.row += 1;
+        let fields = self
+            .columns
+            .iter()
+            .map(|(name, cells)| (name.clone(), cells[row].clone()));
+        let deserializer = MapDeserializer::<_, ValueError>::new(fields);
+        Some(T::deserialize(deserializer).map_err(RecordErrors::from))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let left = self.len - self.row;
+        (left, Some(left))
+    }
+}
+
+impl DataFrame {
+    /// Create a DataFrame from a list of structs
+    ///
+    /// Each field becomes a column named after the field in declaration order. Column dtypes follow
+    /// the field types, unsigned integers are widened, `Option` fields hold missing values
+    /// (`NaN` in numeric columns, empty strings in string columns, integers with missing values become
+    /// floats) and unit enum variants are stored as strings
+    /// # Errors
+    /// * `Serde`: If a record is not a struct with named fields or a field holds nested
+    /// structs, sequences, maps or enums with data
+    /// * `MixedTypes`: If values of a field cannot share a column eg strings and numbers
+    ///
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// struct Trade {
+    ///     ticker: String,
+    ///     price: f64,
+    ///     volume: Option<i64>,
+    /// }
+    /// let df = DataFrame::from_records(vec![
+    ///     Trade { ticker: "ABC".to_string(), price: 10.5, volume: Some(100) },
+    ///     Trade { ticker: "XYZ".to_string(), price: 3.25, volume: None },
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(df.columns(), vec!["ticker", "price", "volume"]);
+    /// ```
+    pub fn from_records<T: Serialize>(records: Vec<T>) -> Result<DataFrame, RecordErrors> {
+        let mut names: Vec<&'static str> = vec![];
+        let mut columns: Vec<Vec<Cell>> = vec![];
+        let rows = records.len();
+        for record in records {
+            let fields = record.serialize(RecordSerializer)?;
+            if columns.is_empty() {
+                names = fields.iter().map(|f| f.0).collect();
+                columns = vec![Vec::with_capacity(rows); names.len()];
+            }
+            if fields.len() != names.len() {
+                return Err(RecordErrors::Serde(ValueError::custom(
+                    "Records have different fields",
+                )));
+            }
+            for (column, (_, cell)) in columns.iter_mut().zip(fields) {
+                column.push(cell);
+            }
+        }
+        let mut df = DataFrame::new();
+        for (name, cells) in names.into_iter().zip(columns) {
+            add_cells(&mut df, name, cells)?;
+        }
+        Ok(df)
+    }
+    /// Create a DataFrame from rows of a type implementing [`DataFrameRow`]
+    ///
+    /// Unlike [`from_records`](#method.from_records) fields are pushed straight into typed
+    /// columns without going through serde
+    /// # Errors
+    /// * If the columns cannot be added to a DataFrame
+    ///
+    /// [`DataFrameRow`]: ../records/trait.DataFrameRow.html
+    pub fn from_rows<R: DataFrameRow, I: IntoIterator<Item = R>>(
+        rows: I,
+    ) -> Result<DataFrame, DataFrameErrors> {
+        let mut builder = R::Builder::default();
+        for row in rows {
+            builder.push(row);
+        }
+        builder.finish()
+    }
+    /// Deserialize every row into a struct
+    ///
+    /// Columns are matched to fields by name, columns without a matching field are ignored.
+    /// Missing values (`NaN`, missing datetimes) deserialize to `None` for `Option` fields and
+    /// datetimes deserialize as `%Y-%m-%d %H:%M:%S` strings
+    /// # Errors
+    /// * `Serde`: If a row cannot be deserialized into `T`, eg a field has no column
+    ///
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use serde::Deserialize;
+    /// use std::collections::HashMap;
+    /// use std::convert::TryFrom;
+    ///
+    /// #[derive(Deserialize, Debug, PartialEq)]
+    /// struct Point {
+    ///     x: f64,
+    ///     y: f64,
+    /// }
+    /// let values: HashMap<&str, Vec<f64>> = [("x", vec![1.0, 2.0]), ("y", vec![3.0, 4.0])]
+    ///     .iter()
+    ///     .cloned()
+    ///     .collect();
+    /// let df = DataFrame::try_from(values).unwrap();
+    /// let points = df.to_records::<Point>().unwrap();
+    /// assert_eq!(points[1], Point { x: 2.0, y: 4.0 });
+    /// ```
+    pub fn to_records<T: DeserializeOwned>(&self) -> Result<Vec<T>, RecordErrors> {
+        self.rows().collect()
+    }
+    /// Iterate over the rows deserializing each into a struct
+    ///
+    /// See [`to_records`](#method.to_records) for how columns are matched to fields
+    pub fn rows<T: DeserializeOwned>(&self) -> Rows<T> {
+        Rows {
+            columns: frame_cells(self),
+            row: 0,
+            len: self.len(),
+            marker: PhantomData,
+        }
+    }
+}
@@ -0,0 +1,298 @@
+//! `Serialize` and `Deserialize` implementations for Series and DataFrames
+//!
+//! A Series is serialized as a struct with its `name`, `dtype`, `index` and `values`.
+//! A DataFrame is serialized as a struct with its `index` and a list of `columns` in order,
+//! each column holds its `name` and its `values` as an externally tagged enum whose variant is the
+//! dtype of the column, so frames can be read back by formats that are not self describing like bincode.
+//!
+//! # Example
+//! ```
+//! use dami::prelude::*;
+//! use ndarray::arr2;
+//! let df = DataFrame::from(arr2(&[[1.0, 2.0], [3.0, 4.0]]));
+//! let json = serde_json::to_string(&df).unwrap();
+//! assert_eq!(
+//!     json,
+//!     r#"{"index":["0","1"],"columns":[{"name":"0","values":{"F64":[1.0,3.0]}},{"name":"1","values":{"F64":[2.0,4.0]}}]}"#
+//! );
+//! let back: DataFrame = serde_json::from_str(&json).unwrap();
+//! assert_eq!(back.get::<f64>("1").unwrap().to_vec(), vec![2.0, 4.0]);
+//! ```
+//! Missing float values of Series and DataFrames are written as `null` and read back as `NaN`
+//! ```
+//! use dami::prelude::*;
+//! use ndarray::arr2;
+//! let df = DataFrame::from(arr2(&[[1.0, f64::NAN], [3.0, 4.0]]));
+//! let json = serde_json::to_string(&df).unwrap();
+//! assert!(json.contains(r#"{"F64":[null,4.0]}"#));
+//! let back: DataFrame = serde_json::from_str(&json).unwrap();
+//! let values = back.get::<f64>("1").unwrap().to_vec();
+//! assert!(values[0].is_nan());
+//! assert_eq!(values[1], 4.0);
+//!
+//! let series = Series::from(vec![f32::NAN, 2.0]);
+//! let json = serde_json::to_string(&series).unwrap();
+//! assert!(json.contains(r#""values":[null,2.0]"#));
+//! let back: Series<f32> = serde_json::from_str(&json).unwrap();
+//! assert!(back[0].is_nan());
+//! ```
+use crate::core::dataframe::DataFrame;
+use crate::core::series::Series;
+use crate::enums::DataTypes;
+use serde::de::Error as DeError;
+use serde::ser::{Error as SerError, SerializeStruct};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::any::{Any, TypeId};
+use std::str::FromStr;
+
+/// The values of a Series serialized as a sequence without copying them
+struct SeriesValues<'a, T>(&'a Series<T>);
+
+impl<T: Serialize + Clone + Default + 'static> Serialize for SeriesValues<'_, T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.0.len()).filter_map(|i| self.0.get(i).map(SeriesValue)))
+    }
+}
+
+/// A value of a Series, `f32` and `f64` values are optional so `NaN` is written as a missing value
+/// like in DataFrame columns
+struct SeriesValue<T>(T);
+
+impl<T: Serialize + 'static> Serialize for SeriesValue<&T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let value: &dyn Any = self.0;
+        if let Some(value) = value.downcast_ref::<f64>() {
+            Some(value).filter(|f| !f.is_nan()).serialize(serializer)
+        } else if let Some(value) = value.downcast_ref::<f32>() {
+            Some(value).filter(|f| !f.is_nan()).serialize(serializer)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de> + 'static> Deserialize<'de> for SeriesValue<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match float_nan::<T>() {
+            Some(nan) => Ok(SeriesValue(
+                Option::<T>::deserialize(deserializer)?.unwrap_or(nan),
+            )),
+            None => T::deserialize(deserializer).map(SeriesValue),
+        }
+    }
+}
+
+/// `NaN` if `T` is `f32` or `f64`
+fn float_nan<T: 'static>() -> Option<T> {
+    let nan: Box<dyn Any> = if TypeId::of::<T>() == TypeId::of::<f64>() {
+        Box::new(f64::NAN)
+    } else if TypeId::of::<T>() == TypeId::of::<f32>() {
+        Box::new(f32::NAN)
+    } else {
+        return None;
+    };
+    nan.downcast().ok().map(|nan| *nan)
+}
+
+impl<T: Serialize + Clone + Default + 'static> Serialize for Series<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Series", 4)?;
+        state.serialize_field("name", &self.get_name())?;
+        state.serialize_field("dtype", &format!("{:?}", self.get_dtype()))?;
+        state.serialize_field("index", &self.get_index())?;
+        state.serialize_field("values", &SeriesValues(self))?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "Series", bound = "T: Deserialize<'de> + 'static")]
+struct SeriesRepr<T> {
+    name: String,
+    dtype: String,
+    index: Vec<String>,
+    values: Vec<SeriesValue<T>>,
+}
+
+impl<'de, T> Deserialize<'de> for Series<T>
+where
+    T: Deserialize<'de> + Clone + Default + 'static,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = SeriesRepr::<T>::deserialize(deserializer)?;
+        if repr.index.len() != repr.values.len() {
+            return Err(D::Error::custom(format!(
+                "Series {} has {} index labels but {} values",
+                repr.name,
+                repr.index.len(),
+                repr.values.len()
+            )));
+        }
+        let dtype = DataTypes::from_str(&repr.dtype).map_err(D::Error::custom)?;
+        let values = repr.values.into_iter().map(|f| f.0).collect::<Vec<T>>();
+        let mut series = Series::from(values);
+        // Datetime Series hold i64 values but keep their dtype
+        if dtype == DataTypes::DATETIME && series.get_dtype() == DataTypes::I64 {
+            series.set_dtype(DataTypes::DATETIME);
+        } else if dtype != series.get_dtype() {
+            return Err(D::Error::custom(format!(
+                "Series {} has dtype {:?} which cannot be read as {:?}",
+                repr.name,
+                dtype,
+                series.get_dtype()
+            )));
+        }
+        series.set_name(&repr.name);
+        series.set_index(repr.index);
+        Ok(series)
+    }
+}
+
+/// Values of a DataFrame column, tagged with the dtype
+#[derive(Serialize, Deserialize)]
+enum ColumnValues {
+    Bool(Vec<bool>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    F32(#[serde(with = "nan_as_none")] Vec<f32>),
+    F64(#[serde(with = "nan_as_none")] Vec<f64>),
+    String(Vec<String>),
+    DateTime(Vec<i64>),
+}
+
+/// Serializes `NaN` as a missing value so float columns round-trip through formats like JSON
+/// that have no representation for it
+mod nan_as_none {
+    use num_traits::Float;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<T, S>(values: &[T], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: Float + serde::Serialize,
+        S: Serializer,
+    {
+        serializer.collect_seq(
+            values
+                .iter()
+                .map(|value| if value.is_nan() { None } else { Some(*value) }),
+        )
+    }
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<Vec<T>, D::Error>
+    where
+        T: Float + Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let values = Vec::<Option<T>>::deserialize(deserializer)?;
+        Ok(values
+            .into_iter()
+            .map(|value| value.unwrap_or_else(T::nan))
+            .collect())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Column")]
+struct ColumnRepr {
+    name: String,
+    values: ColumnValues,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "DataFrame")]
+struct DataFrameRepr {
+    index: Vec<String>,
+    columns: Vec<ColumnRepr>,
+}
+
+/// `str` columns are serialized as `String` columns, `object` columns cannot be serialized
+impl Serialize for DataFrame {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let dtypes = self.dtypes();
+        let mut columns = vec![];
+        for name in self.columns() {
+            let values = match dtypes[&name] {
+                DataTypes::BOOL => ColumnValues::Bool(self.get(&name).unwrap().into_vec()),
+                DataTypes::I32 => ColumnValues::I32(self.get(&name).unwrap().into_vec()),
+                DataTypes::I64 => ColumnValues::I64(self.get(&name).unwrap().into_vec()),
+                DataTypes::F32 => ColumnValues::F32(self.get(&name).unwrap().into_vec()),
+                DataTypes::F64 => ColumnValues::F64(self.get(&name).unwrap().into_vec()),
+                DataTypes::STRING => ColumnValues::String(self.get(&name).unwrap().into_vec()),
+                DataTypes::STR => ColumnValues::String(
+                    self.get::<&str>(&name)
+                        .unwrap()
+                        .into_iter()
+                        .map(ToString::to_string)
+                        .collect(),
+                ),
+                DataTypes::DATETIME => ColumnValues::DateTime(self.get(&name).unwrap().into_vec()),
+                DataTypes::OBJECT => {
+                    return Err(S::Error::custom(format!(
+                        "Column {} has dtype object which cannot be serialized",
+                        name
+                    )))
+                }
+            };
+            columns.push(ColumnRepr { name, values });
+        }
+        DataFrameRepr {
+            index: self.get_index(),
+            columns,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for DataFrame {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = DataFrameRepr::deserialize(deserializer)?;
+        let mut df = DataFrame::new();
+        for column in repr.columns {
+            let result = match column.values {
+                ColumnValues::Bool(values) => {
+                    add_column(&mut df, values, &column.name, &repr.index)
+                }
+                ColumnValues::I32(values) => add_column(&mut df, values, &column.name, &repr.index),
+                ColumnValues::I64(values) => add_column(&mut df, values, &column.name, &repr.index),
+                ColumnValues::F32(values) => add_column(&mut df, values, &column.name, &repr.index),
+                ColumnValues::F64(values) => add_column(&mut df, values, &column.name, &repr.index),
+                ColumnValues::String(values) => {
+                    add_column(&mut df, values, &column.name, &repr.index)
+                }
+                ColumnValues::DateTime(values) => {
+                    let mut series = Series::from(values);
+                    series.set_dtype(DataTypes::DATETIME);
+                    add_series(&mut df, series, &column.name, &repr.index)
+                }
+            };
+            result.map_err(D::Error::custom)?;
+        }
+        Ok(df)
+    }
+}
+fn add_column<T: Clone + Default + 'static>(
+    df: &mut DataFrame,
+    values: Vec<T>,
+    name: &str,
+    index: &[String],
+) -> Result<(), String> {
+    add_series(df, Series::from(values), name, index)
+}
+fn add_series<T: Clone + Default + 'static>(
+    df: &mut DataFrame,
+    mut series: Series<T>,
+    name: &str,
+    index: &[String],
+) -> Result<(), String> {
+    if series.len() != index.len() {
+        return Err(format!(
+            "Column {} has {} values but the index has {} labels",
+            name,
+            series.len(),
+            index.len()
+        ));
+    }
+    series.set_name(name);
+    series.set_index(index.to_vec());
+    df.add_series(series, true)
+        .map_err(|err| format!("{:?}", err))
+}
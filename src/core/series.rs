@@ -1,6 +1,8 @@
 //! A one dimensional ndarray with axis labels
 extern crate ndarray;
 
+use crate::core::block_manager::CellFormat;
+use crate::core::display;
 use crate::core::series::generic::create_index;
 use ndarray::prelude::*;
 use ndarray::Array1;
@@ -205,61 +207,53 @@ impl<T: Clone + 'static + Default> From<Vec<(String, T)>> for Series<T> {
 }
 #[doc(hidden)]
 impl<T: 'static + fmt::Debug + Default + Clone> fmt::Debug for Series<T> {
+    /// Print the Series following the [display options](../display/index.html)
+    ///
+    /// Values are formatted with the float precision of the options, `3` digits by default where
+    /// Series used to print `4`, long Series print their first and last values
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         // Ensure index row is equal to series
         debug_assert!(
             self.array.len() == self.index.len(),
             "Array length and index length are different"
         );
+        let options = display::options();
+        let format = CellFormat::default();
         let mut table = Table::new();
         table.set_format(*FORMAT_CLEAN);
-        // Smaller arrays print everything
-        if self.len() <= 10 {
-            for (index, elm) in self.array.iter().enumerate() {
-                let row = vec![
-                    Cell::new(&self.index[index]),
-                    Cell::new(&format!("{:>.4?}", elm)),
-                ];
-                table.add_row(Row::new(row));
+        let rows = display::visible(self.len(), options.max_rows());
+        for row in &rows {
+            match row {
+                Some(i) => {
+                    let value = format!("{:.*?}", options.precision(), &self.array[*i]);
+                    let mut cells = vec![];
+                    if options.show_index() {
+                        cells.push(Cell::new(&self.index[*i]));
+                    }
+                    cells.push(Cell::new(&format.string(&value)));
+                    table.add_row(Row::new(cells));
+                }
+                // Larger arrays print the first values, a gap and the last values
+                None => {
+                    table.add_empty_row();
+                }
             }
-            table.add_empty_row();
-            let final_row = vec![
-                Cell::new(&format!("name:{}", self.name)),
-                Cell::new(&format!("dtype:{:?}", self.dtype)),
-            ];
-            table.add_row(Row::new(final_row));
-            table.insert_row(0, Row::new(vec![Cell::new("index"), Cell::new("values")]));
-            write!(f, "{}", table.to_string())
         }
-        // Larger arrays print first 5 add ... and print the last 5
-        else {
-            (0..5).for_each(|f| {
-                let row = vec![
-                    Cell::new(&self.index[f]),
-                    Cell::new(&format!("{:.4?}", &self[f])),
-                ];
-                table.add_row(Row::new(row));
-            });
-            table.add_empty_row();
-            let length = self.len() - 5;
-            (0..5).for_each(|f| {
-                let row = vec![
-                    Cell::new(&self.index[length + f]),
-                    Cell::new(&format!("{:.4?}", &self[length + f])),
-                ];
-                table.add_row(Row::new(row));
-            });
-
-            let final_row = vec![
-                Cell::new(&format!("name:{}", self.name)),
-                Cell::new(&format!("dtype:{:?}", self.dtype)),
-                Cell::new(&format!("length:{:?}", self.len())),
-            ];
-
-            table.add_row(Row::new(final_row));
+        table.add_empty_row();
+        let mut final_row = vec![
+            Cell::new(&format!("name:{}", self.name)),
+            Cell::new(&format!("dtype:{:?}", self.dtype)),
+        ];
+        if rows.contains(&None) {
+            final_row.push(Cell::new(&format!("length:{:?}", self.len())));
+        }
+        table.add_row(Row::new(final_row));
+        if options.show_index() {
             table.insert_row(0, Row::new(vec![Cell::new("index"), Cell::new("values")]));
-            write!(f, "{}", table.to_string())
+        } else {
+            table.insert_row(0, Row::new(vec![Cell::new("values")]));
         }
+        write!(f, "{}", table.to_string())
     }
 }
 #[doc(hidden)]
@@ -585,6 +585,27 @@ impl<T: Clone + 'static + Default> Series<T> {
     pub fn to_vec(&self) -> Vec<T> {
         self.array.to_vec()
     }
+    /// Consume the Series returning the underlying values as a vector
+    ///
+    /// Unlike [`to_vec`](#method.to_vec) this reuses the allocation of the array
+    /// when the values are contiguous, so no copy is made
+    pub fn into_vec(self) -> Vec<T> {
+        if self.array.as_slice().is_none() {
+            return self.array.to_vec();
+        }
+        let len = self.array.len();
+        let start = self.array.as_ptr() as usize;
+        let mut vector = self.array.into_raw_vec();
+        if vector.len() != len {
+            // The array is a view into a larger allocation, drop the values outside the view
+            let offset = (start - vector.as_ptr() as usize)
+                .checked_div(std::mem::size_of::<T>())
+                .unwrap_or(0);
+            vector.drain(..offset);
+            vector.truncate(len);
+        }
+        vector
+    }
     /// Returns unique values of the Series object.
     ///
     /// Uniques are `not` returned in order of appearance since a HashSet is used to filter non-unique elements
@@ -4,6 +4,7 @@
 //! * [`DataTypes`] : Contains the rust types officially supported by the crate
 //!  *[`DataFrameErrors`] : Contains errors that may occur when parsing DataFrames
 use std::fmt;
+use std::str::FromStr;
 /// This enum contains officially supported types in the series and DataFrames
 /// For unsupported types, they default to OBJECT variant here.
 /// Such types wont benefit from some type specific functions eg Series.describe()
@@ -23,6 +24,10 @@ pub enum DataTypes {
     STR,
     /// Boolean type
     BOOL,
+    /// Date and time stored as seconds elapsed since the unix epoch in an [`i64`]
+    ///
+    /// Missing values are represented by [`NAT`](../core/index/date_time/constant.NAT.html)
+    DATETIME,
     /// Any other type that is not supported
     OBJECT,
 }
@@ -36,11 +41,30 @@ impl fmt::Debug for DataTypes {
             Self::I64 => write!(f, "i64"),
             Self::STR => write!(f, "str"),
             Self::BOOL => write!(f, "bool"),
+            Self::DATETIME => write!(f, "datetime"),
             Self::STRING => write!(f, "string"),
             Self::OBJECT => write!(f, "object"),
         }
     }
 }
+impl FromStr for DataTypes {
+    type Err = String;
+    /// Parse a dtype from the name it is printed with eg `"f64"` or `"datetime"`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "f32" => Ok(Self::F32),
+            "f64" => Ok(Self::F64),
+            "i32" => Ok(Self::I32),
+            "i64" => Ok(Self::I64),
+            "str" => Ok(Self::STR),
+            "bool" => Ok(Self::BOOL),
+            "datetime" => Ok(Self::DATETIME),
+            "string" => Ok(Self::STRING),
+            "object" => Ok(Self::OBJECT),
+            other => Err(format!("Unknown dtype {}", other)),
+        }
+    }
+}
 /// This provides Error methods for DataFrames
 pub enum DataFrameErrors {
     /// A Series is being inserted into a DataFrame whose length is different
@@ -1,21 +1,48 @@
 //! Exports the io operations dami supports
 //!
-//! - [`clipboard`](clipboard/index.html):`(needs feature clipboard)` provides support for reading clipboard
+//! - [`arrow`](arrow/index.html):`(needs feature arrow)` provides conversion to and from Arrow and reading and
+//! writing Arrow IPC (Feather) files
+//! - [`binary`](binary/index.html):provides support for saving and loading DataFrames in a fast native binary
+//! format, `(needs feature mmap)` to memory map files
+//! - [`clipboard`](clipboard/index.html):`(needs feature clipboard)` provides support for reading and writing the clipboard
 //! and parsing `csv` data only (for the meantime) one day support for json may be included.
 //! - [`csv`](csv/index.html):provides support for reading ad writing csv  data
-//! - [`fwf`](fwf/index.html):provides support for reading fixed width files
+//! - [`excel`](excel/index.html):`(needs feature calamine to read and xlsx to write)` provides support for
+//! reading spreadsheets and writing `xlsx` workbooks
+//! - [`fwf`](fwf/index.html):provides support for reading and writing fixed width files
+//! - [`hdf5`](hdf5/index.html):`(needs feature hdf5)` provides support for reading and writing HDF5 files
+//! - [`html`](html/index.html):provides support for writing HTML tables and `(needs feature html)` reading
+//! the tables of HTML documents
 //! - [`json`](json/index.html):provides support for  reading json formatted files
+//! - [`latex`](latex/index.html):provides support for writing LaTeX tables
+//! - [`markdown`](markdown/index.html):provides support for writing Markdown tables
+//! - [`parquet`](parquet/index.html):`(needs feature parquet)` provides support for reading and writing parquet files
+//! - [`remote`](remote/index.html):`(needs feature remote)` provides options for fetching remote files like
+//! headers, authentication, timeouts, retries and caching
+//! - [`sql`](sql/index.html):`(needs feature sql)` provides support for reading and writing SQLite tables
 //! - [`utils`](utils/index.html):provides utilities used by the modules above like `read`
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod binary;
 #[cfg(feature = "clipboard")]
 pub mod clipboard;
 mod csv;
 pub mod excel;
-mod fwf;
+pub mod fwf;
 #[cfg(feature = "hdf5")]
 pub mod hdf5;
+pub mod html;
 mod json;
+pub mod latex;
+pub mod markdown;
+#[cfg(feature = "parquet")]
+pub mod parquet;
 pub mod parser;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "sql")]
+pub mod sql;
 mod utils;
 
 mod dtypes;
@@ -0,0 +1,498 @@
+//! Interoperability with [Apache Arrow](https://arrow.apache.org/)
+//!
+//! Requires feature `arrow`
+//!
+//! This module converts between [`DataFrame`]s and Arrow [`RecordBatch`]es and between [`Series`]
+//! and Arrow arrays, and reads and writes Arrow IPC files (also known as Feather V2 files).
+//!
+//! Conversions are zero-copy where possible, numeric and datetime columns hand their allocation
+//! over to Arrow (and back) instead of copying it. Boolean and string columns have a different
+//! memory layout in Arrow and are always copied.
+//!
+//! | DataType   | Arrow type                                                   |
+//! |------------|--------------------------------------------------------------|
+//! | `BOOL`     | `Boolean`                                                    |
+//! | `I32`      | `Int32` (also read from `Int8`,`Int16`,`UInt8` and `UInt16`) |
+//! | `I64`      | `Int64` (also read from `UInt32` and `UInt64`)               |
+//! | `F32`      | `Float32` (also read from `Float16`)                         |
+//! | `F64`      | `Float64`                                                    |
+//! | `STRING`   | `Utf8` (also read from `LargeUtf8` and `Dictionary`)         |
+//! | `STR`      | `Utf8` (write only)                                          |
+//! | `DATETIME` | `Timestamp(Second)` (also read from `Timestamp`,`Date32` and `Date64`) |
+//!
+//! # Nulls
+//! Arrow nulls are converted the same way as the parquet reader does it
+//! * Integer and boolean arrays containing nulls are promoted to `f64` with nulls becoming `NaN`
+//! * Null floats become `NaN`
+//! * Null strings become empty strings
+//! * Null datetimes become [`NAT`]
+//!
+//! When writing, [`NAT`] values are written as nulls while `NaN`s are written as is.
+//!
+//! # Note
+//! The DataFrame index is not part of a RecordBatch and is not preserved
+//!
+//! [`DataFrame`]: ../../core/dataframe/struct.DataFrame.html
+//! [`Series`]: ../../core/series/struct.Series.html
+//! [`RecordBatch`]: https://docs.rs/arrow/latest/arrow/record_batch/struct.RecordBatch.html
+//! [`NAT`]: ../../core/index/date_time/constant.NAT.html
+#![cfg(feature = "arrow")]
+use crate::core::dataframe::DataFrame;
+use crate::core::index::date_time::NAT;
+use crate::core::series::Series;
+use crate::enums::{DataFrameErrors, DataTypes};
+use crate::io::dtypes::add_named_series;
+use crate::io::utils::to_local_path;
+
+use arrow::array::{
+    Array, ArrayRef, ArrowPrimitiveType, AsArray, BooleanArray, Float32Array, Float64Array,
+    Int32Array, Int64Array, StringArray, TimestampSecondArray,
+};
+use arrow::buffer::{NullBuffer, ScalarBuffer};
+use arrow::compute::{cast, concat_batches};
+use arrow::datatypes::{
+    DataType, Date32Type, Date64Type, Field, Float32Type, Float64Type, Int32Type, Int64Type,
+    Schema, TimeUnit, TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+    TimestampSecondType,
+};
+use arrow::error::ArrowError;
+use arrow::ipc::reader::FileReader;
+use arrow::ipc::writer::FileWriter;
+use arrow::record_batch::RecordBatch;
+
+use std::any::Any;
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+/// Errors that may occur when converting to and from Arrow
+pub enum ArrowErrors {
+    /// An error originating from the arrow library
+    Arrow(ArrowError),
+    /// The file could not be opened or created
+    IOError(std::io::Error),
+    /// The Arrow type has no equivalent dami dtype
+    UnsupportedType(DataType),
+    /// The dami dtype has no equivalent Arrow type
+    UnsupportedDtype(DataTypes),
+    /// The array was converted to a Series of the contained dtype but a different type was requested
+    TypeMismatch(DataTypes),
+    /// The columns could not be put together in a DataFrame, eg two columns share a name
+    DataFrame(DataFrameErrors),
+}
+impl fmt::Debug for ArrowErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Arrow(ref err) => write!(f, "{}", err),
+            Self::IOError(ref err) => write!(f, "{}", err),
+            Self::UnsupportedType(ref dtype) => {
+                write!(f, "Arrow type {} cannot be converted to a Series", dtype)
+            }
+            Self::UnsupportedDtype(ref dtype) => {
+                write!(f, "dtype {:?} cannot be converted to an Arrow array", dtype)
+            }
+            Self::TypeMismatch(ref dtype) => write!(
+                f,
+                "The array holds values of dtype {:?} which differs from the requested type",
+                dtype
+            ),
+            Self::DataFrame(ref err) => write!(f, "{:?}", err),
+        }
+    }
+}
+impl From<ArrowError> for ArrowErrors {
+    fn from(err: ArrowError) -> Self {
+        Self::Arrow(err)
+    }
+}
+impl From<std::io::Error> for ArrowErrors {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(err)
+    }
+}
+impl From<DataFrameErrors> for ArrowErrors {
+    fn from(err: DataFrameErrors) -> Self {
+        Self::DataFrame(err)
+    }
+}
+
+/// A Series of any of the dtypes an arrow array can be converted into
+enum Column {
+    Bool(Series<bool>),
+    I32(Series<i32>),
+    I64(Series<i64>),
+    F32(Series<f32>),
+    F64(Series<f64>),
+    Str(Series<String>),
+    DateTime(Series<i64>),
+}
+impl Column {
+    fn dtype(&self) -> DataTypes {
+        match self {
+            Self::Bool(_) => DataTypes::BOOL,
+            Self::I32(_) => DataTypes::I32,
+            Self::I64(_) => DataTypes::I64,
+            Self::F32(_) => DataTypes::F32,
+            Self::F64(_) => DataTypes::F64,
+            Self::Str(_) => DataTypes::STRING,
+            Self::DateTime(_) => DataTypes::DATETIME,
+        }
+    }
+    fn into_any(self) -> Box<dyn Any> {
+        match self {
+            Self::Bool(series) => Box::new(series),
+            Self::I32(series) => Box::new(series),
+            Self::I64(series) => Box::new(series),
+            Self::F32(series) => Box::new(series),
+            Self::F64(series) => Box::new(series),
+            Self::Str(series) => Box::new(series),
+            Self::DateTime(series) => Box::new(series),
+        }
+    }
+    fn add_to(self, df: &mut DataFrame, name: &str) -> Result<(), DataFrameErrors> {
+        match self {
+            Self::Bool(series) => add_named_series(df, series, name),
+            Self::I32(series) => add_named_series(df, series, name),
+            Self::I64(series) => add_named_series(df, series, name),
+            Self::F32(series) => add_named_series(df, series, name),
+            Self::F64(series) => add_named_series(df, series, name),
+            Self::Str(series) => add_named_series(df, series, name),
+            Self::DateTime(series) => add_named_series(df, series, name),
+        }
+    }
+}
+/// Take the values and validity of a primitive array
+///
+/// The value buffer is reused if no other array references it, otherwise it's copied
+fn primitive_values<T: ArrowPrimitiveType>(
+    array: ArrayRef,
+) -> (Vec<T::Native>, Option<NullBuffer>) {
+    let primitive = array.as_primitive::<T>().clone();
+    // Drop our reference to the buffers so that they can be unwrapped
+    drop(array);
+    let (_, values, nulls) = primitive.into_parts();
+    let values = values
+        .into_inner()
+        .into_vec::<T::Native>()
+        .unwrap_or_else(|buffer| ScalarBuffer::<T::Native>::from(buffer).to_vec());
+    (values, nulls.filter(|f| f.null_count() > 0))
+}
+fn nulls_to_nan<N: Copy, F: Fn(N) -> f64>(values: &[N], nulls: &NullBuffer, func: F) -> Vec<f64> {
+    values
+        .iter()
+        .zip(nulls.iter())
+        .map(|(value, valid)| if valid { func(*value) } else { f64::NAN })
+        .collect()
+}
+/// Convert timestamps to seconds since the epoch marking nulls as NAT
+fn to_seconds<N: Copy, F: Fn(N) -> i64>(
+    values: &[N],
+    nulls: Option<NullBuffer>,
+    func: F,
+) -> Series<i64> {
+    let seconds = match nulls {
+        Some(nulls) => values
+            .iter()
+            .zip(nulls.iter())
+            .map(|(value, valid)| if valid { func(*value) } else { NAT })
+            .collect(),
+        None => values.iter().map(|f| func(*f)).collect::<Vec<i64>>(),
+    };
+    let mut series = Series::from(seconds);
+    series.set_dtype(DataTypes::DATETIME);
+    series
+}
+#[allow(clippy::cast_precision_loss)]
+fn array_to_column(array: ArrayRef) -> Result<Column, ArrowErrors> {
+    let column = match array.data_type().clone() {
+        DataType::Boolean => {
+            let booleans = array.as_boolean();
+            if booleans.null_count() > 0 {
+                Column::F64(Series::from(
+                    booleans
+                        .iter()
+                        .map(|f| f.map_or(f64::NAN, |v| if v { 1.0 } else { 0.0 }))
+                        .collect::<Vec<f64>>(),
+                ))
+            } else {
+                Column::Bool(Series::from(
+                    booleans.values().iter().collect::<Vec<bool>>(),
+                ))
+            }
+        }
+        DataType::Int8 | DataType::Int16 | DataType::UInt8 | DataType::UInt16 => {
+            array_to_column(cast(&array, &DataType::Int32)?)?
+        }
+        DataType::UInt32 | DataType::UInt64 => array_to_column(cast(&array, &DataType::Int64)?)?,
+        DataType::Float16 => array_to_column(cast(&array, &DataType::Float32)?)?,
+        DataType::Int32 => match primitive_values::<Int32Type>(array) {
+            (values, Some(nulls)) => {
+                Column::F64(Series::from(nulls_to_nan(&values, &nulls, f64::from)))
+            }
+            (values, None) => Column::I32(Series::from(values)),
+        },
+        DataType::Int64 => match primitive_values::<Int64Type>(array) {
+            (values, Some(nulls)) => {
+                Column::F64(Series::from(nulls_to_nan(&values, &nulls, |f| f as f64)))
+            }
+            (values, None) => Column::I64(Series::from(values)),
+        },
+        DataType::Float32 => {
+            let (mut values, nulls) = primitive_values::<Float32Type>(array);
+            if let Some(nulls) = nulls {
+                values
+                    .iter_mut()
+                    .zip(nulls.iter())
+                    .filter(|(_, valid)| !valid)
+                    .for_each(|(value, _)| *value = f32::NAN);
+            }
+            Column::F32(Series::from(values))
+        }
+        DataType::Float64 => {
+            let (mut values, nulls) = primitive_values::<Float64Type>(array);
+            if let Some(nulls) = nulls {
+                values
+                    .iter_mut()
+                    .zip(nulls.iter())
+                    .filter(|(_, valid)| !valid)
+                    .for_each(|(value, _)| *value = f64::NAN);
+            }
+            Column::F64(Series::from(values))
+        }
+        DataType::Utf8 => Column::Str(Series::from(
+            array
+                .as_string::<i32>()
+                .iter()
+                .map(|f| f.unwrap_or("").to_string())
+                .collect::<Vec<String>>(),
+        )),
+        DataType::LargeUtf8 => Column::Str(Series::from(
+            array
+                .as_string::<i64>()
+                .iter()
+                .map(|f| f.unwrap_or("").to_string())
+                .collect::<Vec<String>>(),
+        )),
+        DataType::Dictionary(_, ref value) if **value == DataType::Utf8 => {
+            array_to_column(cast(&array, &DataType::Utf8)?)?
+        }
+        DataType::Timestamp(TimeUnit::Second, _) => {
+            let (values, nulls) = primitive_values::<TimestampSecondType>(array);
+            if nulls.is_none() {
+                // Seconds are stored as is, so reuse the buffer
+                let mut series = Series::from(values);
+                series.set_dtype(DataTypes::DATETIME);
+                Column::DateTime(series)
+            } else {
+                Column::DateTime(to_seconds(&values, nulls, |f| f))
+            }
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, _) => {
+            let (values, nulls) = primitive_values::<TimestampMillisecondType>(array);
+            Column::DateTime(to_seconds(&values, nulls, |f| f.div_euclid(1_000)))
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, _) => {
+            let (values, nulls) = primitive_values::<TimestampMicrosecondType>(array);
+            Column::DateTime(to_seconds(&values, nulls, |f| f.div_euclid(1_000_000)))
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, _) => {
+            let (values, nulls) = primitive_values::<TimestampNanosecondType>(array);
+            Column::DateTime(to_seconds(&values, nulls, |f| f.div_euclid(1_000_000_000)))
+        }
+        DataType::Date32 => {
+            let (values, nulls) = primitive_values::<Date32Type>(array);
+            Column::DateTime(to_seconds(&values, nulls, |f| i64::from(f) * 86_400))
+        }
+        DataType::Date64 => {
+            let (values, nulls) = primitive_values::<Date64Type>(array);
+            Column::DateTime(to_seconds(&values, nulls, |f| f.div_euclid(1_000)))
+        }
+        other => return Err(ArrowErrors::UnsupportedType(other)),
+    };
+    Ok(column)
+}
+
+impl<T: Clone + Default + 'static> Series<T> {
+    /// # Requires Feature
+    ///  > * `arrow`
+    ///
+    /// Convert the Series into an Arrow array
+    ///
+    /// Numeric and datetime Series are converted without copying the values.
+    ///
+    /// The name and index of the Series are dropped.
+    /// # Errors
+    /// * `UnsupportedDtype`: If the Series has dtype `object`
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// let array = Series::from(vec![1.0, 2.0, 3.0]).into_arrow().unwrap();
+    /// assert_eq!(array.len(), 3);
+    /// ```
+    pub fn into_arrow(self) -> Result<ArrayRef, ArrowErrors> {
+        let dtype = self.get_dtype();
+        let series: Box<dyn Any> = Box::new(self);
+        let array: ArrayRef = match dtype {
+            DataTypes::BOOL => Arc::new(BooleanArray::from(
+                series.downcast::<Series<bool>>().unwrap().into_vec(),
+            )),
+            DataTypes::I32 => Arc::new(Int32Array::new(
+                ScalarBuffer::from(series.downcast::<Series<i32>>().unwrap().into_vec()),
+                None,
+            )),
+            DataTypes::I64 => Arc::new(Int64Array::new(
+                ScalarBuffer::from(series.downcast::<Series<i64>>().unwrap().into_vec()),
+                None,
+            )),
+            DataTypes::F32 => Arc::new(Float32Array::new(
+                ScalarBuffer::from(series.downcast::<Series<f32>>().unwrap().into_vec()),
+                None,
+            )),
+            DataTypes::F64 => Arc::new(Float64Array::new(
+                ScalarBuffer::from(series.downcast::<Series<f64>>().unwrap().into_vec()),
+                None,
+            )),
+            DataTypes::STRING => Arc::new(StringArray::from(
+                series.downcast::<Series<String>>().unwrap().into_vec(),
+            )),
+            DataTypes::STR => Arc::new(StringArray::from(
+                series.downcast::<Series<&str>>().unwrap().into_vec(),
+            )),
+            DataTypes::DATETIME => {
+                let values = series.downcast::<Series<i64>>().unwrap().into_vec();
+                let nulls = if values.contains(&NAT) {
+                    Some(NullBuffer::from(
+                        values.iter().map(|f| *f != NAT).collect::<Vec<bool>>(),
+                    ))
+                } else {
+                    None
+                };
+                Arc::new(TimestampSecondArray::new(ScalarBuffer::from(values), nulls))
+            }
+            DataTypes::OBJECT => return Err(ArrowErrors::UnsupportedDtype(dtype)),
+        };
+        Ok(array)
+    }
+    /// # Requires Feature
+    ///  > * `arrow`
+    ///
+    /// Create a Series from an Arrow array
+    ///
+    /// The array is converted to the dtype listed in the [module documentation](../../io/arrow/index.html)
+    /// and `T` must be the Rust type of that dtype (`i64` for datetime arrays).
+    ///
+    /// Numeric and datetime arrays without nulls reuse the Arrow buffer if `array` is its only
+    /// reference and isn't sliced.
+    /// # Errors
+    /// * `UnsupportedType`: If the Arrow type cannot be converted
+    /// * `TypeMismatch`: If `T` is not the type of the converted array
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use arrow::array::{ArrayRef, Int32Array};
+    /// use std::sync::Arc;
+    /// let array: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3]));
+    /// let series = Series::<i32>::from_arrow(array).unwrap();
+    /// assert_eq!(series.to_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn from_arrow(array: ArrayRef) -> Result<Series<T>, ArrowErrors> {
+        let column = array_to_column(array)?;
+        let dtype = column.dtype();
+        column
+            .into_any()
+            .downcast::<Series<T>>()
+            .map(|series| *series)
+            .map_err(|_| ArrowErrors::TypeMismatch(dtype))
+    }
+}
+
+impl DataFrame {
+    /// # Requires Feature
+    ///  > * `arrow`
+    ///
+    /// Convert the DataFrame into an Arrow RecordBatch
+    ///
+    /// Columns are moved out of the DataFrame so numeric and datetime columns are not copied.
+    /// # Errors
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    pub fn into_record_batch(mut self) -> Result<RecordBatch, ArrowErrors> {
+        let dtypes = self.dtypes();
+        let mut fields = vec![];
+        let mut arrays = vec![];
+        for name in self.columns() {
+            let array = match dtypes[&name] {
+                DataTypes::BOOL => self.pop::<bool>(&name).unwrap().into_arrow()?,
+                DataTypes::I32 => self.pop::<i32>(&name).unwrap().into_arrow()?,
+                DataTypes::I64 | DataTypes::DATETIME => {
+                    self.pop::<i64>(&name).unwrap().into_arrow()?
+                }
+                DataTypes::F32 => self.pop::<f32>(&name).unwrap().into_arrow()?,
+                DataTypes::F64 => self.pop::<f64>(&name).unwrap().into_arrow()?,
+                DataTypes::STRING => self.pop::<String>(&name).unwrap().into_arrow()?,
+                DataTypes::STR => self.pop::<&str>(&name).unwrap().into_arrow()?,
+                DataTypes::OBJECT => return Err(ArrowErrors::UnsupportedDtype(DataTypes::OBJECT)),
+            };
+            fields.push(Field::new(
+                name,
+                array.data_type().clone(),
+                array.null_count() > 0,
+            ));
+            arrays.push(array);
+        }
+        Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+    }
+    /// # Requires Feature
+    ///  > * `arrow`
+    ///
+    /// Create a DataFrame from an Arrow RecordBatch
+    ///
+    /// Column names are taken from the schema of the batch.
+    /// # Errors
+    /// * `UnsupportedType`: If a column of the batch cannot be converted
+    /// * `DataFrame`: If two columns have the same name
+    pub fn from_record_batch(batch: RecordBatch) -> Result<DataFrame, ArrowErrors> {
+        let schema = batch.schema();
+        let columns = batch.columns().to_vec();
+        // Drop the batch so the column buffers can be reused
+        drop(batch);
+        let mut df = DataFrame::new();
+        for (field, array) in schema.fields().iter().zip(columns) {
+            array_to_column(array)?.add_to(&mut df, field.name())?;
+        }
+        Ok(df)
+    }
+    /// # Requires Feature
+    ///  > * `arrow`
+    ///
+    /// Write the DataFrame to an Arrow IPC file
+    ///
+    /// The IPC file format is the same as the Feather V2 format so the file can be read by
+    /// `pyarrow.feather` and `pandas.read_feather`
+    /// # Errors
+    /// * `IOError`: If the file cannot be created
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    /// * `Arrow`: If the writer fails
+    pub fn to_ipc<P: AsRef<Path>>(&self, path: P) -> Result<(), ArrowErrors> {
+        let batch = self.clone().into_record_batch()?;
+        let mut writer = FileWriter::try_new(File::create(path)?, &batch.schema())?;
+        writer.write(&batch)?;
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Read an Arrow IPC (Feather V2) file into a DataFrame
+///
+/// All the record batches in the file are concatenated into one DataFrame
+/// # Errors
+/// * `IOError`: If the file cannot be opened
+/// * `Arrow`: If the file is not a valid IPC file
+/// * `UnsupportedType`: If a column cannot be converted
+/// * `DataFrame`: If two columns have the same name
+pub fn read_ipc_file<P: AsRef<Path>>(path: P) -> Result<DataFrame, ArrowErrors> {
+    let reader = FileReader::try_new(File::open(to_local_path(path))?, None)?;
+    let schema = reader.schema();
+    let batches = reader.collect::<Result<Vec<RecordBatch>, ArrowError>>()?;
+    DataFrame::from_record_batch(concat_batches(&schema, &batches)?)
+}
@@ -0,0 +1,607 @@
+//! A native columnar binary format for saving and loading DataFrames
+//!
+//! Files written by [`DataFrame::save`] start with the magic bytes `DAMIBIN1` followed by the
+//! length of the schema as a little endian `u64` and the schema itself encoded as JSON. The schema holds
+//! the number of rows, the index, the compression used and for each column its name, dtype and where its
+//! values are stored.
+//!
+//! Values follow the schema with the columns of each block (all columns of one dtype) stored next to
+//! each other. Numbers are stored as little endian bytes, booleans as one byte each and strings as the
+//! length of every string as a `u64` followed by their UTF-8 bytes. Each column is compressed on its own
+//! so [`DamiFile`] can load single columns without reading the rest of the file, with the `mmap` feature
+//! the file is memory mapped instead of read.
+//!
+//! `str` columns are stored as `String` columns, `object` columns cannot be stored.
+//!
+//! # Example
+//! ```no_run
+//! use dami::prelude::*;
+//! use dami::io::binary::DamiFile;
+//! use ndarray::arr2;
+//!
+//! let df = DataFrame::from(arr2(&[[1.0, 2.0], [3.0, 4.0]]));
+//! df.save("frame.dami").unwrap();
+//! let back = DataFrame::load("frame.dami").unwrap();
+//! // Only read the column named 1
+//! let file = DamiFile::open("frame.dami").unwrap();
+//! let column = file.column::<f64>("1").unwrap();
+//! ```
+//! [`DataFrame::save`]: ../../core/dataframe/struct.DataFrame.html#method.save
+//! [`DamiFile`]: struct.DamiFile.html
+use crate::core::dataframe::DataFrame;
+use crate::core::series::Series;
+use crate::enums::{DataFrameErrors, DataTypes};
+use crate::io::utils::to_local_path;
+use lzma_rs::{lzma_compress, lzma_decompress};
+#[cfg(feature = "mmap")]
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::convert::{TryFrom, TryInto};
+use std::fmt;
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+#[cfg(not(feature = "mmap"))]
+use std::io::{Seek, SeekFrom};
+use std::path::Path;
+
+/// The first bytes of every file
+const MAGIC: &[u8; 8] = b"DAMIBIN1";
+/// The order blocks are written in
+const BLOCKS: [DataTypes; 7] = [
+    DataTypes::BOOL,
+    DataTypes::I32,
+    DataTypes::I64,
+    DataTypes::F32,
+    DataTypes::F64,
+    DataTypes::STRING,
+    DataTypes::DATETIME,
+];
+
+/// Errors that may occur when saving or loading binary files
+pub enum BinaryErrors {
+    /// An error occurred reading or writing the file
+    IOError(std::io::Error),
+    /// The file was not written by dami or is corrupted
+    InvalidFile(String),
+    /// The schema could not be written or read
+    Schema(serde_json::Error),
+    /// A column could not be decompressed
+    Compression(String),
+    /// The file has no column with this name
+    KeyError(String),
+    /// The column holds values of another type, holds the column and its dtype
+    DtypeError(String, DataTypes),
+    /// The column has an `object` dtype which cannot be stored
+    UnsupportedDtype(String),
+    /// The columns could not be added to a DataFrame
+    DataFrame(DataFrameErrors),
+}
+impl fmt::Debug for BinaryErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IOError(err) => write!(f, "{}", err),
+            Self::InvalidFile(reason) => write!(f, "Not a valid dami file: {}", reason),
+            Self::Schema(err) => write!(f, "Invalid schema: {}", err),
+            Self::Compression(err) => write!(f, "Could not decompress column: {}", err),
+            Self::KeyError(column) => write!(f, "Column {} does not exist", column),
+            Self::DtypeError(column, dtype) => {
+                write!(f, "Column {} has dtype {:?}", column, dtype)
+            }
+            Self::UnsupportedDtype(column) => write!(
+                f,
+                "Column {} has dtype object which cannot be stored in a binary file",
+                column
+            ),
+            Self::DataFrame(err) => write!(f, "{:?}", err),
+        }
+    }
+}
+impl From<std::io::Error> for BinaryErrors {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(err)
+    }
+}
+impl From<serde_json::Error> for BinaryErrors {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Schema(err)
+    }
+}
+impl From<DataFrameErrors> for BinaryErrors {
+    fn from(err: DataFrameErrors) -> Self {
+        Self::DataFrame(err)
+    }
+}
+
+/// How the values of each column are compressed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    /// Values are stored as they are, the fastest to save and load
+    None,
+    /// Values are compressed using LZMA, smaller files but slower to save and load
+    Lzma,
+}
+impl Default for Compression {
+    fn default() -> Self {
+        Compression::None
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct ColumnSchema {
+    name: String,
+    dtype: String,
+    /// Start of the values counted from the end of the schema
+    offset: u64,
+    /// Number of (compressed) bytes
+    size: u64,
+}
+#[derive(Serialize, Deserialize)]
+struct Schema {
+    len: usize,
+    index: Vec<String>,
+    compression: Compression,
+    /// Columns in the order of the DataFrame
+    columns: Vec<ColumnSchema>,
+}
+
+/// Types whose values can be stored in binary files
+pub trait BinaryType: Clone + Default + 'static {
+    /// Whether a column of `dtype` holds values of this type
+    fn holds(dtype: &DataTypes) -> bool;
+    /// Append the bytes of `values` to `out`
+    fn encode(values: &[Self], out: &mut Vec<u8>);
+    /// Read `len` values from `bytes`
+    ///
+    /// Returns `None` if `bytes` does not hold `len` values
+    fn decode(bytes: &[u8], len: usize) -> Option<Vec<Self>>;
+}
+macro_rules! impl_binary_type {
+    ($type:ty,$size:expr,$($dtype:path),+) => {
+        impl BinaryType for $type {
+            fn holds(dtype: &DataTypes) -> bool {
+                matches!(dtype, $($dtype)|+)
+            }
+            fn encode(values: &[Self], out: &mut Vec<u8>) {
+                out.reserve(values.len() * $size);
+                for value in values {
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            fn decode(bytes: &[u8], len: usize) -> Option<Vec<Self>> {
+                // `len` comes from the schema, a corrupted one must not overflow
+                if bytes.len() != len.checked_mul($size)? {
+                    return None;
+                }
+                Some(
+                    bytes
+                        .chunks_exact($size)
+                        .map(|f| <$type>::from_le_bytes(f.try_into().unwrap()))
+                        .collect(),
+                )
+            }
+        }
+    };
+}
+impl_binary_type!(i32, 4, DataTypes::I32);
+impl_binary_type!(i64, 8, DataTypes::I64, DataTypes::DATETIME);
+impl_binary_type!(f32, 4, DataTypes::F32);
+impl_binary_type!(f64, 8, DataTypes::F64);
+
+impl BinaryType for bool {
+    fn holds(dtype: &DataTypes) -> bool {
+        *dtype == DataTypes::BOOL
+    }
+    fn encode(values: &[Self], out: &mut Vec<u8>) {
+        out.extend(values.iter().map(|f| u8::from(*f)));
+    }
+    fn decode(bytes: &[u8], len: usize) -> Option<Vec<Self>> {
+        if bytes.len() != len {
+            return None;
+        }
+        Some(bytes.iter().map(|f| *f != 0).collect())
+    }
+}
+impl BinaryType for String {
+    fn holds(dtype: &DataTypes) -> bool {
+        *dtype == DataTypes::STRING
+    }
+    fn encode(values: &[Self], out: &mut Vec<u8>) {
+        for value in values {
+            out.extend_from_slice(&(value.len() as u64).to_le_bytes());
+        }
+        for value in values {
+            out.extend_from_slice(value.as_bytes());
+        }
+    }
+    fn decode(bytes: &[u8], len: usize) -> Option<Vec<Self>> {
+        let mut start = len.checked_mul(8)?;
+        let lengths = decode_lengths(bytes.get(..start)?, len)?;
+        let mut values = Vec::with_capacity(len);
+        for length in lengths {
+            let end = start.checked_add(usize::try_from(length).ok()?)?;
+            values.push(String::from_utf8(bytes.get(start..end)?.to_vec()).ok()?);
+            start = end;
+        }
+        if start == bytes.len() {
+            Some(values)
+        } else {
+            None
+        }
+    }
+}
+/// Read the lengths stored before the bytes of strings
+fn decode_lengths(bytes: &[u8], len: usize) -> Option<Vec<u64>> {
+    if bytes.len() != len.checked_mul(8)? {
+        return None;
+    }
+    Some(
+        bytes
+            .chunks_exact(8)
+            .map(|f| u64::from_le_bytes(f.try_into().unwrap()))
+            .collect(),
+    )
+}
+
+/// Writes DataFrames to binary files
+///
+/// # Example
+/// ```no_run
+/// use dami::prelude::*;
+/// use dami::io::binary::{BinaryWriter, Compression};
+/// use ndarray::arr2;
+///
+/// let df = DataFrame::from(arr2(&[[1.0, 2.0], [3.0, 4.0]]));
+/// BinaryWriter::new()
+///     .set_compression(Compression::Lzma)
+///     .write(&df, "frame.dami")
+///     .unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BinaryWriter {
+    compression: Compression,
+}
+impl BinaryWriter {
+    /// Create a new writer, by default values are not compressed
+    pub fn new() -> BinaryWriter {
+        BinaryWriter::default()
+    }
+    /// Set how the values of each column are compressed
+    pub fn set_compression(&mut self, compression: Compression) -> &mut Self {
+        self.compression = compression;
+        self
+    }
+    /// Write `df` to a file at `path`, replacing the file if it exists
+    ///
+    /// `file://` urls are resolved to local paths, as they are by [`DamiFile::open`](struct.DamiFile.html#method.open)
+    /// # Errors
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    /// * `IOError`: If the file cannot be created or written
+    pub fn write<P: AsRef<Path>>(&self, df: &DataFrame, path: P) -> Result<(), BinaryErrors> {
+        let names = df.columns();
+        let dtypes = df.dtypes();
+        if let Some(name) = names.iter().find(|f| dtypes[*f] == DataTypes::OBJECT) {
+            return Err(BinaryErrors::UnsupportedDtype(name.clone()));
+        }
+        let mut data = vec![];
+        let mut columns = HashMap::with_capacity(names.len());
+        for block in &BLOCKS {
+            for name in &names {
+                let dtype = &dtypes[name];
+                let stored = if *dtype == DataTypes::STR {
+                    &DataTypes::STRING
+                } else {
+                    dtype
+                };
+                if stored != block {
+                    continue;
+                }
+                let mut bytes = vec![];
+                match dtype {
+                    DataTypes::BOOL => encode_column::<bool>(df, name, &mut bytes),
+                    DataTypes::I32 => encode_column::<i32>(df, name, &mut bytes),
+                    DataTypes::I64 | DataTypes::DATETIME => {
+                        encode_column::<i64>(df, name, &mut bytes)
+                    }
+                    DataTypes::F32 => encode_column::<f32>(df, name, &mut bytes),
+                    DataTypes::F64 => encode_column::<f64>(df, name, &mut bytes),
+                    DataTypes::STRING => encode_column::<String>(df, name, &mut bytes),
+                    DataTypes::STR => {
+                        let values = df
+                            .get::<&str>(name)
+                            .unwrap()
+                            .into_iter()
+                            .map(ToString::to_string)
+                            .collect::<Vec<String>>();
+                        String::encode(&values, &mut bytes)
+                    }
+                    DataTypes::OBJECT => unreachable!(),
+                }
+                if self.compression == Compression::Lzma {
+                    let mut compressed = vec![];
+                    lzma_compress(&mut bytes.as_slice(), &mut compressed)?;
+                    bytes = compressed;
+                }
+                columns.insert(
+                    name.clone(),
+                    ColumnSchema {
+                        name: name.clone(),
+                        dtype: format!("{:?}", stored),
+                        offset: data.len() as u64,
+                        size: bytes.len() as u64,
+                    },
+                );
+                data.extend_from_slice(&bytes);
+            }
+        }
+        let schema = Schema {
+            len: df.len(),
+            index: df.get_index(),
+            compression: self.compression,
+            columns: names.iter().map(|f| columns[f].clone()).collect(),
+        };
+        let schema = serde_json::to_vec(&schema)?;
+        let mut file = BufWriter::new(File::create(to_local_path(path))?);
+        file.write_all(MAGIC)?;
+        file.write_all(&(schema.len() as u64).to_le_bytes())?;
+        file.write_all(&schema)?;
+        file.write_all(&data)?;
+        file.flush()?;
+        Ok(())
+    }
+}
+fn encode_column<T: BinaryType>(df: &DataFrame, name: &str, out: &mut Vec<u8>) {
+    T::encode(&df.get::<T>(name).unwrap().into_vec(), out);
+}
+
+/// A binary file whose columns are loaded when they are needed
+///
+/// Opening the file only reads the schema, columns are read (and decompressed) by [`column`],
+/// [`select`] and [`to_dataframe`]. With the `mmap` feature the file is memory mapped so the
+/// operating system only pages in the bytes of the columns read.
+///
+/// [`column`]: #method.column
+/// [`select`]: #method.select
+/// [`to_dataframe`]: #method.to_dataframe
+pub struct DamiFile {
+    schema: Schema,
+    /// DataTypes of the columns in the order of the schema, checked when the file is opened
+    dtypes: Vec<DataTypes>,
+    /// Where values start in the file
+    start: u64,
+    #[cfg(not(feature = "mmap"))]
+    file: File,
+    #[cfg(feature = "mmap")]
+    map: Mmap,
+}
+impl DamiFile {
+    /// Open a file written by [`DataFrame::save`] or [`BinaryWriter`] and read its schema
+    /// # Errors
+    /// * `IOError`: If the file cannot be opened
+    /// * `InvalidFile`, `Schema`: If the file was not written by dami or a column has an unknown dtype
+    ///
+    /// [`DataFrame::save`]: ../../core/dataframe/struct.DataFrame.html#method.save
+    /// [`BinaryWriter`]: struct.BinaryWriter.html
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<DamiFile, BinaryErrors> {
+        let mut file = File::open(to_local_path(path))?;
+        let mut head = [0; 16];
+        file.read_exact(&mut head)
+            .map_err(|_| BinaryErrors::InvalidFile("the file is too short".to_string()))?;
+        if &head[..8] != MAGIC {
+            return Err(BinaryErrors::InvalidFile(
+                "the file does not start with the magic bytes".to_string(),
+            ));
+        }
+        let schema_len = u64::from_le_bytes(head[8..].try_into().unwrap());
+        let mut schema = vec![];
+        (&mut file).take(schema_len).read_to_end(&mut schema)?;
+        if schema.len() as u64 != schema_len {
+            return Err(BinaryErrors::InvalidFile(
+                "the schema is truncated".to_string(),
+            ));
+        }
+        let schema: Schema = serde_json::from_slice(&schema)?;
+        let dtypes = schema
+            .columns
+            .iter()
+            .map(parse_dtype)
+            .collect::<Result<Vec<DataTypes>, BinaryErrors>>()?;
+        Ok(DamiFile {
+            schema,
+            dtypes,
+            start: 16 + schema_len,
+            // Safety: the map is only read, changes to the file while it is open show up as
+            // decoding errors
+            #[cfg(feature = "mmap")]
+            map: unsafe { Mmap::map(&file)? },
+            #[cfg(not(feature = "mmap"))]
+            file,
+        })
+    }
+    /// Names of the columns in order
+    pub fn columns(&self) -> Vec<String> {
+        self.schema.columns.iter().map(|f| f.name.clone()).collect()
+    }
+    /// DataTypes of the columns
+    pub fn dtypes(&self) -> HashMap<String, DataTypes> {
+        self.schema
+            .columns
+            .iter()
+            .zip(&self.dtypes)
+            .map(|(f, dtype)| (f.name.clone(), dtype.clone()))
+            .collect()
+    }
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.schema.len
+    }
+    /// Whether the file has no rows
+    pub fn is_empty(&self) -> bool {
+        self.schema.len == 0
+    }
+    /// The row labels
+    pub fn get_index(&self) -> Vec<String> {
+        self.schema.index.clone()
+    }
+    /// How the values of each column are compressed
+    pub fn compression(&self) -> Compression {
+        self.schema.compression
+    }
+    /// Read a single column
+    /// # Errors
+    /// * `KeyError`: If the file has no column named `name`
+    /// * `DtypeError`: If the column does not hold values of type `T`
+    /// * `IOError`, `Compression`, `InvalidFile`: If the values cannot be read
+    pub fn column<T: BinaryType>(&self, name: &str) -> Result<Series<T>, BinaryErrors> {
+        let (column, dtype) = self.schema_of(name)?;
+        if !T::holds(dtype) {
+            return Err(BinaryErrors::DtypeError(name.to_string(), dtype.clone()));
+        }
+        let mut series = Series::from(self.read_values::<T>(column)?);
+        if *dtype == DataTypes::DATETIME {
+            series.set_dtype(DataTypes::DATETIME);
+        }
+        series.set_name(name);
+        if self.schema.index.len() == series.len() {
+            series.set_index(self.schema.index.clone());
+        }
+        Ok(series)
+    }
+    /// Read some columns into a DataFrame, columns are added in the order of `names`
+    /// # Errors
+    /// * `KeyError`: If a column does not exist
+    /// * `IOError`, `Compression`, `InvalidFile`: If the values cannot be read
+    pub fn select(&self, names: &[&str]) -> Result<DataFrame, BinaryErrors> {
+        let mut df = DataFrame::new();
+        for name in names {
+            let (_, dtype) = self.schema_of(name)?;
+            match dtype {
+                DataTypes::BOOL => df.add_series(self.column::<bool>(name)?, true)?,
+                DataTypes::I32 => df.add_series(self.column::<i32>(name)?, true)?,
+                DataTypes::I64 | DataTypes::DATETIME => {
+                    df.add_series(self.column::<i64>(name)?, true)?
+                }
+                DataTypes::F32 => df.add_series(self.column::<f32>(name)?, true)?,
+                DataTypes::F64 => df.add_series(self.column::<f64>(name)?, true)?,
+                DataTypes::STRING => df.add_series(self.column::<String>(name)?, true)?,
+                DataTypes::STR | DataTypes::OBJECT => {
+                    unreachable!("DamiFile::open rejects dtypes that are never stored")
+                }
+            }
+        }
+        Ok(df)
+    }
+    /// Read every column into a DataFrame
+    /// # Errors
+    /// * `IOError`, `Compression`, `InvalidFile`: If the values cannot be read
+    pub fn to_dataframe(&self) -> Result<DataFrame, BinaryErrors> {
+        let names = self.columns();
+        self.select(&names.iter().map(String::as_str).collect::<Vec<&str>>())
+    }
+    fn schema_of(&self, name: &str) -> Result<(&ColumnSchema, &DataTypes), BinaryErrors> {
+        self.schema
+            .columns
+            .iter()
+            .zip(&self.dtypes)
+            .find(|(f, _)| f.name == name)
+            .ok_or_else(|| BinaryErrors::KeyError(name.to_string()))
+    }
+    fn read_values<T: BinaryType>(&self, column: &ColumnSchema) -> Result<Vec<T>, BinaryErrors> {
+        let raw = self.raw(column)?;
+        let bytes = match self.schema.compression {
+            Compression::None => raw,
+            Compression::Lzma => {
+                let mut decompressed = vec![];
+                lzma_decompress(&mut raw.as_ref(), &mut decompressed)
+                    .map_err(|err| BinaryErrors::Compression(format!("{:?}", err)))?;
+                Cow::Owned(decompressed)
+            }
+        };
+        T::decode(&bytes, self.schema.len).ok_or_else(|| {
+            BinaryErrors::InvalidFile(format!("column {} is corrupted", column.name))
+        })
+    }
+    /// Start and end of the bytes of `column` in the file, the offsets come from the schema so a
+    /// corrupted one must not overflow
+    fn range(&self, column: &ColumnSchema) -> Result<(u64, u64), BinaryErrors> {
+        let truncated =
+            || BinaryErrors::InvalidFile(format!("column {} is truncated", column.name));
+        let start = self
+            .start
+            .checked_add(column.offset)
+            .ok_or_else(truncated)?;
+        let end = start.checked_add(column.size).ok_or_else(truncated)?;
+        if usize::try_from(end).is_err() {
+            return Err(truncated());
+        }
+        Ok((start, end))
+    }
+    #[cfg(feature = "mmap")]
+    fn raw(&self, column: &ColumnSchema) -> Result<Cow<'_, [u8]>, BinaryErrors> {
+        let (start, end) = self.range(column)?;
+        self.map
+            .get(start as usize..end as usize)
+            .map(Cow::Borrowed)
+            .ok_or_else(|| {
+                BinaryErrors::InvalidFile(format!("column {} is truncated", column.name))
+            })
+    }
+    #[cfg(not(feature = "mmap"))]
+    fn raw(&self, column: &ColumnSchema) -> Result<Cow<'_, [u8]>, BinaryErrors> {
+        let (start, _) = self.range(column)?;
+        let mut file = &self.file;
+        file.seek(SeekFrom::Start(start))?;
+        // The size comes from the schema, let the buffer grow with what is actually read
+        let mut bytes = vec![];
+        file.take(column.size).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != column.size {
+            return Err(BinaryErrors::InvalidFile(format!(
+                "column {} is truncated",
+                column.name
+            )));
+        }
+        Ok(Cow::Owned(bytes))
+    }
+}
+/// Parse the dtype of `column`, only the dtypes in [`BLOCKS`] are ever stored
+fn parse_dtype(column: &ColumnSchema) -> Result<DataTypes, BinaryErrors> {
+    column
+        .dtype
+        .parse()
+        .ok()
+        .filter(|dtype| BLOCKS.contains(dtype))
+        .ok_or_else(|| {
+            BinaryErrors::InvalidFile(format!(
+                "column {} has unknown dtype {}",
+                column.name, column.dtype
+            ))
+        })
+}
+
+impl DataFrame {
+    /// Save the DataFrame to a binary file at `path`
+    ///
+    /// Values are stored uncompressed, use [`BinaryWriter`] to compress them. Loading binary files is
+    /// much faster than parsing text formats like csv, see the [`binary`] module for the layout of the file
+    /// # Errors
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    /// * `IOError`: If the file cannot be created or written
+    ///
+    /// [`BinaryWriter`]: ../../io/binary/struct.BinaryWriter.html
+    /// [`binary`]: ../../io/binary/index.html
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), BinaryErrors> {
+        BinaryWriter::new().write(self, path)
+    }
+    /// Load a DataFrame saved by [`save`](#method.save)
+    ///
+    /// Use [`DamiFile`] to load only some columns
+    /// # Errors
+    /// * `IOError`: If the file cannot be read
+    /// * `InvalidFile`, `Schema`, `Compression`: If the file was not written by dami or is corrupted
+    ///
+    /// [`DamiFile`]: ../../io/binary/struct.DamiFile.html
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<DataFrame, BinaryErrors> {
+        DamiFile::open(path)?.to_dataframe()
+    }
+}
@@ -8,63 +8,246 @@
 //! `features=['minimal']` option on Cargo dependencies
 //!
 //! Overall it adds about 2 seconds on compile time (debug profile)
+//!
+//! The clipboard is reached through the [`ClipboardBackend`] trait, [`SystemClipboard`] talks to the
+//! system clipboard and [`MemoryClipboard`] keeps the contents in memory so the readers and writers
+//! can be used where no clipboard is available
+//! ```
+//! use dami::prelude::*;
+//! use dami::io::clipboard::{ClipReader, MemoryClipboard};
+//! use ndarray::arr2;
+//!
+//! let df = DataFrame::from(arr2(&[[1, 2], [3, 4]]));
+//! let mut clipboard = MemoryClipboard::new();
+//! df.to_clipboard_with(&mut clipboard, "\t", false).unwrap();
+//! assert_eq!(clipboard.contents(), "0\t1\n1\t2\n3\t4\n");
+//!
+//! let read = ClipReader::with_backend(clipboard).read(None, None).unwrap();
+//! assert_eq!(read.get::<i32>("1").unwrap().into_vec(), vec![2, 4]);
+//! ```
 //! # Notes
 //! Requirements on:
 //!
 //! - Linux: `sudo  apt install xorg-dev libxcb-shape0-dev libxcb-xfixes0-dev`
+//!
+//! [`ClipboardBackend`]: trait.ClipboardBackend.html
+//! [`SystemClipboard`]: struct.SystemClipboard.html
+//! [`MemoryClipboard`]: struct.MemoryClipboard.html
 extern crate clipboard;
+use crate::core::dataframe::DataFrame;
 use crate::core::series::Series;
 use crate::io::csv::{series_to_csv, Reader};
 use clipboard::{ClipboardContext, ClipboardProvider};
 use std::collections::HashMap;
+use std::fmt;
 use std::fmt::Display;
 
-#[derive(Clone, Debug)]
+/// Separator marking whitespace aligned tables
+const WHITESPACE: &str = r"\s+";
+
+/// Errors that may occur when reading from or writing to the clipboard
+pub enum ClipboardErrors {
+    /// The clipboard could not be reached or its contents could not be read or set
+    Clipboard(String),
+    /// The clipboard contains no data
+    Empty,
+    /// The DataFrame contains an `object` column which cannot be written
+    UnsupportedDtype(String),
+}
+impl fmt::Debug for ClipboardErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Clipboard(err) => write!(f, "Could not access the clipboard: {}", err),
+            Self::Empty => write!(f, "The clipboard contains no data"),
+            Self::UnsupportedDtype(name) => write!(
+                f,
+                "Column {} has an object dtype which cannot be copied to the clipboard",
+                name
+            ),
+        }
+    }
+}
+
+/// Somewhere clipboard text can be read from and written to
+pub trait ClipboardBackend {
+    /// Get the text stored on the clipboard
+    /// # Errors
+    /// * `Clipboard`: If the clipboard cannot be read
+    fn get_contents(&mut self) -> Result<String, ClipboardErrors>;
+    /// Replace the text stored on the clipboard with `contents`
+    /// # Errors
+    /// * `Clipboard`: If the clipboard cannot be written
+    fn set_contents(&mut self, contents: String) -> Result<(), ClipboardErrors>;
+}
+impl<B: ClipboardBackend + ?Sized> ClipboardBackend for &mut B {
+    fn get_contents(&mut self) -> Result<String, ClipboardErrors> {
+        (**self).get_contents()
+    }
+    fn set_contents(&mut self, contents: String) -> Result<(), ClipboardErrors> {
+        (**self).set_contents(contents)
+    }
+}
+
+/// The system clipboard
+///
+/// A connection to the clipboard is opened on every read and write
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClipboard;
+
+impl SystemClipboard {
+    fn context() -> Result<ClipboardContext, ClipboardErrors> {
+        ClipboardProvider::new().map_err(|err| ClipboardErrors::Clipboard(format!("{}", err)))
+    }
+}
+impl ClipboardBackend for SystemClipboard {
+    fn get_contents(&mut self) -> Result<String, ClipboardErrors> {
+        Self::context()?
+            .get_contents()
+            .map_err(|err| ClipboardErrors::Clipboard(format!("{}", err)))
+    }
+    fn set_contents(&mut self, contents: String) -> Result<(), ClipboardErrors> {
+        Self::context()?
+            .set_contents(contents)
+            .map_err(|err| ClipboardErrors::Clipboard(format!("{}", err)))
+    }
+}
+
+/// A clipboard kept in memory, useful for tests and headless machines
+#[derive(Clone, Debug, Default)]
+pub struct MemoryClipboard {
+    contents: String,
+}
+impl MemoryClipboard {
+    /// Create an empty clipboard
+    #[must_use]
+    pub fn new() -> MemoryClipboard {
+        Self::default()
+    }
+    /// Create a clipboard holding `contents`
+    pub fn with_contents<S: Into<String>>(contents: S) -> MemoryClipboard {
+        MemoryClipboard {
+            contents: contents.into(),
+        }
+    }
+    /// The text stored on the clipboard
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+}
+impl ClipboardBackend for MemoryClipboard {
+    fn get_contents(&mut self) -> Result<String, ClipboardErrors> {
+        Ok(self.contents.clone())
+    }
+    fn set_contents(&mut self, contents: String) -> Result<(), ClipboardErrors> {
+        self.contents = contents;
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
 /// Read data from clipboard and pass it to `read_csv` method of csv module
-pub struct ClipReader<'a> {
+pub struct ClipReader<B: ClipboardBackend = SystemClipboard> {
     data: String,
-    csv_reader: Reader<'a>,
+    backend: B,
 }
-impl<'a> Default for ClipReader<'a> {
-    fn default() -> ClipReader<'a> {
-        ClipReader {
-            data: String::new(),
-            csv_reader: Reader::new(),
-        }
+impl Default for ClipReader {
+    fn default() -> ClipReader {
+        ClipReader::with_backend(SystemClipboard)
     }
 }
-impl<'a> ClipReader<'a> {
-    /// Create a new instance of Clipboard reader
-    ///
-    /// The Reader initializes also its own `CSVReader` from the csv module
-    /// and whatever data is read from the clipboard is passed on to the underlying `CSVReader`.
+impl ClipReader {
+    /// Create a new instance of Clipboard reader reading from the system clipboard
     ///
+    /// Whatever data is read from the clipboard is passed on to a `CSVReader` from the csv module
     #[must_use]
-    pub fn new() -> ClipReader<'a> {
+    pub fn new() -> ClipReader {
         Self::default()
     }
+}
+impl<B: ClipboardBackend> ClipReader<B> {
+    /// Create a Clipboard reader reading from `backend`
+    pub fn with_backend(backend: B) -> ClipReader<B> {
+        ClipReader {
+            data: String::new(),
+            backend,
+        }
+    }
     /// Read data from the clipboard
     ///
     /// # Arguments
-    /// * `sep`:A string or regex delimiter. The default of `\s+` denotes
-    /// one or more whitespace characters.
-    /// > `type`: [`&str`]
+    /// * `sep`: The delimiter of the records, if `None` the delimiter is detected from the data.
+    /// Tabs (cells copied from a spreadsheet) are tried first then commas, otherwise the data is
+    /// treated as a table aligned with whitespace, as is the case of a printed DataFrame.
+    /// `\s+` can be passed to read whitespace aligned tables explicitly
+    /// > `type`: [`Option<&str>`]
     /// * `options`: A `HashMap` containing settings for the CSV Parser to consider when parsing the CSV
     /// since it is wrapped in an [`Option`] None is also valid, where the default settings will be used
     /// > `type`: [`Option<Hashmap>`]
     /// # Returns
-    ///  [`Reader<'a>`](/dami/io/csv/struct.Reader.html)
+    ///  A [`DataFrame`](/dami/core/dataframe/struct.DataFrame.html) of the data
+    /// # Errors
+    /// * `Clipboard`: If the clipboard cannot be read
+    /// * `Empty`: If the clipboard contains no data
+    ///
+    /// # Example
+    /// ```
+    /// use dami::core::display::{self, Options};
+    /// use dami::io::clipboard::{ClipReader, MemoryClipboard};
+    /// use dami::prelude::*;
+    /// use ndarray::Array2;
+    ///
+    /// // Cells copied from a spreadsheet are tab separated
+    /// let clipboard = MemoryClipboard::with_contents("a\tb\n1\tx\n2\ty\n");
+    /// let df = ClipReader::with_backend(clipboard).read(None, None).unwrap();
+    /// assert_eq!(df.get::<i32>("a").unwrap().into_vec(), vec![1, 2]);
+    /// assert_eq!(df.get::<String>("b").unwrap().into_vec(), vec!["x", "y"]);
+    ///
+    /// // A header with an empty first field holds the index
+    /// let clipboard = MemoryClipboard::with_contents(",a,b\nr1,1,2.5\nr2,3,4.5\n");
+    /// let df = ClipReader::with_backend(clipboard).read(None, None).unwrap();
+    /// assert_eq!(df.columns(), vec!["", "a", "b"]);
+    /// assert_eq!(df.get::<f64>("b").unwrap().into_vec(), vec![2.5, 4.5]);
+    ///
+    /// // Whitespace aligned text without an index
+    /// let clipboard = MemoryClipboard::with_contents("a   b\n1   2\n3   4\n");
+    /// let df = ClipReader::with_backend(clipboard).read(None, None).unwrap();
+    /// assert_eq!(df.get::<i32>("b").unwrap().into_vec(), vec![2, 4]);
+    ///
+    /// // A printed DataFrame, the elided rows and the shape footer are skipped
+    /// let printed = DataFrame::from(Array2::from_elem((100, 2), 1.5));
+    /// let mut options = Options::new();
+    /// options.set_max_rows(Some(6));
+    /// let text = display::with_options(options, || printed.to_string());
+    /// assert!(text.contains("...."));
+    /// let clipboard = MemoryClipboard::with_contents(text);
+    /// let df = ClipReader::with_backend(clipboard).read(None, None).unwrap();
+    /// assert_eq!(df.get::<f64>("1").unwrap().into_vec(), vec![1.5; 6]);
+    /// ```
     ///
-    /// [`&str`]: /std/primitive.str.html
+    /// [`Option<&str>`]: /std/option/enum.Option.html
     /// [`Option`]: /std/option/enum.Option.html
     /// [`Option<Hashmap>`]: /std/option/enum.Option.html
-    pub fn read(&mut self, sep: &'a str, options: Option<HashMap<&'a str, &'a str>>) -> Reader<'a> {
+    pub fn read<'a>(
+        &mut self,
+        sep: Option<&'a str>,
+        options: Option<HashMap<&'a str, &'a str>>,
+    ) -> Result<DataFrame, ClipboardErrors> {
+        self.data = self.backend.get_contents()?;
+        if self.data.trim().is_empty() {
+            return Err(ClipboardErrors::Empty);
+        }
+        let sep = sep.unwrap_or_else(|| detect_separator(&self.data));
         let mut options = options.unwrap_or_default();
-        options.insert("sep", sep);
-        let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
-        self.data.push_str(ctx.get_contents().unwrap().as_str());
-        self.csv_reader.update_kwargs(options);
-        self.csv_reader.parse_string_csv(&self.data)
+        let mut csv_reader = Reader::new();
+        if sep == WHITESPACE {
+            options.insert("sep", "\t");
+            csv_reader.update_kwargs(options);
+            Ok(csv_reader.parse_string_csv(&align_whitespace(&self.data)))
+        } else {
+            options.insert("sep", sep);
+            csv_reader.update_kwargs(options);
+            Ok(csv_reader.parse_string_csv(&self.data))
+        }
     }
 
     /// Return the data read from the clipboard as a [`String`](https://doc.rust-lang.org/std/string/struct.String.html) type
@@ -73,34 +256,155 @@ impl<'a> ClipReader<'a> {
         self.data.clone()
     }
 }
+/// Detect the delimiter of clipboard text from its first lines
+///
+/// Tabs are used if every line contains one, commas if every line has the same number of them
+/// and whitespace otherwise
+fn detect_separator(data: &str) -> &'static str {
+    let lines = data
+        .lines()
+        .filter(|f| !f.trim().is_empty() && !f.starts_with('#') && !is_elision(f))
+        .take(20)
+        .collect::<Vec<&str>>();
+    if lines.iter().all(|f| f.contains('\t')) {
+        return "\t";
+    }
+    let commas = lines
+        .iter()
+        .map(|f| f.matches(',').count())
+        .collect::<Vec<usize>>();
+    if commas[0] > 0 && commas.iter().all(|f| *f == commas[0]) {
+        return ",";
+    }
+    WHITESPACE
+}
+/// Whether `line` was added when a DataFrame was printed truncated, the `....` row standing for
+/// elided rows or the `[N rows x M columns]` footer
+fn is_elision(line: &str) -> bool {
+    let line = line.trim();
+    let footer = line.starts_with('[') && line.ends_with(" columns]") && line.contains(" rows x ");
+    footer || line.split_whitespace().all(|f| f == "....")
+}
+/// Rewrite a whitespace aligned table as tab separated lines
+///
+/// If the header has one field less than the first row, as when the DataFrame was printed with its
+/// index, an empty name is added for the index column. Rows elided when a DataFrame was printed
+/// truncated are skipped
+fn align_whitespace(data: &str) -> String {
+    let mut rows = data
+        .lines()
+        .filter(|f| !f.trim().is_empty() && !is_elision(f))
+        .map(|f| f.split_whitespace().collect::<Vec<&str>>())
+        .collect::<Vec<Vec<&str>>>();
+    if rows.len() > 1 && rows[0].len() + 1 == rows[1].len() {
+        rows[0].insert(0, "");
+    }
+    rows.iter().fold(String::new(), |mut text, row| {
+        text.push_str(&row.join("\t"));
+        text.push('\n');
+        text
+    })
+}
+impl DataFrame {
+    /// Copy the DataFrame to the system clipboard
+    ///
+    /// The first line holds the column names and each following line holds one row with values
+    /// separated by `sep`, tab separated text pastes into cells when pasted into a spreadsheet.
+    /// Values containing `sep`, quotes or newlines are quoted, `NaN`s and missing datetimes
+    /// are left blank
+    ///
+    /// # Arguments
+    /// * `sep`: The separator of the values, usually `"\t"`
+    /// * `index`: Whether to write the row labels as the first column
+    /// # Errors
+    /// * `Clipboard`: If the clipboard cannot be written
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    /// # Warning ⚠️
+    ///
+    /// This overwrites any data stored on the clipboard
+    pub fn to_clipboard(&self, sep: &str, index: bool) -> Result<(), ClipboardErrors> {
+        self.to_clipboard_with(&mut SystemClipboard, sep, index)
+    }
+    /// Copy the DataFrame to `backend`
+    ///
+    /// See [`to_clipboard`](#method.to_clipboard)
+    /// # Errors
+    /// * `Clipboard`: If the clipboard cannot be written
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    pub fn to_clipboard_with<B: ClipboardBackend>(
+        &self,
+        backend: &mut B,
+        sep: &str,
+        index: bool,
+    ) -> Result<(), ClipboardErrors> {
+        let names = self.columns();
+        let mut columns = Vec::with_capacity(names.len() + 1);
+        let mut header = Vec::with_capacity(names.len() + 1);
+        if index {
+            columns.push(self.get_index());
+            header.push(String::new());
+        }
+        for name in &names {
+            columns.push(
+                self.get_strings(name)
+                    .ok_or_else(|| ClipboardErrors::UnsupportedDtype(name.clone()))?,
+            );
+            header.push(name.clone());
+        }
+        let mut text = String::new();
+        push_line(&mut text, header.iter(), sep);
+        for row in 0..self.len() {
+            push_line(&mut text, columns.iter().map(|f| &f[row]), sep);
+        }
+        backend.set_contents(text)
+    }
+}
+/// Push one line of `sep` separated values onto `text`, quoting values where needed
+fn push_line<'b, I: Iterator<Item = &'b String>>(text: &mut String, values: I, sep: &str) {
+    for (i, value) in values.enumerate() {
+        if i > 0 {
+            text.push_str(sep);
+        }
+        if (!sep.is_empty() && value.contains(sep))
+            || value.contains(|c| c == '"' || c == '\n' || c == '\r')
+        {
+            text.push('"');
+            text.push_str(&value.replace('"', "\"\""));
+            text.push('"');
+        } else {
+            text.push_str(value);
+        }
+    }
+    text.push('\n');
+}
 /// Write text data to the clipboard after parsing it
 /// The data can then be pasted to Excel for example
 ///
 /// # Arguments
-/// * `obj`:to be written to the clipboard
-///
-/// > `type`: [`String`]: or any type that implements `into<String>`
+/// * `series`: The series to be written to the clipboard
 ///
-/// * `sep`: Optional separator defaults to tab
+/// > `type`: [`Series`]
 ///
-///  > `type`: [`&str`]
-///
-/// *  `other` keywords are passed to `to_csv_writer` method of csv
-///  > `type`: [`HashMap`]
+/// # Panics
+/// If the system clipboard cannot be written, use [`series_to_clipboard_with`] to handle the error
 ///
 /// # Warning ⚠️
 ///
 /// This overwrites any data stored on the clipboard
 ///
-/// [`&str`]: /std/primitive.str.html
-/// [`String`]: /std/string/struct.String.html
-/// [`HashMap`]: /std/collections/struct.HashMap.html
+/// [`Series`]: /dami/core/series/struct.Series.html
+/// [`series_to_clipboard_with`]: fn.series_to_clipboard_with.html
 pub fn series_to_clipboard<T: Display + Clone + Default + 'static>(series: &Series<T>) {
+    series_to_clipboard_with(&mut SystemClipboard, series).unwrap();
+}
+/// Write a series to `backend` with its name on the first line and a value on each following line
+/// # Errors
+/// * `Clipboard`: If the clipboard cannot be written
+pub fn series_to_clipboard_with<T: Display + Clone + Default + 'static, B: ClipboardBackend>(
+    backend: &mut B,
+    series: &Series<T>,
+) -> Result<(), ClipboardErrors> {
     let mut buff = Vec::new();
     series_to_csv(series, &mut buff);
-    // Call to_writer
-    let mut ctx: ClipboardContext = ClipboardProvider::new().unwrap();
-    // Set contents
-    ctx.set_contents(String::from_utf8_lossy(&buff).to_string())
-        .unwrap();
+    backend.set_contents(String::from_utf8_lossy(&buff).to_string())
 }
@@ -8,11 +8,10 @@ use std::path::Path;
 
 use crate::core::series::Series;
 use crate::io::dtypes::{is_bool, is_float, is_int, str_to_bool, str_to_float, str_to_int};
-use crate::io::utils::{is_compressed, is_url, read};
+use crate::io::utils::open_reader;
 use crate::prelude::DataFrame;
 use std::cmp::min;
-use std::fs::File;
-use std::io::{BufRead, BufReader, Write};
+use std::io::{BufRead, Lines, Write};
 
 /// The Error type for CSV
 pub enum CSVError {
@@ -87,23 +86,10 @@ impl<'a> Reader<'a> {
     /// Call it at your own risk
     ///
     /// # Returns
-    /// [Reader<'a>](struct.Reader.html)
-    fn parse_string_csv(&mut self, data: &str) -> DataFrame {
-        if self.builder.has_headers && self.headers.is_empty() {
-            let headers = data.lines().next().unwrap();
-            self.smart_push(
-                smart_split(headers, self.builder.delimiter, self.builder.quote_char),
-                true,
-            )
-            .unwrap();
-        }
+    /// A [`DataFrame`](../../core/dataframe/struct.DataFrame.html) of the records
+    pub(crate) fn parse_string_csv(&mut self, data: &str) -> DataFrame {
         for line in data.lines() {
-            if line.starts_with(self.builder.ignore) {
-                continue;
-            }
-            // Smart split is actually noice :)
-            let split_lines = smart_split(line, self.builder.delimiter, self.builder.quote_char);
-            self.smart_push(split_lines, false).unwrap()
+            self.push_line(line);
         }
         self.to_dataframe()
     }
@@ -114,52 +100,54 @@ impl<'a> Reader<'a> {
     ///
     /// to parse the data and also calls [`update_kwargs`](#method.update_kwargs) to update keyword
     /// arguments
+    ///
+    /// Local files are parsed line by line as they are read and remote files as they are downloaded
     pub fn parse_csv<P: AsRef<Path> + Debug + Clone>(
         &mut self,
         path: P,
         kwargs: HashMap<&'a str, &'a str>,
     ) -> DataFrame {
         self.update_kwargs(kwargs);
-
-        if is_url(path.as_ref().to_str().unwrap()) || is_compressed(path.as_ref().to_str().unwrap())
-        {
-            let lines = read(path);
-            self.parse_string_csv(&lines)
-        } else {
-            // For local files. We don't need to read the whole file to memory we can parse it line by line
-            self.parse_local_file(path.as_ref().to_str().unwrap())
+        for line in open_reader(path).lines() {
+            self.push_line(&line.unwrap());
         }
+        self.to_dataframe()
     }
-    fn parse_local_file(&mut self, path: &str) -> DataFrame {
-        let fd = File::open(path).unwrap();
-        let buf = BufReader::new(fd);
-        for line in buf.lines() {
-            let line = line.unwrap();
-            if self.builder.has_headers && self.headers.is_empty() {
-                let headers = line;
-                self.smart_push(
-                    smart_split(
-                        headers.as_str(),
-                        self.builder.delimiter,
-                        self.builder.quote_char,
-                    ),
-                    true,
-                )
-                .unwrap();
-                continue;
-            }
-            if line.starts_with(self.builder.ignore) {
-                continue;
-            }
-            // Smart split is actually noice :)
-            let split_lines = smart_split(
-                line.as_str(),
-                self.builder.delimiter,
-                self.builder.quote_char,
-            );
-            self.smart_push(split_lines, false).unwrap()
+    /// Parse a csv file in chunks of at most `chunksize` rows
+    ///
+    /// See [`read_csv_chunked`](../parser/fn.read_csv_chunked.html)
+    pub fn parse_csv_chunked<P: AsRef<Path> + Debug + Clone>(
+        mut self,
+        path: P,
+        kwargs: HashMap<&'a str, &'a str>,
+        chunksize: usize,
+    ) -> CsvChunks<'a> {
+        assert!(chunksize > 0, "chunksize should be greater than zero");
+        self.update_kwargs(kwargs);
+        CsvChunks {
+            reader: self,
+            lines: open_reader(path).lines(),
+            chunksize,
+            rows: 0,
         }
-        self.to_dataframe()
+    }
+    /// Parse one line of the csv, returns `true` if the line is a record
+    fn push_line(&mut self, line: &str) -> bool {
+        if self.builder.has_headers && self.headers.is_empty() {
+            self.smart_push(
+                smart_split(line, self.builder.delimiter, self.builder.quote_char),
+                true,
+            )
+            .unwrap();
+            return false;
+        }
+        if line.starts_with(self.builder.ignore) {
+            return false;
+        }
+        // Smart split is actually noice :)
+        let split_lines = smart_split(line, self.builder.delimiter, self.builder.quote_char);
+        self.smart_push(split_lines, false).unwrap();
+        true
     }
     /// Update keyword arguments settings for the CSV reader
     ///
@@ -175,7 +163,7 @@ impl<'a> Reader<'a> {
     /// >> `ignore` : becomes the new ignore of the underlying builder
     ///
     /// >> `names` : Becomes the new headers of the CSV files
-    fn update_kwargs(&mut self, mut new_kwargs: HashMap<&'a str, &'a str>) {
+    pub(crate) fn update_kwargs(&mut self, mut new_kwargs: HashMap<&'a str, &'a str>) {
         if new_kwargs.contains_key("sep") || new_kwargs.contains_key("delimiter") {
             self.builder.set_delimiter(
                 new_kwargs
@@ -240,31 +228,83 @@ impl<'a> Reader<'a> {
     ///
     /// Currently. This uses the first record in the array to determine the type of the records for that column
     pub fn to_dataframe(&self) -> DataFrame {
+        self.frame(None)
+    }
+    /// Convert the parsed records to a DataFrame, labelling rows from `first_row` if given
+    fn frame(&self, first_row: Option<usize>) -> DataFrame {
         let size = min(10, self.data[0].len());
+        let index = first_row.map(|first| {
+            (first..first + self.data[0].len())
+                .map(|f| f.to_string())
+                .collect::<Vec<String>>()
+        });
         let mut df = DataFrame::new();
         for (i, j) in self.data.iter().enumerate() {
             let header = self.headers.get(i).unwrap();
             if is_int(&j[0..size]) {
-                let mut series = Series::from(str_to_int(j));
-                series.set_name(header.as_str());
-                df.add_series(series, true).unwrap();
+                add_column(&mut df, str_to_int(j), header, index.as_deref());
             } else if is_float(&j[0..size]) {
-                let mut series = Series::from(str_to_float(j));
-                series.set_name(header.as_str());
-                df.add_series(series, true).unwrap();
+                add_column(&mut df, str_to_float(j), header, index.as_deref());
             } else if is_bool(&j[0..size]) {
-                let mut series = Series::from(str_to_bool(j));
-                series.set_name(header.as_str());
-                df.add_series(series, true).unwrap();
+                add_column(&mut df, str_to_bool(j), header, index.as_deref());
             } else {
-                let mut series = Series::from(j.to_owned());
-                series.set_name(header.as_str());
-                df.add_series(series, true).unwrap();
+                add_column(&mut df, j.to_owned(), header, index.as_deref());
             }
         }
         df
     }
 }
+fn add_column<T: Clone + Default + 'static>(
+    df: &mut DataFrame,
+    values: Vec<T>,
+    name: &str,
+    index: Option<&[String]>,
+) {
+    let mut series = Series::from(values);
+    series.set_name(name);
+    if let Some(index) = index {
+        if index.len() == series.len() {
+            series.set_index(index.to_vec());
+        }
+    }
+    df.add_series(series, true).unwrap();
+}
+/// An iterator over the rows of a csv file yielding DataFrames of at most `chunksize` rows
+///
+/// Created by [`read_csv_chunked`](../parser/fn.read_csv_chunked.html), rows are read from the file
+/// (or the connection for remote files) only when the next chunk is requested. Rows are labelled by
+/// their position in the file so the index continues from one chunk to the next.
+///
+/// The dtype of each column is inferred separately for each chunk
+pub struct CsvChunks<'a> {
+    reader: Reader<'a>,
+    lines: Lines<Box<dyn BufRead>>,
+    chunksize: usize,
+    rows: usize,
+}
+impl Iterator for CsvChunks<'_> {
+    type Item = DataFrame;
+    fn next(&mut self) -> Option<DataFrame> {
+        self.reader.data.clear();
+        let mut read = 0;
+        while read < self.chunksize {
+            match self.lines.next() {
+                Some(line) => {
+                    if self.reader.push_line(&line.unwrap()) {
+                        read += 1;
+                    }
+                }
+                None => break,
+            }
+        }
+        if read == 0 {
+            return None;
+        }
+        let df = self.reader.frame(Some(self.rows));
+        self.rows += read;
+        Some(df)
+    }
+}
 /// A  builder that exposes some common settings for the CSV reader
 ///
 /// The following settings can be set from the builder
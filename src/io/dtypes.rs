@@ -1,4 +1,7 @@
 //! Contains helper functions for determining and converting between various DataTypes;
+use crate::core::dataframe::DataFrame;
+use crate::core::series::Series;
+use crate::enums::DataFrameErrors;
 use serde_json::Value;
 use std::f64::NAN;
 /// Determines whether a string value can be parsed to an integer.
@@ -41,6 +44,47 @@ pub fn str_to_big_int(val: &[String]) -> Vec<i64> {
         .map(|f| f.parse::<i64>().unwrap_or_default())
         .collect()
 }
+/// Add `values` to `df` as a column named `name`, with a dtype inferred from the first ten values
+///
+/// Empty strings are missing values, they are not used to infer the dtype
+/// and turn integer columns into float columns so they can hold `NaN`
+pub fn add_inferred_column(
+    df: &mut DataFrame,
+    name: &str,
+    values: &[String],
+) -> Result<(), DataFrameErrors> {
+    let sample = values
+        .iter()
+        .filter(|f| !f.is_empty())
+        .take(10)
+        .cloned()
+        .collect::<Vec<String>>();
+    let has_blanks = values.iter().any(String::is_empty);
+    if sample.is_empty() {
+        add_named_series(df, Series::from(values), name)
+    } else if is_int(&sample) && !has_blanks {
+        add_named_series(df, Series::from(str_to_int(values)), name)
+    } else if is_float(&sample) {
+        add_named_series(df, Series::from(str_to_float(values)), name)
+    } else if is_bool(&sample) && !has_blanks {
+        add_named_series(df, Series::from(str_to_bool(values)), name)
+    } else {
+        add_named_series(df, Series::from(values), name)
+    }
+}
+/// Name `series` `name` and add it to `df`
+///
+/// # Errors
+/// * `DifferentLength`: If the length of the Series differs from the DataFrame's
+/// * `ColumnNameErrors`: If the DataFrame already has a column named `name`
+pub fn add_named_series<T: Clone + Default + 'static>(
+    df: &mut DataFrame,
+    mut series: Series<T>,
+    name: &str,
+) -> Result<(), DataFrameErrors> {
+    series.set_name(name);
+    df.add_series(series, true)
+}
 
 pub fn json_is_int(val: &[Value]) -> bool {
     val.iter().all(serde_json::value::Value::is_i64)
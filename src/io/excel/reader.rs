@@ -0,0 +1,523 @@
+//! Read and deserialize spreadsheet files using [calamine]
+//!
+//! [calamine]:https://docs.rs/calamine/
+use crate::core::dataframe::DataFrame;
+use crate::core::index::date_time::{format_timestamp, parse_timestamp, NAT};
+use crate::core::series::Series;
+use crate::enums::DataTypes;
+use crate::io::dtypes::add_named_series;
+use crate::io::excel::{ExcelErrors, UNIX_EPOCH_SERIAL};
+use crate::io::utils::to_local_path;
+
+use calamine::{open_workbook_auto, Data, Range, Reader};
+#[cfg(feature = "regex")]
+use regex::Regex;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::path::Path;
+
+/// Selects which sheets of a workbook are read
+#[derive(Clone)]
+pub enum Sheet {
+    /// The sheet at a zero based position in the workbook
+    Index(usize),
+    /// Sheets with the given names, multiple names are separated with a `,` eg `"Sheet1,Sheet3"`
+    Names(String),
+    /// Every sheet whose name matches the regular expression
+    ///
+    /// Requires feature `regex`
+    #[cfg(feature = "regex")]
+    Regex(Regex),
+    /// Every sheet in the workbook
+    All,
+}
+impl Default for Sheet {
+    fn default() -> Self {
+        Sheet::Index(0)
+    }
+}
+impl From<usize> for Sheet {
+    fn from(index: usize) -> Self {
+        Sheet::Index(index)
+    }
+}
+impl From<&str> for Sheet {
+    fn from(names: &str) -> Self {
+        Sheet::Names(names.to_string())
+    }
+}
+#[cfg(feature = "regex")]
+impl From<Regex> for Sheet {
+    fn from(regex: Regex) -> Self {
+        Sheet::Regex(regex)
+    }
+}
+
+/// The Excel Reader
+///
+/// Empty and error cells are read as missing values, which become `NaN` for numeric
+/// and boolean columns and empty strings for string columns.
+///
+/// When dtypes are not given, they are inferred per column
+/// * Columns containing only integer cells become `I64`
+/// * Columns containing integer and float cells become `F64`
+/// * Columns containing only boolean cells become `BOOL`
+/// * Columns containing only date formatted cells become `DATETIME`
+/// * Columns containing only duration formatted cells (eg `[hh]:mm:ss`) become `I64` holding
+/// the duration in seconds
+/// * Everything else becomes `STRING`
+///
+/// Integer and boolean columns containing missing values are promoted to `F64`
+///
+/// # Dates
+/// Excel stores dates as the number of days since an epoch, which is 1900-01-00 or, for
+/// workbooks using the 1904 date system, 1904-01-01. Cells formatted as dates are detected and
+/// converted to seconds since the unix epoch using the date system of the workbook.
+///
+/// Columns of dates which aren't formatted as dates (they appear as plain numbers or text)
+/// can be converted using [`set_parse_dates`](#method.set_parse_dates), numbers are treated
+/// as serial dates in the date system given by [`set_date1904`](#method.set_date1904)
+///
+/// # Example
+/// ```no_run
+/// use dami::io::excel::{ExcelReader, Sheet};
+/// let frames = ExcelReader::new()
+///     .set_sheet(Sheet::All)
+///     .set_usecols("A:D")
+///     .unwrap()
+///     .set_skiprows(2)
+///     .read_sheets("data.xlsx")
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct ExcelReader {
+    sheet: Sheet,
+    headers: bool,
+    dtypes: Option<Vec<DataTypes>>,
+    // Zero based column positions, eg A is 0
+    usecols: Option<Vec<usize>>,
+    skiprows: usize,
+    parse_dates: Vec<String>,
+    date1904: bool,
+}
+impl Default for ExcelReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl ExcelReader {
+    /// Create a new reader
+    ///
+    /// By default the first sheet is read, the first row is used as headers, dtypes are inferred
+    /// and all columns are read
+    pub fn new() -> ExcelReader {
+        ExcelReader {
+            sheet: Sheet::default(),
+            headers: true,
+            dtypes: None,
+            usecols: None,
+            skiprows: 0,
+            parse_dates: vec![],
+            date1904: false,
+        }
+    }
+    /// Select the sheets to read
+    pub fn set_sheet<S: Into<Sheet>>(&mut self, sheet: S) -> &mut Self {
+        self.sheet = sheet.into();
+        self
+    }
+    /// Whether the first row (after skipped rows) contains column names
+    ///
+    /// If false columns are named by their position starting at `0`
+    pub fn set_headers(&mut self, headers: bool) -> &mut Self {
+        self.headers = headers;
+        self
+    }
+    /// Set the dtypes of the read columns instead of inferring them
+    ///
+    /// The length should be equal to the number of columns read
+    pub fn set_dtypes(&mut self, dtypes: Vec<DataTypes>) -> &mut Self {
+        self.dtypes = Some(dtypes);
+        self
+    }
+    /// Only read the columns in `usecols`
+    ///
+    /// `usecols` is a comma separated list of Excel column letters and ranges eg `"A:D"` or `"A,C,E:F"`
+    /// # Errors
+    /// `InvalidColumns`: If `usecols` is not a valid column list
+    pub fn set_usecols(&mut self, usecols: &str) -> Result<&mut Self, ExcelErrors> {
+        self.usecols = Some(parse_usecols(usecols)?);
+        Ok(self)
+    }
+    /// Skip the first `skiprows` rows of each sheet, the header row is the first row after them
+    pub fn set_skiprows(&mut self, skiprows: usize) -> &mut Self {
+        self.skiprows = skiprows;
+        self
+    }
+    /// Convert the named columns to `DATETIME` columns regardless of the cell format
+    ///
+    /// Numeric cells are read as Excel serial dates and text cells are parsed as
+    /// `YYYY-MM-DD[ HH:MM:SS]` or RFC 3339 dates, cells that cannot be converted become `NaT`
+    pub fn set_parse_dates(&mut self, columns: &[&str]) -> &mut Self {
+        self.parse_dates = columns.iter().map(|f| (*f).to_string()).collect();
+        self
+    }
+    /// Interpret numeric cells in [`parse_dates`](#method.set_parse_dates) columns using the
+    /// 1904 date system instead of the default 1900 date system
+    ///
+    /// Cells formatted as dates don't need this, the date system is read from the workbook
+    pub fn set_date1904(&mut self, date1904: bool) -> &mut Self {
+        self.date1904 = date1904;
+        self
+    }
+    /// Read the first sheet matching the selected sheets to a DataFrame
+    /// # Errors
+    /// * `Calamine`: If the workbook cannot be opened or the sheet cannot be read
+    /// * `SheetNotFound`: If no sheet matches
+    /// * `DtypesLength`: If the number of dtypes differs from the number of read columns
+    /// * `ColumnNotFound`: If a column in `parse_dates` isn't in the sheet
+    /// * `UnsupportedDtype`: If a dtype cannot be read from a spreadsheet
+    /// * `DataFrame`: If two columns have the same name
+    pub fn read<P: AsRef<Path>>(&self, path: P) -> Result<DataFrame, ExcelErrors> {
+        let mut workbook = open_workbook_auto(to_local_path(path))?;
+        let sheets = self.matching_sheets(&workbook.sheet_names())?;
+        let name = sheets
+            .first()
+            .ok_or_else(|| ExcelErrors::SheetNotFound(self.describe_sheet()))?;
+        let range = get_range(&mut workbook, name)?;
+        self.range_to_dataframe(&range)
+    }
+    /// Read every sheet matching the selected sheets returning a map of sheet names to DataFrames
+    /// # Errors
+    /// See [`read`](#method.read)
+    pub fn read_sheets<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<HashMap<String, DataFrame>, ExcelErrors> {
+        let mut workbook = open_workbook_auto(to_local_path(path))?;
+        let sheets = self.matching_sheets(&workbook.sheet_names())?;
+        if sheets.is_empty() {
+            return Err(ExcelErrors::SheetNotFound(self.describe_sheet()));
+        }
+        let mut frames = HashMap::with_capacity(sheets.len());
+        for name in sheets {
+            let range = get_range(&mut workbook, &name)?;
+            frames.insert(name, self.range_to_dataframe(&range)?);
+        }
+        Ok(frames)
+    }
+    /// Get the names of the sheets matched by `self.sheet` in workbook order
+    fn matching_sheets(&self, names: &[String]) -> Result<Vec<String>, ExcelErrors> {
+        let sheets = match self.sheet {
+            Sheet::Index(idx) => match names.get(idx) {
+                Some(name) => vec![name.clone()],
+                None => return Err(ExcelErrors::SheetNotFound(idx.to_string())),
+            },
+            Sheet::Names(ref wanted) => {
+                let mut sheets = vec![];
+                for name in wanted.split(',').map(str::trim) {
+                    if !names.iter().any(|f| f == name) {
+                        return Err(ExcelErrors::SheetNotFound(name.to_string()));
+                    }
+                    sheets.push(name.to_string());
+                }
+                sheets
+            }
+            #[cfg(feature = "regex")]
+            Sheet::Regex(ref regex) => names
+                .iter()
+                .filter(|f| regex.is_match(f))
+                .cloned()
+                .collect(),
+            Sheet::All => names.to_vec(),
+        };
+        Ok(sheets)
+    }
+    fn describe_sheet(&self) -> String {
+        match self.sheet {
+            Sheet::Index(idx) => idx.to_string(),
+            Sheet::Names(ref names) => names.clone(),
+            #[cfg(feature = "regex")]
+            Sheet::Regex(ref regex) => regex.as_str().to_string(),
+            Sheet::All => "any sheet".to_string(),
+        }
+    }
+    fn range_to_dataframe(&self, range: &Range<Data>) -> Result<DataFrame, ExcelErrors> {
+        // Positions of the read columns relative to the start of the range
+        let start_col = range.start().map_or(0, |(_, col)| col as usize);
+        let columns: Vec<Option<usize>> = match self.usecols {
+            Some(ref cols) => cols
+                .iter()
+                .map(|f| f.checked_sub(start_col).filter(|f| *f < range.width()))
+                .collect(),
+            None => (0..range.width()).map(Some).collect(),
+        };
+        let mut rows = range.rows().skip(self.skiprows);
+        let names: Vec<String> = if self.headers {
+            let header = rows.next().unwrap_or(&[]);
+            columns
+                .iter()
+                .enumerate()
+                .map(|(pos, col)| match col.and_then(|col| header.get(col)) {
+                    Some(Data::Empty) | Some(Data::Error(_)) | None => pos.to_string(),
+                    Some(cell) => cell.to_string(),
+                })
+                .collect()
+        } else {
+            (0..columns.len()).map(|f| f.to_string()).collect()
+        };
+        // Transpose rows into columns
+        let mut cells: Vec<Vec<Data>> = vec![vec![]; columns.len()];
+        for row in rows {
+            for (column, col) in cells.iter_mut().zip(columns.iter()) {
+                column.push(
+                    col.and_then(|col| row.get(col))
+                        .cloned()
+                        .unwrap_or(Data::Empty),
+                );
+            }
+        }
+        let mut dtypes = match self.dtypes {
+            Some(ref dtypes) => {
+                if dtypes.len() != columns.len() {
+                    return Err(ExcelErrors::DtypesLength(columns.len(), dtypes.len()));
+                }
+                dtypes.clone()
+            }
+            None => cells.iter().map(|f| infer_dtype(f)).collect(),
+        };
+        for column in &self.parse_dates {
+            match names.iter().position(|f| f == column) {
+                Some(pos) => dtypes[pos] = DataTypes::DATETIME,
+                None => return Err(ExcelErrors::ColumnNotFound(column.clone())),
+            }
+        }
+        let mut df = DataFrame::new();
+        for ((name, column), dtype) in names.iter().zip(cells).zip(dtypes) {
+            add_column(&mut df, name, &dtype, &column, self.date1904)?;
+        }
+        Ok(df)
+    }
+}
+fn get_range<RS, R>(workbook: &mut R, name: &str) -> Result<Range<Data>, ExcelErrors>
+where
+    RS: Read + Seek,
+    R: Reader<RS>,
+    ExcelErrors: From<R::Error>,
+{
+    workbook.worksheet_range(name).map_err(ExcelErrors::from)
+}
+/// Parse Excel column letters eg `"A:C,E"` into zero based column positions
+fn parse_usecols(usecols: &str) -> Result<Vec<usize>, ExcelErrors> {
+    let invalid = || ExcelErrors::InvalidColumns(usecols.to_string());
+    let mut columns = vec![];
+    for part in usecols.split(',').map(str::trim) {
+        let mut bounds = part.splitn(2, ':');
+        let start = column_position(bounds.next().unwrap_or("")).ok_or_else(invalid)?;
+        let end = match bounds.next() {
+            Some(end) => column_position(end).ok_or_else(invalid)?,
+            None => start,
+        };
+        if end < start {
+            return Err(invalid());
+        }
+        columns.extend(start..=end);
+    }
+    Ok(columns)
+}
+/// Convert a column name like `AB` to its zero based position
+fn column_position(letters: &str) -> Option<usize> {
+    let letters = letters.trim();
+    if letters.is_empty() || !letters.chars().all(|f| f.is_ascii_alphabetic()) {
+        return None;
+    }
+    letters
+        .to_ascii_uppercase()
+        .bytes()
+        .try_fold(0_usize, |acc, f| {
+            acc.checked_mul(26)?.checked_add(usize::from(f - b'A') + 1)
+        })
+        .map(|f| f - 1)
+}
+fn is_null(cell: &Data) -> bool {
+    matches!(cell, Data::Empty | Data::Error(_))
+}
+/// Infer the dtype of a column ignoring missing values
+fn infer_dtype(cells: &[Data]) -> DataTypes {
+    let values = cells.iter().filter(|f| !is_null(f));
+    if values.clone().all(|f| matches!(f, Data::Int(_))) {
+        DataTypes::I64
+    } else if values
+        .clone()
+        .all(|f| matches!(f, Data::Int(_) | Data::Float(_)))
+    {
+        DataTypes::F64
+    } else if values.clone().all(|f| matches!(f, Data::Bool(_))) {
+        DataTypes::BOOL
+    } else if values.clone().all(|f| match f {
+        Data::DateTime(value) => value.is_datetime(),
+        Data::DateTimeIso(_) => true,
+        _ => false,
+    }) {
+        DataTypes::DATETIME
+    } else if values.clone().all(|f| match f {
+        Data::DateTime(value) => value.is_duration(),
+        _ => false,
+    }) {
+        DataTypes::I64
+    } else {
+        DataTypes::STRING
+    }
+}
+#[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+fn cell_to_f64(cell: &Data) -> f64 {
+    match cell {
+        Data::Int(value) => *value as f64,
+        Data::Float(value) => *value,
+        Data::Bool(value) => {
+            if *value {
+                1.0
+            } else {
+                0.0
+            }
+        }
+        Data::String(value) => value.trim().parse().unwrap_or(f64::NAN),
+        Data::DateTime(value) if value.is_duration() => value.as_f64() * 86400.0,
+        Data::DateTime(value) => value.as_f64(),
+        _ => f64::NAN,
+    }
+}
+#[allow(clippy::cast_possible_truncation, clippy::float_cmp)]
+fn cell_to_i64(cell: &Data) -> Option<i64> {
+    match cell {
+        Data::Int(value) => Some(*value),
+        // Calamine reads numbers in xlsx files as floats
+        Data::Float(value) if value.trunc() == *value => Some(*value as i64),
+        Data::String(value) => value.trim().parse().ok(),
+        Data::DateTime(value) if value.is_duration() => {
+            value.as_duration().map(|f| f.num_seconds())
+        }
+        _ => None,
+    }
+}
+fn cell_to_bool(cell: &Data) -> Option<bool> {
+    match cell {
+        Data::Bool(value) => Some(*value),
+        Data::String(value) => value.trim().to_lowercase().parse().ok(),
+        _ => None,
+    }
+}
+/// Convert the cells of a column to a Series of `dtype` and add it to the DataFrame
+fn add_column(
+    df: &mut DataFrame,
+    name: &str,
+    dtype: &DataTypes,
+    cells: &[Data],
+    date1904: bool,
+) -> Result<(), ExcelErrors> {
+    let added = match dtype {
+        DataTypes::I64 | DataTypes::I32 => {
+            let values = cells.iter().map(cell_to_i64).collect::<Option<Vec<i64>>>();
+            match values {
+                Some(values) => add_named_series(df, Series::from(values), name),
+                // Promote to floats so missing values can be represented
+                None => add_named_series(df, Series::from(to_floats(cells)), name),
+            }
+        }
+        DataTypes::F64 | DataTypes::F32 => {
+            add_named_series(df, Series::from(to_floats(cells)), name)
+        }
+        DataTypes::BOOL => {
+            let values = cells
+                .iter()
+                .map(cell_to_bool)
+                .collect::<Option<Vec<bool>>>();
+            match values {
+                Some(values) => add_named_series(df, Series::from(values), name),
+                None => add_named_series(df, Series::from(to_floats(cells)), name),
+            }
+        }
+        DataTypes::STRING | DataTypes::STR => {
+            let values = cells
+                .iter()
+                .map(|f| match f {
+                    Data::Empty | Data::Error(_) => String::new(),
+                    Data::DateTime(value) if value.is_datetime() => {
+                        format_timestamp(cell_to_timestamp(f, date1904))
+                    }
+                    other => other.to_string(),
+                })
+                .collect::<Vec<String>>();
+            add_named_series(df, Series::from(values), name)
+        }
+        DataTypes::DATETIME => {
+            let values = cells
+                .iter()
+                .map(|f| cell_to_timestamp(f, date1904))
+                .collect::<Vec<i64>>();
+            let mut series = Series::from(values);
+            series.set_dtype(DataTypes::DATETIME);
+            add_named_series(df, series, name)
+        }
+        other => return Err(ExcelErrors::UnsupportedDtype(other.clone())),
+    };
+    added.map_err(ExcelErrors::from)
+}
+/// Convert a date cell to seconds since the unix epoch
+fn cell_to_timestamp(cell: &Data, date1904: bool) -> i64 {
+    match cell {
+        Data::DateTime(value) if value.is_datetime() => {
+            value.as_datetime().map_or(NAT, |f| f.timestamp())
+        }
+        Data::Int(_) | Data::Float(_) => serial_to_timestamp(cell_to_f64(cell), date1904),
+        Data::DateTimeIso(value) | Data::String(value) => parse_timestamp(value),
+        _ => NAT,
+    }
+}
+/// Convert an Excel serial date to seconds since the unix epoch
+#[allow(clippy::cast_possible_truncation)]
+fn serial_to_timestamp(serial: f64, date1904: bool) -> i64 {
+    if !serial.is_finite() {
+        return NAT;
+    }
+    let days = if date1904 {
+        serial + 1462.0
+    } else if serial < 60.0 {
+        // Excel considers 1900 a leap year so serials before 1900-03-01 are off by a day
+        serial + 1.0
+    } else {
+        serial
+    };
+    ((days - UNIX_EPOCH_SERIAL) * 86400.0).round() as i64
+}
+fn to_floats(cells: &[Data]) -> Vec<f64> {
+    cells.iter().map(cell_to_f64).collect()
+}
+
+/// Read a sheet of a spreadsheet file into a DataFrame
+///
+/// # Arguments
+/// * `path`: The path to the workbook, `xls`,`xlsx`,`xlsb` and `ods` files are supported
+/// * `sheet`: The sheet to read, either a zero based index, names of sheets or a regex.
+///  If more than one sheet matches, the first one is read, use [`ExcelReader::read_sheets`] to read all
+/// * `headers`: Whether the first row contains the column names
+/// * `dtypes`: The dtypes of the columns, if `None` they are inferred
+///
+/// # Errors
+/// See [`ExcelReader::read`]
+///
+/// [`ExcelReader::read`]: struct.ExcelReader.html#method.read
+/// [`ExcelReader::read_sheets`]: struct.ExcelReader.html#method.read_sheets
+pub fn read_excel<P: AsRef<Path>, S: Into<Sheet>>(
+    path: P,
+    sheet: S,
+    headers: bool,
+    dtypes: Option<Vec<DataTypes>>,
+) -> Result<DataFrame, ExcelErrors> {
+    let mut reader = ExcelReader::new();
+    reader.set_sheet(sheet).set_headers(headers);
+    if let Some(dtypes) = dtypes {
+        reader.set_dtypes(dtypes);
+    }
+    reader.read(path)
+}
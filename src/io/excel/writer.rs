@@ -0,0 +1,207 @@
+//! Write DataFrames to `xlsx` workbooks
+use crate::core::dataframe::DataFrame;
+use crate::core::index::date_time::NAT;
+use crate::enums::DataTypes;
+use crate::io::excel::{ExcelErrors, UNIX_EPOCH_SERIAL};
+
+use rust_xlsxwriter::{FormatBorder, Workbook, Worksheet};
+use std::path::Path;
+
+pub use rust_xlsxwriter::Format;
+
+/// Write one or more DataFrames to sheets of an `xlsx` workbook
+///
+/// # Example
+/// ```no_run
+/// use dami::prelude::*;
+/// use dami::io::excel::ExcelWriter;
+/// use ndarray::Array2;
+/// let df = DataFrame::from(Array2::<f64>::ones((3, 2)));
+/// let mut writer = ExcelWriter::new();
+/// writer.set_index(true).set_float_format("0.00");
+/// writer.add_sheet(&df, "ones").unwrap();
+/// writer.add_sheet(&df, "more ones").unwrap();
+/// writer.save("ones.xlsx").unwrap();
+/// ```
+pub struct ExcelWriter {
+    workbook: Workbook,
+    index: bool,
+    header_format: Format,
+    float_format: Option<Format>,
+    date_format: Format,
+}
+impl Default for ExcelWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl ExcelWriter {
+    /// Create a new writer with an empty workbook
+    ///
+    /// By default headers are bold with a bottom border, floats are written using the
+    /// `General` Excel format, datetimes are written using `yyyy-mm-dd hh:mm:ss` and the
+    /// index is not written
+    pub fn new() -> ExcelWriter {
+        ExcelWriter {
+            workbook: Workbook::new(),
+            index: false,
+            header_format: Format::new()
+                .set_bold()
+                .set_border_bottom(FormatBorder::Thin),
+            float_format: None,
+            date_format: Format::new().set_num_format("yyyy-mm-dd hh:mm:ss"),
+        }
+    }
+    /// Write the DataFrame index as the first column of each sheet
+    pub fn set_index(&mut self, index: bool) -> &mut Self {
+        self.index = index;
+        self
+    }
+    /// Set the format used for the header row (and the index column if written)
+    pub fn set_header_format(&mut self, format: Format) -> &mut Self {
+        self.header_format = format;
+        self
+    }
+    /// Set the Excel number format used for float columns eg `"0.00"` or `"0.0%"`
+    pub fn set_float_format(&mut self, num_format: &str) -> &mut Self {
+        self.float_format = Some(Format::new().set_num_format(num_format));
+        self
+    }
+    /// Set the Excel number format used for datetime columns eg `"yyyy-mm-dd"`
+    pub fn set_date_format(&mut self, num_format: &str) -> &mut Self {
+        self.date_format = Format::new().set_num_format(num_format);
+        self
+    }
+    /// Write `df` to a new sheet named `sheet_name`
+    ///
+    /// Sheets appear in the workbook in the order they are added
+    /// # Errors
+    /// * `Xlsx`: If the sheet name is invalid or already used, or the DataFrame doesn't fit in a sheet
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    pub fn add_sheet(
+        &mut self,
+        df: &DataFrame,
+        sheet_name: &str,
+    ) -> Result<&mut Self, ExcelErrors> {
+        let mut worksheet = Worksheet::new();
+        worksheet.set_name(sheet_name)?;
+        self.write_frame(&mut worksheet, df)?;
+        worksheet.set_freeze_panes(1, 0)?;
+        worksheet.autofit();
+        self.workbook.push_worksheet(worksheet);
+        Ok(self)
+    }
+    /// Save the workbook to `path`
+    /// # Errors
+    /// * `Xlsx`: If the file cannot be written
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<(), ExcelErrors> {
+        self.workbook.save(path)?;
+        Ok(())
+    }
+    #[allow(clippy::cast_possible_truncation, clippy::cast_precision_loss)]
+    fn write_frame(&self, sheet: &mut Worksheet, df: &DataFrame) -> Result<(), ExcelErrors> {
+        let dtypes = df.dtypes();
+        let mut col: u16 = 0;
+        if self.index {
+            sheet.write_blank(0, 0, &self.header_format)?;
+            for (row, label) in df.get_index().iter().enumerate() {
+                sheet.write_string_with_format(row as u32 + 1, 0, label, &self.header_format)?;
+            }
+            col += 1;
+        }
+        for name in df.columns() {
+            sheet.write_string_with_format(0, col, &name, &self.header_format)?;
+            match dtypes[&name] {
+                DataTypes::BOOL => {
+                    for (row, value) in df.get::<bool>(&name).unwrap().into_iter().enumerate() {
+                        sheet.write_boolean(row as u32 + 1, col, value)?;
+                    }
+                }
+                DataTypes::I32 => {
+                    for (row, value) in df.get::<i32>(&name).unwrap().into_iter().enumerate() {
+                        sheet.write_number(row as u32 + 1, col, value)?;
+                    }
+                }
+                DataTypes::I64 => {
+                    for (row, value) in df.get::<i64>(&name).unwrap().into_iter().enumerate() {
+                        sheet.write_number(row as u32 + 1, col, value as f64)?;
+                    }
+                }
+                DataTypes::F32 => {
+                    let values = df.get::<f32>(&name).unwrap().into_iter().map(f64::from);
+                    self.write_floats(sheet, col, values)?;
+                }
+                DataTypes::F64 => {
+                    let values = df.get::<f64>(&name).unwrap().into_iter();
+                    self.write_floats(sheet, col, values)?;
+                }
+                DataTypes::STRING => {
+                    for (row, value) in df.get::<String>(&name).unwrap().into_iter().enumerate() {
+                        sheet.write_string(row as u32 + 1, col, value)?;
+                    }
+                }
+                DataTypes::STR => {
+                    for (row, value) in df.get::<&str>(&name).unwrap().into_iter().enumerate() {
+                        sheet.write_string(row as u32 + 1, col, value)?;
+                    }
+                }
+                DataTypes::DATETIME => {
+                    for (row, value) in df.get::<i64>(&name).unwrap().into_iter().enumerate() {
+                        // Leave missing values as empty cells
+                        if value != NAT {
+                            let serial = value as f64 / 86400.0 + UNIX_EPOCH_SERIAL;
+                            sheet.write_number_with_format(
+                                row as u32 + 1,
+                                col,
+                                serial,
+                                &self.date_format,
+                            )?;
+                        }
+                    }
+                }
+                DataTypes::OBJECT => return Err(ExcelErrors::UnsupportedDtype(DataTypes::OBJECT)),
+            }
+            col += 1;
+        }
+        Ok(())
+    }
+    /// Write floats leaving `NaN`s as empty cells since Excel has no representation for them
+    #[allow(clippy::cast_possible_truncation)]
+    fn write_floats<I: Iterator<Item = f64>>(
+        &self,
+        sheet: &mut Worksheet,
+        col: u16,
+        values: I,
+    ) -> Result<(), ExcelErrors> {
+        for (row, value) in values.enumerate() {
+            if value.is_nan() {
+                continue;
+            }
+            match self.float_format {
+                Some(ref format) => {
+                    sheet.write_number_with_format(row as u32 + 1, col, value, format)?
+                }
+                None => sheet.write_number(row as u32 + 1, col, value)?,
+            };
+        }
+        Ok(())
+    }
+}
+
+impl DataFrame {
+    /// # Requires Feature
+    ///  > * `xlsx`
+    ///
+    /// Write the DataFrame to a sheet in a new `xlsx` workbook
+    ///
+    /// This uses the default [`ExcelWriter`] settings, to write multiple sheets to one workbook,
+    /// write the index or change the header and number formats, use an [`ExcelWriter`]
+    /// # Errors
+    /// * `Xlsx`: If the sheet name is invalid or the file cannot be written
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    ///
+    /// [`ExcelWriter`]: ../io/excel/struct.ExcelWriter.html
+    pub fn to_excel<P: AsRef<Path>>(&self, path: P, sheet_name: &str) -> Result<(), ExcelErrors> {
+        ExcelWriter::new().add_sheet(self, sheet_name)?.save(path)
+    }
+}
@@ -15,15 +15,161 @@
 //! - Each data column has a defined width specified as a number of characters that is always the same for all rows.
 //! - The data within each column is padded with spaces (or any character you specify) if it does not completely use all the characters allotted to it (empty space).
 //! - Each column must consistently use the same number of characters, same pad character and same alignment (left/right).
-use crate::core::series::Series;
-use crate::io::dtypes::{is_bool, is_float, is_int, str_to_bool, str_to_float, str_to_int};
-use crate::io::utils::read;
+use crate::enums::DataTypes;
+use crate::io::dtypes::add_inferred_column;
+use crate::io::utils::{is_url, open_reader, read, to_local_path};
 use crate::prelude::DataFrame;
 use std::cmp::min;
 use std::collections::HashMap;
+use std::fmt;
 use std::fmt::Debug;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Cursor, Write};
+use std::iter::once;
 use std::path::Path;
 
+/// Number of lines used to infer column extents when reading with [`ColSpecs::Infer`] through
+/// [`read_fwf`](../parser/fn.read_fwf.html)
+///
+/// [`ColSpecs::Infer`]: enum.ColSpecs.html#variant.Infer
+pub const INFER_NROWS: usize = 100;
+
+/// Errors that may occur when reading or writing fixed width files
+pub enum FWFError {
+    /// An error occurred reading or writing the file
+    IOError(std::io::Error),
+    /// The number of widths differs from the number of columns
+    WidthsLength(usize, usize),
+    /// A value (or column name) doesn't fit in its field, holds the column and the value
+    Overflow(String, String),
+    /// The column has an `object` dtype which cannot be formatted
+    UnsupportedDtype(String),
+    /// A line ends before the start of a field, holds the line number (starting at 1),
+    /// the minimum number of characters and the number of characters in the line
+    ShortLine(usize, usize, usize),
+}
+impl fmt::Debug for FWFError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IOError(err) => write!(f, "{}", err),
+            Self::WidthsLength(columns, widths) => write!(
+                f,
+                "Expected {} widths, one for each column but got {}",
+                columns, widths
+            ),
+            Self::Overflow(column, value) => write!(
+                f,
+                "Value `{}` in column {} is wider than the column width",
+                value, column
+            ),
+            Self::UnsupportedDtype(column) => write!(
+                f,
+                "Column {} has dtype object which cannot be written to a fixed width file",
+                column
+            ),
+            Self::ShortLine(line, expected, found) => write!(
+                f,
+                "Line {} has {} characters but at least {} are needed to reach every field",
+                line, found, expected
+            ),
+        }
+    }
+}
+impl From<std::io::Error> for FWFError {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(err)
+    }
+}
+
+/// Locations of the fields in each line of a fixed width file
+#[derive(Clone, Debug)]
+pub enum ColSpecs {
+    /// Half open `(from, to)` character extents of each field
+    Specs(Vec<(usize, usize)>),
+    /// Widths of contiguous fields starting from the beginning of each line
+    Widths(Vec<usize>),
+    /// Infer the extents from the first `n` lines using [`infer_colspecs`](fn.infer_colspecs.html)
+    Infer(usize),
+}
+impl From<Vec<(usize, usize)>> for ColSpecs {
+    fn from(specs: Vec<(usize, usize)>) -> Self {
+        Self::Specs(specs)
+    }
+}
+impl ColSpecs {
+    /// Resolve the field extents, `lines` are the lines of the file
+    fn resolve<S: AsRef<str>>(&self, lines: &[S]) -> Vec<(usize, usize)> {
+        match self {
+            Self::Specs(specs) => specs.clone(),
+            Self::Widths(widths) => {
+                let mut start = 0;
+                widths
+                    .iter()
+                    .map(|width| {
+                        start += width;
+                        (start - width, start)
+                    })
+                    .collect()
+            }
+            Self::Infer(n) => infer_colspecs(&lines[..min(*n, lines.len())]),
+        }
+    }
+}
+/// Infer the extents of fixed width fields from whitespace shared by all `lines`
+///
+/// A character position is part of a field if any line has a non whitespace character there,
+/// each run of such positions becomes one half open `(from, to)` extent, like `colspecs='infer'`
+/// in pandas. Positions are counted in characters, not bytes.
+///
+/// # Example
+/// ```
+/// use dami::io::fwf::infer_colspecs;
+/// let lines = ["id     value", "id8141 360.2", "id1594  44.9"];
+/// assert_eq!(infer_colspecs(&lines), vec![(0, 6), (7, 12)]);
+/// ```
+pub fn infer_colspecs<S: AsRef<str>>(lines: &[S]) -> Vec<(usize, usize)> {
+    let mut mask: Vec<bool> = vec![];
+    for line in lines {
+        for (pos, c) in line
+            .as_ref()
+            .trim_end_matches(&['\r', '\n'][..])
+            .chars()
+            .enumerate()
+        {
+            if pos >= mask.len() {
+                mask.resize(pos + 1, false);
+            }
+            mask[pos] |= !c.is_whitespace();
+        }
+    }
+    let mut specs = vec![];
+    let mut start = None;
+    for (pos, filled) in mask.iter().enumerate() {
+        match (start, filled) {
+            (None, true) => start = Some(pos),
+            (Some(from), false) => {
+                specs.push((from, pos));
+                start = None;
+            }
+            _ => (),
+        }
+    }
+    if let Some(from) = start {
+        specs.push((from, mask.len()));
+    }
+    specs
+}
+/// Alignment of values inside their fields when writing fixed width files
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Alignment {
+    /// Pad values with spaces on the right
+    Left,
+    /// Pad values with spaces on the left
+    Right,
+    /// Right align numeric and boolean columns and left align strings and datetimes
+    Auto,
+}
+
 ///The The Fixed Width File Reader
 #[derive(Clone)]
 pub struct FWFReader<'a> {
@@ -104,45 +250,79 @@ impl<'a> FWFReader<'a> {
     /// To set a separate line terminator,
     /// pass the argument `line_terminator` with your custom line terminator to be used
     ///
-    /// If the're is no data inside colspecs or an out of bound error the data is skipped
-    /// See also [read](#method.read)
+    /// See [read_with](#method.read_with) for how fields are extracted and for errors,
+    /// see also [read](#method.read)
+    #[allow(clippy::missing_errors_doc)]
     pub fn read_with_colspecs<P: AsRef<Path> + Debug + Clone>(
         &mut self,
         path: P,
         colspecs: &[(usize, usize)],
         settings: HashMap<&'a str, &'a str>,
-    ) -> Self {
-        let data = read(path);
-        let line_sep = settings.get("line_terminator").unwrap_or(&"\n");
-        let split_data: Vec<String> = data
-            .split(line_sep)
-            .collect::<Vec<&str>>()
-            .into_iter()
-            .map(std::string::ToString::to_string)
-            .collect::<Vec<String>>();
-        for data in split_data {
-            let mut holder = Vec::new();
-            for numbers in colspecs {
-                let data_to_be_pushed = data.to_string().clone();
-                let temp = data_to_be_pushed.get(numbers.0..numbers.1);
-                match temp {
-                    Some(data) => {
-                        // RFC says we respect spaces :)
-                        if !data.trim().is_empty() {
-                            holder.push(data.trim().to_string())
-                        }
-                    }
-                    None => continue,
+    ) -> Result<Self, FWFError> {
+        self.read_with(path, &ColSpecs::Specs(colspecs.to_vec()), settings)
+    }
+    /// Read using field extents, field widths or extents inferred from the first lines of the file
+    ///
+    /// Local files are read one line at a time. Extents are counted in characters so multi-byte
+    /// UTF-8 text is sliced correctly, fields are `trim()`ed and blank fields are read as
+    /// missing values (`NaN` in numeric columns, empty strings in string columns). Blank lines are skipped.
+    ///
+    /// To set a separate line terminator,
+    /// pass the argument `line_terminator` with your custom line terminator to be used
+    /// # Errors
+    /// * `ShortLine`: If a line ends before the start of one of the fields, a line may end
+    /// inside the last field
+    /// * `IOError`: If the file cannot be opened or read
+    /// # Example
+    /// ```no_run
+    /// use dami::io::fwf::{ColSpecs, FWFReader};
+    /// use std::collections::HashMap;
+    /// let df = FWFReader::new()
+    ///     .read_with("bar.txt", &ColSpecs::Widths(vec![6, 13, 13, 10]), HashMap::new())
+    ///     .unwrap()
+    ///     .to_dataframe();
+    /// ```
+    pub fn read_with<P: AsRef<Path> + Debug + Clone>(
+        &mut self,
+        path: P,
+        colspecs: &ColSpecs,
+        settings: HashMap<&'a str, &'a str>,
+    ) -> Result<Self, FWFError> {
+        let terminator = settings.get("line_terminator").unwrap_or(&"\n");
+        let mut lines = Lines::new(open(path)?, terminator);
+        // Lines used to infer the extents are kept and parsed afterwards
+        let mut buffered = vec![];
+        if let ColSpecs::Infer(n) = colspecs {
+            while buffered.len() < *n {
+                match lines.next_line()? {
+                    Some(line) => buffered.push(line),
+                    None => break,
                 }
             }
-            if self.headers.is_empty() {
-                self.smart_push(holder, true);
-            } else {
-                self.smart_push(holder, false);
+        }
+        let colspecs = colspecs.resolve(&buffered);
+        let min_len = colspecs.iter().map(|f| f.0 + 1).max().unwrap_or(0);
+        let mut buffered = buffered.into_iter();
+        let mut line_no = 0;
+        loop {
+            let line = match buffered.next() {
+                Some(line) => line,
+                None => match lines.next_line()? {
+                    Some(line) => line,
+                    None => break,
+                },
+            };
+            line_no += 1;
+            if line.trim().is_empty() {
+                continue;
             }
+            let fields = split_fields(&line, &colspecs, min_len)
+                .map_err(|found| FWFError::ShortLine(line_no, min_len, found))?;
+            let headers = self.headers.is_empty();
+            self.smart_push(fields, headers);
         }
         self.settings = settings;
-        self.own_it()
+        Ok(self.own_it())
     }
     fn smart_push(&mut self, data: Vec<String>, headers: bool) {
         if headers {
@@ -162,28 +342,179 @@ impl<'a> FWFReader<'a> {
     }
     /// Return the fwf file as a DataFrame
     pub fn to_dataframe(&self) -> DataFrame {
-        let size = min(10, self.data[0].len());
         let mut df = DataFrame::new();
         for (i, j) in self.data.iter().enumerate() {
             let header = self.headers.get(i).unwrap();
-            if is_int(&j[0..size]) {
-                let mut series = Series::from(str_to_int(j));
-                series.set_name(header.as_str());
-                df.add_series(series, true).unwrap();
-            } else if is_float(&j[0..size]) {
-                let mut series = Series::from(str_to_float(j));
-                series.set_name(header.as_str());
-                df.add_series(series, true).unwrap();
-            } else if is_bool(&j[0..size]) {
-                let mut series = Series::from(str_to_bool(j));
-                series.set_name(header.as_str());
-                df.add_series(series, true).unwrap();
-            } else {
-                let mut series = Series::from(j.as_slice());
-                series.set_name(header.as_str());
-                df.add_series(series, true).unwrap();
-            }
+            add_inferred_column(&mut df, header, j).unwrap();
         }
         df
     }
 }
+/// Open a local file for buffered reading
+///
+/// Remote and compressed files are fetched or decompressed into memory by [`read`]
+fn open<P: AsRef<Path> + Debug + Clone>(path: P) -> Result<Box<dyn BufRead>, FWFError> {
+    let path = to_local_path(path);
+    let name = path.to_string_lossy().to_string();
+    let compressed = [".zip", ".lzma", ".lzma2", ".xz"]
+        .iter()
+        .any(|f| name.ends_with(f));
+    if is_url(&name) {
+        return Ok(open_reader(path));
+    }
+    if compressed {
+        return Ok(Box::new(Cursor::new(read(path).into_bytes())));
+    }
+    Ok(Box::new(BufReader::new(File::open(path)?)))
+}
+/// Lines of a reader split on a custom terminator
+struct Lines {
+    reader: Box<dyn BufRead>,
+    terminator: Vec<u8>,
+    buf: Vec<u8>,
+}
+impl Lines {
+    fn new(reader: Box<dyn BufRead>, terminator: &str) -> Lines {
+        Lines {
+            reader,
+            terminator: terminator.as_bytes().to_vec(),
+            buf: Vec::new(),
+        }
+    }
+    /// Read the next line without its terminator, `\r\n` is accepted when the terminator is `\n`
+    fn next_line(&mut self) -> Result<Option<String>, FWFError> {
+        self.buf.clear();
+        let last = *self.terminator.last().unwrap_or(&b'\n');
+        loop {
+            let read = self.reader.read_until(last, &mut self.buf)?;
+            if read == 0 || self.buf.ends_with(&self.terminator) {
+                break;
+            }
+        }
+        if self.buf.is_empty() {
+            return Ok(None);
+        }
+        if self.buf.ends_with(&self.terminator) {
+            self.buf.truncate(self.buf.len() - self.terminator.len());
+            if self.terminator == b"\n" && self.buf.last() == Some(&b'\r') {
+                self.buf.pop();
+            }
+        }
+        Ok(Some(String::from_utf8_lossy(&self.buf).into_owned()))
+    }
+}
+/// Split `line` into trimmed fields using character extents
+///
+/// Returns the number of characters in the line if it is shorter than `min_len`
+fn split_fields(
+    line: &str,
+    colspecs: &[(usize, usize)],
+    min_len: usize,
+) -> Result<Vec<String>, usize> {
+    // Byte offset of each character and of the end of the line
+    let offsets = line
+        .char_indices()
+        .map(|(pos, _)| pos)
+        .chain(once(line.len()))
+        .collect::<Vec<usize>>();
+    let chars = offsets.len() - 1;
+    if chars < min_len {
+        return Err(chars);
+    }
+    Ok(colspecs
+        .iter()
+        .map(|(from, to)| {
+            let from = offsets[min(*from, chars)];
+            let to = offsets[min(*to, chars)];
+            line.get(from..to).unwrap_or("").trim().to_string()
+        })
+        .collect())
+}
+
+impl DataFrame {
+    /// Write the DataFrame as a fixed width file
+    ///
+    /// The first line holds the column names, each following line holds one row with the value of
+    /// each column padded with spaces to its width in `widths`. Widths are counted in characters,
+    /// `NaN`s and missing datetimes are written as blank fields.
+    /// # Errors
+    /// * `WidthsLength`: If `widths` doesn't contain one width for each column
+    /// * `Overflow`: If a value or column name is wider than its column
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    /// * `IOError`: If writing to `writer` fails
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use dami::io::fwf::Alignment;
+    /// use ndarray::arr2;
+    /// let df = DataFrame::from(arr2(&[[1, 20], [300, 4]]));
+    /// let mut out = Vec::new();
+    /// df.to_fwf(&mut out, &[4, 4], Alignment::Right).unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap(), "   0   1\n   1  20\n 300   4\n");
+    /// ```
+    pub fn to_fwf<W: Write>(
+        &self,
+        writer: &mut W,
+        widths: &[usize],
+        alignment: Alignment,
+    ) -> Result<(), FWFError> {
+        let names = self.columns();
+        if names.len() != widths.len() {
+            return Err(FWFError::WidthsLength(names.len(), widths.len()));
+        }
+        let dtypes = self.dtypes();
+        let mut columns = Vec::with_capacity(names.len());
+        for name in &names {
+            let values = self
+                .get_strings(name)
+                .ok_or_else(|| FWFError::UnsupportedDtype(name.clone()))?;
+            let right = match alignment {
+                Alignment::Left => false,
+                Alignment::Right => true,
+                Alignment::Auto => !matches!(
+                    dtypes[name],
+                    DataTypes::STRING | DataTypes::STR | DataTypes::DATETIME
+                ),
+            };
+            columns.push((values, right));
+        }
+        let mut line = String::new();
+        for ((name, width), (_, right)) in names.iter().zip(widths).zip(&columns) {
+            pad(&mut line, name, name, *width, *right)?;
+        }
+        line.push('\n');
+        writer.write_all(line.as_bytes())?;
+        for row in 0..self.len() {
+            line.clear();
+            for ((name, width), (values, right)) in names.iter().zip(widths).zip(&columns) {
+                pad(&mut line, name, &values[row], *width, *right)?;
+            }
+            line.push('\n');
+            writer.write_all(line.as_bytes())?;
+        }
+        writer.flush()?;
+        Ok(())
+    }
+}
+/// Push `value` padded with spaces to `width` characters onto `line`
+fn pad(
+    line: &mut String,
+    column: &str,
+    value: &str,
+    width: usize,
+    right: bool,
+) -> Result<(), FWFError> {
+    let len = value.chars().count();
+    if len > width {
+        return Err(FWFError::Overflow(column.to_string(), value.to_string()));
+    }
+    let padding = " ".repeat(width - len);
+    if right {
+        line.push_str(&padding);
+        line.push_str(value);
+    } else {
+        line.push_str(value);
+        line.push_str(&padding);
+    }
+    Ok(())
+}
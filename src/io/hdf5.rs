@@ -3,9 +3,20 @@
 //! This module exports functions used in handling of hdf5  files
 use crate::core::dataframe::DataFrame;
 use crate::core::series::Series;
-use hdf5::{File, H5Type};
+use crate::enums::DataTypes;
+use crate::io::dtypes::add_named_series;
+use crate::io::utils::to_local_path;
+use hdf5::types::{CompoundField, CompoundType, TypeDescriptor, VarLenUnicode};
+use hdf5::{Dataset, Datatype, File, Group, H5Type};
+use hdf5_sys::h5::H5free_memory;
+use hdf5_sys::h5d::H5Dread;
+use hdf5_sys::h5p::H5P_DEFAULT;
+use hdf5_sys::h5s::H5S_ALL;
 
 use ndarray::{Array1, Array2};
+use std::convert::TryInto;
+use std::ffi::CStr;
+use std::os::raw::c_char;
 
 /// Read a HDF5 dataSet to a dami [`Series`]
 /// # Arguments
@@ -24,7 +35,7 @@ use ndarray::{Array1, Array2};
 ///
 /// * If the array cannot be converted into type `T`
 pub fn read_dataset_to_series<T: Clone + H5Type + Default>(file: &str, dataset: &str) -> Series<T> {
-    let file = File::open(file).unwrap();
+    let file = File::open(to_local_path(file)).unwrap();
     let dataset = file
         .dataset(dataset)
         .expect("Dataset could not be loaded \n");
@@ -32,11 +43,603 @@ pub fn read_dataset_to_series<T: Clone + H5Type + Default>(file: &str, dataset:
     return Series::from(array);
 }
 /// Read hdf5 to a DataFrame
+///
+/// If `dataset` names a group written by [`DataFrame::to_hdf5`] or [`HDF5Writer`] the columns are
+/// read back with their names, dtypes and index and `T` is ignored, the same goes for datasets
+/// with a compound datatype which are read as in [`read_hdf5_table`]. Otherwise `dataset` should be
+/// a two dimensional dataset of type `T`, use [`read_hdf5_group`] to read a group of one dimensional
+/// datasets of different types
+/// # Panics
+/// * If the file cannot be opened
+///
+/// * If the dataset is not a two dimensional array, a table or a group written by [`HDF5Writer`]
+///
+/// [`DataFrame::to_hdf5`]: ../../core/dataframe/struct.DataFrame.html#method.to_hdf5
+/// [`HDF5Writer`]: struct.HDF5Writer.html
+/// [`read_hdf5_table`]: fn.read_hdf5_table.html
+/// [`read_hdf5_group`]: fn.read_hdf5_group.html
 pub fn read_hdf5<T: Clone + H5Type + Default>(file: &str, dataset: &str) -> DataFrame {
-    let file = File::open(file).unwrap();
+    let file = File::open(to_local_path(file)).unwrap();
+    if let Ok(group) = file.group(dataset) {
+        if group
+            .attr_names()
+            .unwrap_or_default()
+            .iter()
+            .any(|f| f == "columns")
+        {
+            return read_frame(&group).expect("Could not read group to a DataFrame");
+        }
+    }
     let dataset = file.dataset(dataset).expect("Dataset could not be loaded");
+    if let Ok(TypeDescriptor::Compound(_)) = dataset.dtype().and_then(|f| f.to_descriptor()) {
+        return read_table(&dataset).expect("Could not read table to a DataFrame");
+    }
     let arr: Array2<T> = dataset
         .read_2d()
         .expect("Could not read DataSet to 2-D array");
     DataFrame::from(arr)
 }
+
+/// Name of the dataset holding the DataFrame index inside a group written by [`HDF5Writer`]
+const INDEX_DATASET: &str = "__index__";
+
+/// Write DataFrames to groups of a HDF5 file
+///
+/// Each column is stored as its own one dimensional dataset inside the group, the column names
+/// and dtypes are stored in the `columns` and `dtypes` attributes of the group and the index
+/// is stored in the `__index__` dataset. Groups written this way can be read back using
+/// [`read_hdf5`](fn.read_hdf5.html).
+///
+/// | DataType          | HDF5 type              |
+/// |-------------------|------------------------|
+/// | `BOOL`            | `H5T_ENUM` of `i8`     |
+/// | `I32`,`I64`       | `H5T_STD_I32LE`,`H5T_STD_I64LE` |
+/// | `F32`,`F64`       | `H5T_IEEE_F32LE`,`H5T_IEEE_F64LE` |
+/// | `STRING`,`STR`    | variable length UTF-8 string |
+/// | `DATETIME`        | `H5T_STD_I64LE` (seconds since the epoch) |
+///
+/// # Example
+/// ```no_run
+/// use dami::prelude::*;
+/// use dami::io::hdf5::HDF5Writer;
+/// use ndarray::Array2;
+/// let df = DataFrame::from(Array2::<f64>::ones((1000, 4)));
+/// HDF5Writer::new()
+///     .set_chunk(256)
+///     .set_gzip(4)
+///     .write(&df, "ones.h5", "ones")
+///     .unwrap();
+/// ```
+#[derive(Default, Clone)]
+pub struct HDF5Writer {
+    chunk: Option<usize>,
+    gzip: Option<u8>,
+}
+impl HDF5Writer {
+    /// Create a new writer which writes contiguous uncompressed datasets
+    pub fn new() -> HDF5Writer {
+        HDF5Writer::default()
+    }
+    /// Store datasets in chunks of `chunk` rows
+    pub fn set_chunk(&mut self, chunk: usize) -> &mut Self {
+        self.chunk = Some(chunk);
+        self
+    }
+    /// Compress datasets using gzip with compression level `level` (0-9)
+    ///
+    /// Compression requires chunked datasets, if no chunk size was set one is chosen
+    pub fn set_gzip(&mut self, level: u8) -> &mut Self {
+        self.gzip = Some(level);
+        self
+    }
+    /// Write the DataFrame to `group` in the HDF5 file at `path`
+    ///
+    /// The file is created if it doesn't exist, other groups in the file are left untouched.
+    /// # Errors
+    /// * If the file cannot be opened or created
+    /// * If `group` already exists
+    /// * If the DataFrame contains `object` columns
+    /// * If a column is named `__index__`, the name of the dataset holding the index
+    pub fn write(&self, df: &DataFrame, path: &str, group: &str) -> Result<(), hdf5::Error> {
+        let names = df.columns();
+        if names.iter().any(|f| f == INDEX_DATASET) {
+            return Err(hdf5::Error::from(format!(
+                "Column name {} is reserved for the index",
+                INDEX_DATASET
+            )));
+        }
+        let file = File::append(path)?;
+        let group = file.create_group(group)?;
+        let dtypes = df.dtypes();
+        for name in &names {
+            match dtypes[name] {
+                DataTypes::BOOL => {
+                    self.write_dataset(&group, name, df.get::<bool>(name).unwrap().to_vec())?
+                }
+                DataTypes::I32 => {
+                    self.write_dataset(&group, name, df.get::<i32>(name).unwrap().to_vec())?
+                }
+                DataTypes::I64 | DataTypes::DATETIME => {
+                    self.write_dataset(&group, name, df.get::<i64>(name).unwrap().to_vec())?
+                }
+                DataTypes::F32 => {
+                    self.write_dataset(&group, name, df.get::<f32>(name).unwrap().to_vec())?
+                }
+                DataTypes::F64 => {
+                    self.write_dataset(&group, name, df.get::<f64>(name).unwrap().to_vec())?
+                }
+                DataTypes::STRING => {
+                    let values = df.get::<String>(name).unwrap().to_vec();
+                    self.write_dataset(&group, name, to_unicode(&values))?
+                }
+                DataTypes::STR => {
+                    let values = df.get::<&str>(name).unwrap().to_vec();
+                    self.write_dataset(&group, name, to_unicode(&values))?
+                }
+                DataTypes::OBJECT => {
+                    return Err(hdf5::Error::from(format!(
+                        "Column {} has dtype object which cannot be written to HDF5",
+                        name
+                    )))
+                }
+            }
+        }
+        self.write_dataset(&group, INDEX_DATASET, to_unicode(&df.get_index()))?;
+        let dtype_names = names
+            .iter()
+            .map(|f| format!("{:?}", dtypes[f]))
+            .collect::<Vec<String>>();
+        write_attribute(&group, "columns", &to_unicode(&names))?;
+        write_attribute(&group, "dtypes", &to_unicode(&dtype_names))?;
+        Ok(())
+    }
+    fn write_dataset<T: H5Type>(
+        &self,
+        group: &Group,
+        name: &str,
+        values: Vec<T>,
+    ) -> Result<(), hdf5::Error> {
+        let len = values.len();
+        let mut builder = group.new_dataset::<T>();
+        // Empty datasets cannot be chunked
+        if len > 0 {
+            match (self.chunk, self.gzip) {
+                (Some(chunk), _) => {
+                    builder.chunk(chunk.min(len).max(1));
+                }
+                (None, Some(_)) => {
+                    builder.chunk(len.min(DEFAULT_CHUNK));
+                }
+                (None, None) => (),
+            }
+            if let Some(level) = self.gzip {
+                builder.gzip(level);
+            }
+        }
+        let dataset = builder.create(name, len)?;
+        dataset.write(&Array1::from(values))?;
+        Ok(())
+    }
+}
+/// Chunk size used when compressing without an explicit chunk size
+const DEFAULT_CHUNK: usize = 65536;
+
+fn to_unicode<S: AsRef<str>>(values: &[S]) -> Vec<VarLenUnicode> {
+    values
+        .iter()
+        // Strings containing NUL bytes cannot be stored in HDF5
+        .map(|f| {
+            f.as_ref()
+                .replace('\0', "")
+                .parse::<VarLenUnicode>()
+                .unwrap()
+        })
+        .collect()
+}
+fn write_attribute(group: &Group, name: &str, values: &[VarLenUnicode]) -> Result<(), hdf5::Error> {
+    let attr = group
+        .new_attr::<VarLenUnicode>()
+        .create(name, values.len())?;
+    attr.write(values)
+}
+fn read_attribute(group: &Group, name: &str) -> Result<Vec<String>, hdf5::Error> {
+    let values = group.attr(name)?.read_raw::<VarLenUnicode>()?;
+    Ok(values.iter().map(|f| f.as_str().to_string()).collect())
+}
+/// Read a dataset into a Series of `dtype`
+fn read_column(
+    group: &Group,
+    name: &str,
+    dtype: &DataTypes,
+    df: &mut DataFrame,
+    index: &[String],
+) -> Result<(), hdf5::Error> {
+    let dataset = group.dataset(name)?;
+    match dtype {
+        DataTypes::BOOL => add_series(df, Series::from(dataset.read_raw::<bool>()?), name, index),
+        DataTypes::I32 => add_series(df, Series::from(dataset.read_raw::<i32>()?), name, index),
+        DataTypes::I64 => add_series(df, Series::from(dataset.read_raw::<i64>()?), name, index),
+        DataTypes::F32 => add_series(df, Series::from(dataset.read_raw::<f32>()?), name, index),
+        DataTypes::F64 => add_series(df, Series::from(dataset.read_raw::<f64>()?), name, index),
+        DataTypes::DATETIME => {
+            let mut series = Series::from(dataset.read_raw::<i64>()?);
+            series.set_dtype(DataTypes::DATETIME);
+            add_series(df, series, name, index)
+        }
+        DataTypes::STRING | DataTypes::STR | DataTypes::OBJECT => {
+            let values = dataset
+                .read_raw::<VarLenUnicode>()?
+                .iter()
+                .map(|f| f.as_str().to_string())
+                .collect::<Vec<String>>();
+            add_series(df, Series::from(values), name, index)
+        }
+    }
+}
+fn add_series<T: Clone + Default + 'static>(
+    df: &mut DataFrame,
+    mut series: Series<T>,
+    name: &str,
+    index: &[String],
+) -> Result<(), hdf5::Error> {
+    if index.len() == series.len() {
+        series.set_index(index.to_vec());
+    }
+    add_named_series(df, series, name).map_err(|err| hdf5::Error::from(format!("{:?}", err)))
+}
+/// Read a group written by [`HDF5Writer`](struct.HDF5Writer.html) into a DataFrame
+fn read_frame(group: &Group) -> Result<DataFrame, hdf5::Error> {
+    let names = read_attribute(group, "columns")?;
+    let dtypes = read_attribute(group, "dtypes")?;
+    let index = if group.link_exists(INDEX_DATASET) {
+        group
+            .dataset(INDEX_DATASET)?
+            .read_raw::<VarLenUnicode>()?
+            .iter()
+            .map(|f| f.as_str().to_string())
+            .collect()
+    } else {
+        vec![]
+    };
+    let mut df = DataFrame::new();
+    for (name, dtype) in names.iter().zip(dtypes.iter()) {
+        let dtype = dtype.parse::<DataTypes>().map_err(hdf5::Error::from)?;
+        read_column(group, name, &dtype, &mut df, &index)?;
+    }
+    Ok(df)
+}
+
+impl DataFrame {
+    /// # Requires Feature
+    ///  > * `hdf5`
+    ///
+    /// Write the DataFrame to `group` in the HDF5 file at `path`
+    ///
+    /// See [`HDF5Writer`] for the layout of the group and for writing chunked or compressed datasets
+    /// # Errors
+    /// * If the file cannot be opened or created
+    /// * If `group` already exists
+    /// * If the DataFrame contains `object` columns
+    /// * If a column is named `__index__`
+    ///
+    /// [`HDF5Writer`]: ../io/hdf5/struct.HDF5Writer.html
+    pub fn to_hdf5(&self, path: &str, group: &str) -> Result<(), hdf5::Error> {
+        HDF5Writer::new().write(self, path, group)
+    }
+}
+
+/// List the paths of all datasets in a HDF5 file
+///
+/// Groups are walked recursively and paths are returned relative to the root group
+/// eg `["frames/ones/0", "measurements"]`, these can be passed to [`read_hdf5_table`],
+/// [`read_dataset_to_series`] or [`read_hdf5`]
+/// # Errors
+/// * If the file cannot be opened
+///
+/// [`read_hdf5_table`]: fn.read_hdf5_table.html
+/// [`read_dataset_to_series`]: fn.read_dataset_to_series.html
+/// [`read_hdf5`]: fn.read_hdf5.html
+pub fn list_datasets(file: &str) -> Result<Vec<String>, hdf5::Error> {
+    let file = File::open(to_local_path(file))?;
+    let mut datasets = vec![];
+    walk_group(&file, "", &mut datasets)?;
+    Ok(datasets)
+}
+fn walk_group(group: &Group, prefix: &str, datasets: &mut Vec<String>) -> Result<(), hdf5::Error> {
+    for name in group.member_names()? {
+        let path = format!("{}{}", prefix, name);
+        if group.dataset(&name).is_ok() {
+            datasets.push(path);
+        } else if let Ok(child) = group.group(&name) {
+            walk_group(&child, &format!("{}/", path), datasets)?;
+        }
+    }
+    Ok(())
+}
+
+/// Read the one dimensional datasets of a HDF5 group into a DataFrame
+///
+/// Each dataset becomes a column named after the dataset, with a dtype chosen from the
+/// datatype stored in the file
+///
+/// | HDF5 type                         | DataType |
+/// |-----------------------------------|----------|
+/// | signed integers up to 32 bits, unsigned up to 16 bits | `I32` |
+/// | 64 bit signed, 32 and 64 bit unsigned integers | `I64` |
+/// | 32 bit floats                     | `F32`    |
+/// | 64 bit floats                     | `F64`    |
+/// | booleans and enums with `FALSE`/`TRUE` members | `BOOL` |
+/// | other enums                       | integer of the enum base type |
+/// | fixed and variable length strings | `STRING` |
+///
+/// Sub-groups, multi-dimensional datasets and datasets of other types (arrays, compounds,
+/// references) are skipped. Groups written by [`DataFrame::to_hdf5`] are read back with their
+/// original dtypes and index.
+/// # Errors
+/// * If the file or group cannot be opened
+/// * If the datasets have different lengths
+///
+/// [`DataFrame::to_hdf5`]: ../../core/dataframe/struct.DataFrame.html#method.to_hdf5
+pub fn read_hdf5_group(file: &str, group: &str) -> Result<DataFrame, hdf5::Error> {
+    let file = File::open(to_local_path(file))?;
+    let group = file.group(group)?;
+    if group.attr_names()?.iter().any(|f| f == "columns") {
+        return read_frame(&group);
+    }
+    let mut df = DataFrame::new();
+    for name in group.member_names()? {
+        if name == INDEX_DATASET {
+            continue;
+        }
+        let dataset = match group.dataset(&name) {
+            Ok(dataset) => dataset,
+            Err(_) => continue,
+        };
+        let descriptor = dataset.dtype()?.to_descriptor()?;
+        if dataset.ndim() != 1 || !is_supported(&descriptor) {
+            continue;
+        }
+        let fields = vec![(name, descriptor)];
+        for (name, column) in read_fields(&dataset, fields)? {
+            column.add_to(&mut df, &name)?;
+        }
+    }
+    Ok(df)
+}
+
+/// Read a one dimensional dataset with a compound datatype (a table) into a DataFrame
+///
+/// Each field of the compound type becomes a column, with dtypes chosen as in
+/// [`read_hdf5_group`](fn.read_hdf5_group.html). Fields of unsupported types are skipped.
+///
+/// This reads tables written by `h5py` from numpy structured arrays and by `pandas`/`PyTables`
+/// in `table` format
+/// # Errors
+/// * If the file or dataset cannot be opened
+/// * If the dataset is not a one dimensional dataset of a compound type
+pub fn read_hdf5_table(file: &str, dataset: &str) -> Result<DataFrame, hdf5::Error> {
+    let file = File::open(to_local_path(file))?;
+    let dataset = file.dataset(dataset)?;
+    read_table(&dataset)
+}
+fn read_table(dataset: &Dataset) -> Result<DataFrame, hdf5::Error> {
+    let compound = match dataset.dtype()?.to_descriptor()? {
+        TypeDescriptor::Compound(compound) if dataset.ndim() == 1 => compound,
+        _ => {
+            return Err(hdf5::Error::from(
+                "Expected a one dimensional dataset with a compound datatype",
+            ))
+        }
+    };
+    let fields = compound
+        .fields
+        .into_iter()
+        .filter(|f| is_supported(&f.ty))
+        .map(|f| (f.name, f.ty))
+        .collect();
+    let mut df = DataFrame::new();
+    for (name, column) in read_fields(dataset, fields)? {
+        column.add_to(&mut df, &name)?;
+    }
+    Ok(df)
+}
+
+/// Values of a dataset or of a field of a compound dataset
+enum Column {
+    Bool(Vec<bool>),
+    I32(Vec<i32>),
+    I64(Vec<i64>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    String(Vec<String>),
+}
+impl Column {
+    fn add_to(self, df: &mut DataFrame, name: &str) -> Result<(), hdf5::Error> {
+        match self {
+            Column::Bool(values) => add_series(df, Series::from(values), name, &[]),
+            Column::I32(values) => add_series(df, Series::from(values), name, &[]),
+            Column::I64(values) => add_series(df, Series::from(values), name, &[]),
+            Column::F32(values) => add_series(df, Series::from(values), name, &[]),
+            Column::F64(values) => add_series(df, Series::from(values), name, &[]),
+            Column::String(values) => add_series(df, Series::from(values), name, &[]),
+        }
+    }
+}
+fn is_supported(descriptor: &TypeDescriptor) -> bool {
+    matches!(
+        descriptor,
+        TypeDescriptor::Integer(_)
+            | TypeDescriptor::Unsigned(_)
+            | TypeDescriptor::Float(_)
+            | TypeDescriptor::Boolean
+            | TypeDescriptor::Enum(_)
+            | TypeDescriptor::FixedAscii(_)
+            | TypeDescriptor::FixedUnicode(_)
+            | TypeDescriptor::VarLenAscii
+            | TypeDescriptor::VarLenUnicode
+    )
+}
+/// Read `fields` of a dataset into columns
+///
+/// The dataset is read in one call to `H5Dread` into a packed buffer whose memory type is a
+/// compound of the requested fields (or the single field type for non compound datasets),
+/// letting HDF5 convert byte order and select fields by name, the buffer is then split into columns.
+fn read_fields(
+    dataset: &Dataset,
+    fields: Vec<(String, TypeDescriptor)>,
+) -> Result<Vec<(String, Column)>, hdf5::Error> {
+    let is_compound = matches!(
+        dataset.dtype()?.to_descriptor()?,
+        TypeDescriptor::Compound(_)
+    );
+    let mut offsets = Vec::with_capacity(fields.len());
+    let mut stride = 0;
+    for (_, ty) in &fields {
+        offsets.push(stride);
+        stride += ty.size();
+    }
+    let mem_descriptor = if is_compound {
+        TypeDescriptor::Compound(CompoundType {
+            fields: fields
+                .iter()
+                .zip(offsets.iter())
+                .enumerate()
+                .map(|(index, ((name, ty), offset))| CompoundField {
+                    name: name.clone(),
+                    ty: ty.clone(),
+                    offset: *offset,
+                    index,
+                })
+                .collect(),
+            size: stride,
+        })
+    } else {
+        fields[0].1.clone()
+    };
+    let mem_type = Datatype::from_descriptor(&mem_descriptor)?;
+    let len = dataset.size();
+    let mut buf = vec![0_u8; len * stride];
+    if len > 0 {
+        // Safety: buf holds `len` elements of `mem_type`, calls into the library are serialized
+        // through the same lock the hdf5 crate uses
+        let status = hdf5::sync::sync(|| unsafe {
+            H5Dread(
+                dataset.id(),
+                mem_type.id(),
+                H5S_ALL,
+                H5S_ALL,
+                H5P_DEFAULT,
+                buf.as_mut_ptr().cast(),
+            )
+        });
+        if status < 0 {
+            return Err(hdf5::Error::from("Could not read dataset"));
+        }
+    }
+    Ok(fields
+        .into_iter()
+        .zip(offsets.into_iter())
+        .map(|((name, ty), offset)| {
+            let column = parse_column(&buf, stride, offset, len, &ty);
+            (name, column)
+        })
+        .collect())
+}
+/// Parse the field at `offset` of each of the `len` elements in `buf` into a column
+#[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+fn parse_column(
+    buf: &[u8],
+    stride: usize,
+    offset: usize,
+    len: usize,
+    ty: &TypeDescriptor,
+) -> Column {
+    let size = ty.size();
+    let fields = (0..len).map(|i| &buf[i * stride + offset..i * stride + offset + size]);
+    match ty {
+        TypeDescriptor::Integer(_) if size <= 4 => {
+            Column::I32(fields.map(|f| read_int(f, true) as i32).collect())
+        }
+        TypeDescriptor::Unsigned(_) if size <= 2 => {
+            Column::I32(fields.map(|f| read_int(f, false) as i32).collect())
+        }
+        TypeDescriptor::Integer(_) => Column::I64(fields.map(|f| read_int(f, true)).collect()),
+        TypeDescriptor::Unsigned(_) => Column::I64(fields.map(|f| read_int(f, false)).collect()),
+        TypeDescriptor::Float(_) if size == 4 => Column::F32(
+            fields
+                .map(|f| f32::from_ne_bytes(f.try_into().unwrap()))
+                .collect(),
+        ),
+        TypeDescriptor::Float(_) => Column::F64(
+            fields
+                .map(|f| f64::from_ne_bytes(f.try_into().unwrap()))
+                .collect(),
+        ),
+        TypeDescriptor::Boolean => Column::Bool(fields.map(|f| f[0] != 0).collect()),
+        TypeDescriptor::Enum(enum_type) => {
+            let is_bool = enum_type.members.len() == 2
+                && enum_type.members.iter().all(|f| {
+                    f.name.eq_ignore_ascii_case("true") || f.name.eq_ignore_ascii_case("false")
+                });
+            if is_bool {
+                let true_value = enum_type
+                    .members
+                    .iter()
+                    .find(|f| f.name.eq_ignore_ascii_case("true"))
+                    .map(|f| f.value as i64)
+                    .unwrap();
+                Column::Bool(
+                    fields
+                        .map(|f| read_int(f, enum_type.signed) == true_value)
+                        .collect(),
+                )
+            } else if size <= 4 {
+                Column::I32(
+                    fields
+                        .map(|f| read_int(f, enum_type.signed) as i32)
+                        .collect(),
+                )
+            } else {
+                Column::I64(fields.map(|f| read_int(f, enum_type.signed)).collect())
+            }
+        }
+        TypeDescriptor::VarLenAscii | TypeDescriptor::VarLenUnicode => Column::String(
+            fields
+                .map(|f| {
+                    let ptr = usize::from_ne_bytes(f.try_into().unwrap()) as *mut c_char;
+                    if ptr.is_null() {
+                        return String::new();
+                    }
+                    // Safety: HDF5 allocated a nul terminated string for each element, which we own
+                    let value = unsafe { CStr::from_ptr(ptr).to_string_lossy().into_owned() };
+                    hdf5::sync::sync(|| unsafe { H5free_memory(ptr.cast()) });
+                    value
+                })
+                .collect(),
+        ),
+        // Fixed length strings are nul padded
+        _ => Column::String(
+            fields
+                .map(|f| {
+                    let end = f.iter().position(|b| *b == 0).unwrap_or_else(|| f.len());
+                    String::from_utf8_lossy(&f[..end]).into_owned()
+                })
+                .collect(),
+        ),
+    }
+}
+/// Read a native endian integer of 1, 2, 4 or 8 bytes
+#[allow(clippy::cast_possible_wrap)]
+fn read_int(bytes: &[u8], signed: bool) -> i64 {
+    match (bytes.len(), signed) {
+        (1, true) => i64::from(i8::from_ne_bytes([bytes[0]])),
+        (1, false) => i64::from(bytes[0]),
+        (2, true) => i64::from(i16::from_ne_bytes(bytes.try_into().unwrap())),
+        (2, false) => i64::from(u16::from_ne_bytes(bytes.try_into().unwrap())),
+        (4, true) => i64::from(i32::from_ne_bytes(bytes.try_into().unwrap())),
+        (4, false) => i64::from(u32::from_ne_bytes(bytes.try_into().unwrap())),
+        (_, true) => i64::from_ne_bytes(bytes.try_into().unwrap()),
+        // Values above i64::MAX wrap around
+        (_, false) => u64::from_ne_bytes(bytes.try_into().unwrap()) as i64,
+    }
+}
@@ -0,0 +1,411 @@
+//! Read and write HTML tables
+//!
+//! [`DataFrame::to_html`] is always available, reading tables with [`read_html`] requires the
+//! `html` feature
+//!
+//! [`DataFrame::to_html`]: ../../core/dataframe/struct.DataFrame.html#method.to_html
+//! [`read_html`]: fn.read_html.html
+use crate::core::block_manager::CellFormat;
+use crate::core::dataframe::DataFrame;
+#[cfg(feature = "html")]
+use crate::enums::DataFrameErrors;
+#[cfg(feature = "html")]
+use crate::io::dtypes::add_inferred_column;
+#[cfg(feature = "html")]
+use crate::io::utils::try_read;
+#[cfg(feature = "html")]
+use scraper::{ElementRef, Html, Selector};
+#[cfg(feature = "html")]
+use std::collections::HashSet;
+#[cfg(feature = "html")]
+use std::fmt;
+use std::fmt::Write;
+
+/// Options used by [`DataFrame::to_html`](../../core/dataframe/struct.DataFrame.html#method.to_html)
+///
+/// # Example
+/// ```
+/// use dami::io::html::HtmlOptions;
+/// let mut options = HtmlOptions::new();
+/// options
+///     .set_index(false)
+///     .set_float_precision(2)
+///     .set_classes(&["table", "table-striped"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HtmlOptions {
+    index: bool,
+    precision: usize,
+    classes: Vec<String>,
+    table_id: Option<String>,
+    border: usize,
+    escape: bool,
+}
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        HtmlOptions {
+            index: true,
+            precision: CellFormat::default().precision,
+            classes: vec![],
+            table_id: None,
+            border: 1,
+            escape: true,
+        }
+    }
+}
+impl HtmlOptions {
+    /// Create new options
+    ///
+    /// By default the index is written as the first column, floats are written with 3 decimal
+    /// places, the table has the class `dataframe`, a border of `1` and values are escaped
+    pub fn new() -> HtmlOptions {
+        HtmlOptions::default()
+    }
+    /// Write the index as the first column of the table
+    pub fn set_index(&mut self, index: bool) -> &mut Self {
+        self.index = index;
+        self
+    }
+    /// Set the number of digits written after the decimal point of floats
+    pub fn set_float_precision(&mut self, precision: usize) -> &mut Self {
+        self.precision = precision;
+        self
+    }
+    /// Set CSS classes added to the `<table>` tag after the `dataframe` class
+    pub fn set_classes(&mut self, classes: &[&str]) -> &mut Self {
+        self.classes = classes.iter().map(|f| (*f).to_string()).collect();
+        self
+    }
+    /// Set the `id` attribute of the `<table>` tag
+    pub fn set_table_id(&mut self, id: &str) -> &mut Self {
+        self.table_id = Some(id.to_string());
+        self
+    }
+    /// Set the `border` attribute of the `<table>` tag, `0` omits the attribute
+    pub fn set_border(&mut self, border: usize) -> &mut Self {
+        self.border = border;
+        self
+    }
+    /// Escape `<`, `>`, `&` and quotes in column names, index labels and values
+    ///
+    /// Turning this off allows values to contain HTML markup eg links
+    pub fn set_escape(&mut self, escape: bool) -> &mut Self {
+        self.escape = escape;
+        self
+    }
+}
+/// Escape text for use in HTML element content and attribute values
+pub(crate) fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl DataFrame {
+    /// Render the DataFrame as an HTML `<table>`
+    ///
+    /// Column names are written in `<th>` cells of the `<thead>` and index labels in `<th>`
+    /// cells at the start of each row of the `<tbody>`. Every row is written
+    ///
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use dami::io::html::HtmlOptions;
+    /// use ndarray::arr2;
+    /// let df = DataFrame::from(arr2(&[[1.5, 2.0]]));
+    /// let html = df.to_html(HtmlOptions::new().set_float_precision(1).set_classes(&["report"]));
+    /// assert!(html.starts_with("<table border=\"1\" class=\"dataframe report\">"));
+    /// assert!(html.contains("<td>1.5</td>"));
+    /// ```
+    pub fn to_html(&self, options: &HtmlOptions) -> String {
+        let escape = |text: &str| {
+            if options.escape {
+                escape_html(text)
+            } else {
+                text.to_string()
+            }
+        };
+        let format = CellFormat {
+            precision: options.precision,
+            max_width: None,
+        };
+        let mut html = String::from("<table");
+        if options.border > 0 {
+            write!(html, " border=\"{}\"", options.border).unwrap();
+        }
+        let mut classes = vec!["dataframe".to_string()];
+        classes.extend(options.classes.iter().map(|f| escape_html(f)));
+        write!(html, " class=\"{}\"", classes.join(" ")).unwrap();
+        if let Some(ref id) = options.table_id {
+            write!(html, " id=\"{}\"", escape_html(id)).unwrap();
+        }
+        html.push_str(">\n  <thead>\n    <tr style=\"text-align: right;\">\n");
+        if options.index {
+            html.push_str("      <th></th>\n");
+        }
+        for name in self.columns() {
+            writeln!(html, "      <th>{}</th>", escape(&name)).unwrap();
+        }
+        html.push_str("    </tr>\n  </thead>\n  <tbody>\n");
+        for i in 0..self.len() {
+            let row = self.format_row(i, &format);
+            html.push_str("    <tr>\n");
+            if options.index {
+                writeln!(html, "      <th>{}</th>", escape(&row[0])).unwrap();
+            }
+            for value in &row[1..] {
+                writeln!(html, "      <td>{}</td>", escape(value)).unwrap();
+            }
+            html.push_str("    </tr>\n");
+        }
+        html.push_str("  </tbody>\n</table>");
+        html
+    }
+}
+
+/// Errors that may occur when reading HTML tables
+#[cfg(feature = "html")]
+pub enum HtmlErrors {
+    /// The document contains no `<table>` elements
+    NoTables,
+    /// A table could not be converted to a DataFrame
+    DataFrame(DataFrameErrors),
+    /// The document could not be read from the file or url
+    IOError(std::io::Error),
+}
+#[cfg(feature = "html")]
+impl fmt::Debug for HtmlErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoTables => write!(f, "No tables found in the document"),
+            Self::DataFrame(err) => write!(f, "{:?}", err),
+            Self::IOError(err) => write!(f, "{}", err),
+        }
+    }
+}
+#[cfg(feature = "html")]
+impl From<DataFrameErrors> for HtmlErrors {
+    fn from(err: DataFrameErrors) -> Self {
+        Self::DataFrame(err)
+    }
+}
+#[cfg(feature = "html")]
+impl From<std::io::Error> for HtmlErrors {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(err)
+    }
+}
+
+/// # Requires Feature
+///  > * `html`
+///
+/// Read every `<table>` in an HTML document into a DataFrame
+///
+/// # Arguments
+/// * `source`: Either the HTML text itself (anything starting with `<`) or a path or url of a document
+///
+/// Header rows are the rows of the `<thead>` or, for tables without a `<thead>`, the leading rows
+/// made of `<th>` cells only. Names of columns spanning several header rows are joined with a space
+/// and duplicate names get a `.1`, `.2`.. suffix, tables without headers get columns named `0..n`.
+///
+/// Cells spanning several rows or columns (`rowspan`/`colspan`) are repeated in every row and column
+/// they cover, text is taken from the cell with whitespace collapsed. Missing cells are missing values.
+/// Column dtypes are inferred from the values.
+///
+/// Tables are returned in document order, nested tables are returned after the table containing them
+/// # Errors
+/// * `NoTables`: If the document contains no tables
+/// * `IOError`: If `source` is a path or url that cannot be read
+/// # Example
+/// ```
+/// use dami::io::html::read_html;
+/// let tables = read_html(
+///     "<table>
+///        <tr><th>city</th><th>year</th><th>visitors</th></tr>
+///        <tr><td rowspan=\"2\">Nairobi</td><td>2019</td><td>20</td></tr>
+///        <tr><td>2020</td><td>12</td></tr>
+///      </table>",
+/// )
+/// .unwrap();
+/// assert_eq!(tables[0].columns(), vec!["city", "year", "visitors"]);
+/// assert_eq!(tables[0].get::<String>("city").unwrap().to_vec(), vec!["Nairobi", "Nairobi"]);
+/// assert!(read_html("missing.html").is_err());
+/// ```
+#[cfg(feature = "html")]
+pub fn read_html(source: &str) -> Result<Vec<DataFrame>, HtmlErrors> {
+    let text = if source.trim_start().starts_with('<') {
+        source.to_string()
+    } else {
+        try_read(source)?
+    };
+    let document = Html::parse_document(&text);
+    let selector = Selector::parse("table").unwrap();
+    let tables = document
+        .select(&selector)
+        .map(parse_table)
+        .collect::<Result<Vec<DataFrame>, HtmlErrors>>()?;
+    if tables.is_empty() {
+        return Err(HtmlErrors::NoTables);
+    }
+    Ok(tables)
+}
+/// Rows of a table and whether each row is a header row
+#[cfg(feature = "html")]
+fn table_rows(table: ElementRef) -> Vec<(ElementRef, bool)> {
+    let mut rows = vec![];
+    for child in table.children().filter_map(ElementRef::wrap) {
+        match child.value().name() {
+            "tr" => rows.push((child, false)),
+            section @ "thead" | section @ "tbody" | section @ "tfoot" => {
+                for row in child.children().filter_map(ElementRef::wrap) {
+                    if row.value().name() == "tr" {
+                        rows.push((row, section == "thead"));
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    if !rows.iter().any(|f| f.1) {
+        // Without a <thead> leading rows of <th> cells are headers
+        for row in &mut rows {
+            let mut cells = row.0.children().filter_map(ElementRef::wrap).peekable();
+            if cells.peek().is_none() || !cells.all(|f| f.value().name() == "th") {
+                break;
+            }
+            row.1 = true;
+        }
+    }
+    rows
+}
+/// Expand the cells of `rows` into a grid, repeating cells spanning several rows or columns
+#[cfg(feature = "html")]
+fn expand_spans(rows: &[(ElementRef, bool)]) -> Vec<Vec<String>> {
+    // Text and number of rows left for cells spanning into the following rows
+    let mut pending: Vec<Option<(String, usize)>> = vec![];
+    let mut grid = Vec::with_capacity(rows.len());
+    for (row, _) in rows {
+        let mut values = vec![];
+        let mut cells = row
+            .children()
+            .filter_map(ElementRef::wrap)
+            .filter(|f| matches!(f.value().name(), "td" | "th"));
+        loop {
+            let col = values.len();
+            if let Some(Some((text, left))) = pending.get_mut(col) {
+                values.push(text.clone());
+                *left -= 1;
+                if *left == 0 {
+                    pending[col] = None;
+                }
+                continue;
+            }
+            let cell = match cells.next() {
+                Some(cell) => cell,
+                None if pending.iter().skip(col).any(Option::is_some) => {
+                    // Keep the columns of cells spanning from above aligned
+                    values.push(String::new());
+                    continue;
+                }
+                None => break,
+            };
+            let text = cell.text().collect::<Vec<&str>>().join(" ");
+            let text = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+            let colspan = span(cell, "colspan");
+            let rowspan = span(cell, "rowspan");
+            for _ in 0..colspan {
+                let col = values.len();
+                if rowspan > 1 {
+                    if pending.len() <= col {
+                        pending.resize(col + 1, None);
+                    }
+                    pending[col] = Some((text.clone(), rowspan - 1));
+                }
+                values.push(text.clone());
+            }
+        }
+        grid.push(values);
+    }
+    grid
+}
+/// Largest `colspan` honoured, browsers clamp to the same value
+#[cfg(feature = "html")]
+const MAX_COLSPAN: usize = 1000;
+/// Largest `rowspan` honoured, browsers clamp to the same value
+#[cfg(feature = "html")]
+const MAX_ROWSPAN: usize = 65534;
+#[cfg(feature = "html")]
+fn span(cell: ElementRef, attr: &str) -> usize {
+    let max = if attr == "colspan" {
+        MAX_COLSPAN
+    } else {
+        MAX_ROWSPAN
+    };
+    cell.value()
+        .attr(attr)
+        .and_then(|f| f.trim().parse::<usize>().ok())
+        .unwrap_or(1)
+        .clamp(1, max)
+}
+#[cfg(feature = "html")]
+fn parse_table(table: ElementRef) -> Result<DataFrame, HtmlErrors> {
+    let rows = table_rows(table);
+    let grid = expand_spans(&rows);
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    let headers = rows
+        .iter()
+        .zip(grid.iter())
+        .filter(|(row, _)| row.1)
+        .map(|(_, values)| values)
+        .collect::<Vec<&Vec<String>>>();
+    let mut seen = HashSet::new();
+    let names = (0..width)
+        .map(|col| {
+            let mut parts: Vec<&str> = vec![];
+            for row in &headers {
+                match row.get(col) {
+                    Some(part) if !part.is_empty() && parts.last() != Some(&part.as_str()) => {
+                        parts.push(part)
+                    }
+                    _ => (),
+                }
+            }
+            let name = if parts.is_empty() {
+                col.to_string()
+            } else {
+                parts.join(" ")
+            };
+            let mut unique = name.clone();
+            let mut n = 0;
+            while !seen.insert(unique.clone()) {
+                n += 1;
+                unique = format!("{}.{}", name, n);
+            }
+            unique
+        })
+        .collect::<Vec<String>>();
+    let body = rows
+        .iter()
+        .zip(grid.iter())
+        .filter(|(row, _)| !row.1)
+        .map(|(_, values)| values)
+        .collect::<Vec<&Vec<String>>>();
+    let mut df = DataFrame::new();
+    for (col, name) in names.iter().enumerate() {
+        let values = body
+            .iter()
+            .map(|row| row.get(col).cloned().unwrap_or_default())
+            .collect::<Vec<String>>();
+        add_inferred_column(&mut df, name, &values)?;
+    }
+    Ok(df)
+}
@@ -5,13 +5,12 @@ use crate::io::dtypes::{
     json_is_bool, json_is_float, json_is_int, json_value_to_bool, json_value_to_float,
     json_value_to_int, json_value_to_string,
 };
-use crate::io::utils::{is_compressed, is_url, read};
+use crate::io::utils::open_reader;
 use serde_json::Value;
 use std::cmp::min;
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::fs::{read_to_string, File};
-use std::io::{BufRead, BufReader};
+use std::io::BufRead;
 use std::path::Path;
 
 /// The JSON reader
@@ -37,21 +36,14 @@ impl<'a> JsonReader<'a> {
     }
     /// Read a JSON file
     ///
-    /// The path is a string pointing to a directory
+    /// The path is a string pointing to a local file or a url, remote files are parsed as they are
+    /// downloaded
     pub fn read<P: AsRef<Path> + Debug + Clone>(&mut self, path: P, lines: bool) {
-        if is_url(path.as_ref().to_str().unwrap()) && is_compressed(path.as_ref().to_str().unwrap())
-        {
-            self.parse_string_json(&read(path), lines);
-        } else {
-            // Local non-zipped file
-            self.parse_local_file(path.as_ref().to_str().unwrap(), lines);
-        }
+        self.parse_reader(open_reader(path), lines);
     }
-    fn parse_local_file(&mut self, path: &str, lines: bool) {
+    fn parse_reader(&mut self, reader: Box<dyn BufRead>, lines: bool) {
         if lines {
-            let fd = File::open(path).unwrap();
-            let buf = BufReader::new(fd);
-            for line in buf.lines() {
+            for line in reader.lines() {
                 let mut i_guess: Vec<Value> = Vec::new();
                 let val: Result<Value, _> = serde_json::from_str(&line.unwrap());
                 match val {
@@ -73,37 +65,7 @@ impl<'a> JsonReader<'a> {
                 };
             }
         } else {
-            let dt = read_to_string(path).unwrap();
-            self.parse_string_json(&dt, false)
-        }
-    }
-    fn parse_string_json(&mut self, data: &str, lines: bool) {
-        if lines {
-            let array = data.to_string();
-
-            for line in array.lines() {
-                let mut i_guess: Vec<Value> = Vec::new();
-                let val: Result<Value, _> = serde_json::from_str(line);
-                match val {
-                    Ok(value) => {
-                        let object = value.as_object().unwrap().to_owned();
-                        if self.headers.is_empty() {
-                            let headers = object.keys();
-                            for i in headers {
-                                self.headers.push(i.to_owned());
-                            }
-                        }
-                        let vals = object.values();
-                        for each in vals {
-                            i_guess.push(each.to_owned());
-                        }
-                        self.smart_push(i_guess);
-                    }
-                    Err(_) => continue,
-                };
-            }
-        } else {
-            let val: Result<Value, _> = serde_json::from_str(data);
+            let val: Result<Value, _> = serde_json::from_reader(reader);
             if let Ok(value) = val {
                 let object = value.as_object().unwrap().to_owned();
                 if self.data.is_empty() {
@@ -115,8 +77,8 @@ impl<'a> JsonReader<'a> {
                 for i in object {
                     self.smart_push(vec![i.1]);
                 }
-            };
-        };
+            }
+        }
     }
     fn smart_push(&mut self, data: Vec<Value>) {
         for (pos, record) in data.into_iter().enumerate() {
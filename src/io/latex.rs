@@ -0,0 +1,138 @@
+//! Write DataFrames as LaTeX tables
+use crate::core::block_manager::CellFormat;
+use crate::core::dataframe::DataFrame;
+use crate::enums::DataTypes;
+
+/// Options used by [`DataFrame::to_latex`](../../core/dataframe/struct.DataFrame.html#method.to_latex)
+#[derive(Clone, Debug)]
+pub struct LatexOptions {
+    index: bool,
+    booktabs: bool,
+    column_format: Option<String>,
+}
+impl Default for LatexOptions {
+    fn default() -> Self {
+        LatexOptions {
+            index: true,
+            booktabs: true,
+            column_format: None,
+        }
+    }
+}
+impl LatexOptions {
+    /// Create new options
+    ///
+    /// By default the index is written as the first column, rules use the `booktabs` package and
+    /// the column format is chosen from the dtypes of the columns
+    pub fn new() -> LatexOptions {
+        LatexOptions::default()
+    }
+    /// Write the index as the first column of the table
+    pub fn set_index(&mut self, index: bool) -> &mut Self {
+        self.index = index;
+        self
+    }
+    /// Use `\toprule`, `\midrule` and `\bottomrule` from the `booktabs` package,
+    /// if `false` `\hline` is used
+    pub fn set_booktabs(&mut self, booktabs: bool) -> &mut Self {
+        self.booktabs = booktabs;
+        self
+    }
+    /// Set the column format of the `tabular` environment eg `"lrr"` or `"l|p{3cm}|r"`
+    ///
+    /// By default the index and text columns are left aligned (`l`) and numeric and boolean
+    /// columns right aligned (`r`)
+    pub fn set_column_format(&mut self, column_format: &str) -> &mut Self {
+        self.column_format = Some(column_format.to_string());
+        self
+    }
+}
+
+impl DataFrame {
+    /// Render the DataFrame as a LaTeX `tabular` environment
+    ///
+    /// Values are formatted and long frames truncated the same way as when the DataFrame is
+    /// printed. Special characters in names and values are escaped
+    ///
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use dami::io::latex::LatexOptions;
+    /// use ndarray::arr2;
+    /// let df = DataFrame::from(arr2(&[[1.5, 2.0]]));
+    /// assert_eq!(
+    ///     df.to_latex(&LatexOptions::new()),
+    ///     "\\begin{tabular}{lrr}\n\\toprule\n{} & 0 & 1 \\\\\n\\midrule\n0 & 1.500 & 2.000 \\\\\n\\bottomrule\n\\end{tabular}\n"
+    /// );
+    /// ```
+    pub fn to_latex(&self, options: &LatexOptions) -> String {
+        let dtypes = self.dtypes();
+        let names = self.columns();
+        let column_format = options.column_format.clone().unwrap_or_else(|| {
+            let mut format = if options.index {
+                String::from("l")
+            } else {
+                String::new()
+            };
+            for name in &names {
+                format.push(match dtypes[name] {
+                    DataTypes::STRING
+                    | DataTypes::STR
+                    | DataTypes::DATETIME
+                    | DataTypes::OBJECT => 'l',
+                    _ => 'r',
+                })
+            }
+            format
+        });
+        let (top, mid, bottom) = if options.booktabs {
+            ("\\toprule", "\\midrule", "\\bottomrule")
+        } else {
+            ("\\hline", "\\hline", "\\hline")
+        };
+        let mut latex = format!("\\begin{{tabular}}{{{}}}\n{}\n", column_format, top);
+        let mut header = vec![];
+        if options.index {
+            header.push("{}".to_string());
+        }
+        header.extend(names.iter().map(|f| escape_latex(f)));
+        latex.push_str(&header.join(" & "));
+        latex.push_str(" \\\\\n");
+        latex.push_str(mid);
+        latex.push('\n');
+        let format = CellFormat::default();
+        let skip = if options.index { 0 } else { 1 };
+        for row in self.display_rows() {
+            let cells = match row {
+                Some(i) => self
+                    .format_row(i, &format)
+                    .iter()
+                    .skip(skip)
+                    .map(|f| escape_latex(f))
+                    .collect::<Vec<String>>(),
+                None => vec!["\\dots".to_string(); names.len() + 1 - skip],
+            };
+            latex.push_str(&cells.join(" & "));
+            latex.push_str(" \\\\\n");
+        }
+        latex.push_str(bottom);
+        latex.push_str("\n\\end{tabular}\n");
+        latex
+    }
+}
+fn escape_latex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\textbackslash{}"),
+            '~' => escaped.push_str("\\textasciitilde{}"),
+            '^' => escaped.push_str("\\textasciicircum{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -0,0 +1,97 @@
+//! Write DataFrames as Markdown tables
+use crate::core::block_manager::CellFormat;
+use crate::core::dataframe::DataFrame;
+use crate::enums::DataTypes;
+
+impl DataFrame {
+    /// Render the DataFrame as a GitHub flavoured Markdown pipe table
+    ///
+    /// The index is written as the first column, numeric and boolean columns are right aligned and
+    /// other columns left aligned. Values are formatted and long frames truncated the same way as when
+    /// the DataFrame is printed, pipes in values are escaped
+    ///
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use ndarray::arr2;
+    /// let df = DataFrame::from(arr2(&[[1.5, 2.0]]));
+    /// assert_eq!(
+    ///     df.to_markdown(),
+    ///     "|     |     0 |     1 |\n| :-- | ----: | ----: |\n| 0   | 1.500 | 2.000 |\n"
+    /// );
+    /// ```
+    pub fn to_markdown(&self) -> String {
+        let dtypes = self.dtypes();
+        let mut header = vec![String::new()];
+        header.extend(self.columns().iter().map(|f| escape_markdown(f)));
+        let mut right = vec![false];
+        right.extend(self.columns().iter().map(|f| {
+            !matches!(
+                dtypes[f],
+                DataTypes::STRING | DataTypes::STR | DataTypes::DATETIME | DataTypes::OBJECT
+            )
+        }));
+        let format = CellFormat::default();
+        let rows = self
+            .display_rows()
+            .into_iter()
+            .map(|row| match row {
+                Some(i) => self
+                    .format_row(i, &format)
+                    .iter()
+                    .map(|f| escape_markdown(f))
+                    .collect(),
+                None => vec!["...".to_string(); header.len()],
+            })
+            .collect::<Vec<Vec<String>>>();
+        let widths = (0..header.len())
+            .map(|col| {
+                rows.iter()
+                    .map(|row| row[col].chars().count())
+                    .chain(std::iter::once(header[col].chars().count()))
+                    .max()
+                    .unwrap_or(0)
+                    // The delimiter row needs at least three characters
+                    .max(3)
+            })
+            .collect::<Vec<usize>>();
+        let mut markdown = pipe_row(&header, &widths, &right);
+        let delimiters = widths
+            .iter()
+            .zip(&right)
+            .map(|(width, right)| {
+                if *right {
+                    "-".repeat(width - 1) + ":"
+                } else {
+                    ":".to_string() + &"-".repeat(width - 1)
+                }
+            })
+            .collect::<Vec<String>>();
+        markdown.push_str(&pipe_row(&delimiters, &widths, &right));
+        for row in &rows {
+            markdown.push_str(&pipe_row(row, &widths, &right));
+        }
+        markdown
+    }
+}
+/// Write one row of a pipe table padding cells to `widths`
+fn pipe_row(cells: &[String], widths: &[usize], right: &[bool]) -> String {
+    let mut row = String::from("|");
+    for ((cell, width), right) in cells.iter().zip(widths).zip(right) {
+        let padding = " ".repeat(width - cell.chars().count());
+        row.push(' ');
+        if *right {
+            row.push_str(&padding);
+            row.push_str(cell);
+        } else {
+            row.push_str(cell);
+            row.push_str(&padding);
+        }
+        row.push_str(" |");
+    }
+    row.push('\n');
+    row
+}
+fn escape_markdown(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
@@ -0,0 +1,610 @@
+//! Read and write [Apache Parquet](https://parquet.apache.org/) files
+//!
+//! Requires feature `parquet`
+//!
+//! Parquet types are mapped onto dami [`DataTypes`] as follows
+//!
+//! | Parquet type                                   | DataType   |
+//! |------------------------------------------------|------------|
+//! | `BOOLEAN`                                      | `BOOL`     |
+//! | `INT32` (signed, 8/16/32 bit)                  | `I32`      |
+//! | `INT64`, `INT32 (UINT_32)`, `INT64 (UINT_64)`  | `I64`      |
+//! | `FLOAT`                                        | `F32`      |
+//! | `DOUBLE`, `DECIMAL`                            | `F64`      |
+//! | `BYTE_ARRAY`, `FIXED_LEN_BYTE_ARRAY`           | `STRING`   |
+//! | `DATE`, `TIMESTAMP`, `INT96`                   | `DATETIME` |
+//!
+//! Nested columns (groups, lists and maps) are not supported and are skipped.
+//!
+//! # Nulls
+//! * Integer and boolean columns containing nulls are promoted to `f64` with nulls becoming `NaN`
+//! * Null floats become `NaN`
+//! * Null strings become empty strings
+//! * Null datetimes become [`NAT`]
+//!
+//! [`DataTypes`]: ../../enums/enum.DataTypes.html
+//! [`NAT`]: ../../core/index/date_time/constant.NAT.html
+#![cfg(feature = "parquet")]
+use crate::core::dataframe::DataFrame;
+use crate::core::index::date_time::NAT;
+use crate::core::series::Series;
+use crate::enums::{DataFrameErrors, DataTypes};
+use crate::io::dtypes::add_named_series;
+use crate::io::utils::to_local_path;
+
+use parquet::basic::{ConvertedType, LogicalType, Repetition, TimeUnit, Type as PhysicalType};
+use parquet::data_type::{
+    BoolType, ByteArray, ByteArrayType, Decimal, DoubleType, FloatType, Int32Type, Int64Type,
+};
+use parquet::errors::ParquetError;
+use parquet::file::properties::WriterProperties;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::file::writer::SerializedFileWriter;
+use parquet::format::MicroSeconds;
+use parquet::record::{Field, Row};
+use parquet::schema::types::{Type, TypePtr};
+
+use std::fmt;
+use std::fs::File;
+use std::path::Path;
+use std::sync::Arc;
+
+pub use parquet::basic::Compression;
+
+/// Errors that may occur when reading or writing parquet files
+pub enum ParquetErrors {
+    /// An error originating from the underlying parquet library
+    Parquet(ParquetError),
+    /// The file could not be opened or created
+    IOError(std::io::Error),
+    /// A column requested in the projection does not exist in the file
+    ColumnNotFound(String),
+    /// The columns read could not be put together in a DataFrame, eg two columns share a name
+    DataFrame(DataFrameErrors),
+}
+
+impl fmt::Debug for ParquetErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parquet(ref err) => write!(f, "{}", err),
+            Self::IOError(ref err) => write!(f, "{}", err),
+            Self::ColumnNotFound(ref column) => {
+                write!(f, "Column {} does not exist in the parquet file", column)
+            }
+            Self::DataFrame(ref err) => write!(f, "{:?}", err),
+        }
+    }
+}
+impl From<ParquetError> for ParquetErrors {
+    fn from(err: ParquetError) -> Self {
+        Self::Parquet(err)
+    }
+}
+impl From<std::io::Error> for ParquetErrors {
+    fn from(err: std::io::Error) -> Self {
+        Self::IOError(err)
+    }
+}
+impl From<DataFrameErrors> for ParquetErrors {
+    fn from(err: DataFrameErrors) -> Self {
+        Self::DataFrame(err)
+    }
+}
+
+/// The Parquet Reader
+///
+/// The reader only holds the file metadata in memory, data is decoded one row group at a time
+/// so large files can be processed using [`row_groups`](#method.row_groups) without materializing
+/// the whole file.
+pub struct ParquetReader {
+    reader: SerializedFileReader<File>,
+    // Projected schema passed to the row iterator
+    projection: Option<Type>,
+    // Column names and their dami dtypes in file order
+    names: Vec<String>,
+    dtypes: Vec<DataTypes>,
+}
+impl ParquetReader {
+    /// Open a parquet file
+    ///
+    /// # Errors
+    /// * `IOError`: If the file could not be opened
+    /// * `Parquet`: If the file metadata could not be decoded
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<ParquetReader, ParquetErrors> {
+        let reader = SerializedFileReader::new(File::open(to_local_path(path))?)?;
+        let root = reader
+            .metadata()
+            .file_metadata()
+            .schema_descr()
+            .root_schema();
+        let mut names = vec![];
+        let mut dtypes = vec![];
+        let mut fields: Vec<TypePtr> = vec![];
+        for field in root.get_fields() {
+            if let Some(dtype) = parquet_to_dtype(field) {
+                names.push(field.name().to_string());
+                dtypes.push(dtype);
+                fields.push(field.clone());
+            }
+        }
+        // Always project onto the supported columns so the row iterator never yields
+        // nested fields, builders are paired with row fields by position.
+        let projection = Type::group_type_builder(root.name())
+            .with_fields(fields)
+            .build()?;
+        Ok(ParquetReader {
+            reader,
+            projection: Some(projection),
+            names,
+            dtypes,
+        })
+    }
+    /// Only read `columns` from the file
+    ///
+    /// Columns are returned in the order they appear in the file
+    /// # Errors
+    /// * `ColumnNotFound`: If a column doesn't exist in the file or it is a nested column
+    pub fn set_columns(&mut self, columns: &[&str]) -> Result<&mut Self, ParquetErrors> {
+        for column in columns {
+            if !self.names.iter().any(|f| f == column) {
+                return Err(ParquetErrors::ColumnNotFound((*column).to_string()));
+            }
+        }
+        let root = self
+            .reader
+            .metadata()
+            .file_metadata()
+            .schema_descr()
+            .root_schema();
+        let fields: Vec<TypePtr> = root
+            .get_fields()
+            .iter()
+            .filter(|f| columns.contains(&f.name()))
+            .cloned()
+            .collect();
+        let (names, dtypes) = self
+            .names
+            .iter()
+            .cloned()
+            .zip(self.dtypes.iter().cloned())
+            .filter(|(name, _)| columns.contains(&name.as_str()))
+            .unzip();
+        self.names = names;
+        self.dtypes = dtypes;
+        self.projection = Some(
+            Type::group_type_builder(root.name())
+                .with_fields(fields)
+                .build()?,
+        );
+        Ok(self)
+    }
+    /// Get the names and dtypes of the columns that will be read
+    pub fn schema(&self) -> Vec<(String, DataTypes)> {
+        self.names
+            .iter()
+            .cloned()
+            .zip(self.dtypes.iter().cloned())
+            .collect()
+    }
+    /// Get the number of row groups in the file
+    pub fn num_row_groups(&self) -> usize {
+        self.reader.num_row_groups()
+    }
+    /// Read a single row group into a DataFrame
+    ///
+    /// # Errors
+    /// * `Parquet`: If the row group cannot be decoded
+    /// * `DataFrame`: If two columns have the same name
+    ///
+    /// # Panics
+    /// If `idx` is greater than the number of row groups
+    pub fn read_row_group(&self, idx: usize) -> Result<DataFrame, ParquetErrors> {
+        let row_group = self.reader.get_row_group(idx)?;
+        let mut columns = self.builders();
+        for row in row_group.get_row_iter(self.projection.clone())? {
+            push_row(&mut columns, &row?);
+        }
+        self.finish(columns)
+    }
+    /// Iterate over the row groups in the file, yielding a DataFrame per row group
+    pub fn row_groups(&self) -> RowGroups<'_> {
+        RowGroups {
+            reader: self,
+            position: 0,
+        }
+    }
+    /// Read all the row groups in the file into one DataFrame
+    ///
+    /// # Errors
+    /// * `Parquet`: If a row group cannot be decoded
+    /// * `DataFrame`: If two columns have the same name
+    pub fn read(&self) -> Result<DataFrame, ParquetErrors> {
+        let mut columns = self.builders();
+        for idx in 0..self.num_row_groups() {
+            let row_group = self.reader.get_row_group(idx)?;
+            for row in row_group.get_row_iter(self.projection.clone())? {
+                push_row(&mut columns, &row?);
+            }
+        }
+        self.finish(columns)
+    }
+    fn builders(&self) -> Vec<ColumnBuilder> {
+        self.dtypes.iter().map(ColumnBuilder::new).collect()
+    }
+    fn finish(&self, columns: Vec<ColumnBuilder>) -> Result<DataFrame, ParquetErrors> {
+        let mut df = DataFrame::new();
+        for (name, column) in self.names.iter().zip(columns) {
+            column.add_to(&mut df, name)?;
+        }
+        Ok(df)
+    }
+}
+/// An iterator over the row groups of a parquet file
+///
+/// Created by [`ParquetReader::row_groups`](struct.ParquetReader.html#method.row_groups)
+pub struct RowGroups<'a> {
+    reader: &'a ParquetReader,
+    position: usize,
+}
+impl Iterator for RowGroups<'_> {
+    type Item = Result<DataFrame, ParquetErrors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.position >= self.reader.num_row_groups() {
+            return None;
+        }
+        self.position += 1;
+        Some(self.reader.read_row_group(self.position - 1))
+    }
+}
+/// Holds values of a column while rows are being decoded
+enum ColumnBuilder {
+    Bool(Vec<Option<bool>>),
+    I32(Vec<Option<i32>>),
+    I64(Vec<Option<i64>>),
+    F32(Vec<f32>),
+    F64(Vec<f64>),
+    Str(Vec<String>),
+    DateTime(Vec<i64>),
+}
+impl ColumnBuilder {
+    fn new(dtype: &DataTypes) -> ColumnBuilder {
+        match dtype {
+            DataTypes::BOOL => Self::Bool(vec![]),
+            DataTypes::I32 => Self::I32(vec![]),
+            DataTypes::I64 => Self::I64(vec![]),
+            DataTypes::F32 => Self::F32(vec![]),
+            DataTypes::F64 => Self::F64(vec![]),
+            DataTypes::DATETIME => Self::DateTime(vec![]),
+            _ => Self::Str(vec![]),
+        }
+    }
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn push(&mut self, field: &Field) {
+        match self {
+            Self::Bool(values) => values.push(match field {
+                Field::Bool(value) => Some(*value),
+                _ => None,
+            }),
+            Self::I32(values) => values.push(match field {
+                Field::Byte(value) => Some(i32::from(*value)),
+                Field::Short(value) => Some(i32::from(*value)),
+                Field::Int(value) => Some(*value),
+                Field::UByte(value) => Some(i32::from(*value)),
+                Field::UShort(value) => Some(i32::from(*value)),
+                _ => None,
+            }),
+            Self::I64(values) => values.push(match field {
+                Field::Int(value) => Some(i64::from(*value)),
+                Field::Long(value) => Some(*value),
+                Field::UInt(value) => Some(i64::from(*value)),
+                Field::ULong(value) => Some(*value as i64),
+                _ => None,
+            }),
+            Self::F32(values) => values.push(match field {
+                Field::Float(value) => *value,
+                _ => f32::NAN,
+            }),
+            Self::F64(values) => values.push(match field {
+                Field::Double(value) => *value,
+                Field::Float(value) => f64::from(*value),
+                Field::Decimal(value) => decimal_to_f64(value),
+                _ => f64::NAN,
+            }),
+            Self::Str(values) => values.push(match field {
+                Field::Null => String::new(),
+                Field::Str(value) => value.clone(),
+                Field::Bytes(value) => String::from_utf8_lossy(value.data()).to_string(),
+                other => other.to_string(),
+            }),
+            Self::DateTime(values) => values.push(match field {
+                Field::Date(days) => i64::from(*days) * 86400,
+                Field::TimestampMillis(millis) => millis.div_euclid(1000),
+                Field::TimestampMicros(micros) => micros.div_euclid(1_000_000),
+                // The record API returns TIMESTAMP(NANOS) values as plain longs
+                Field::Long(nanos) => nanos.div_euclid(1_000_000_000),
+                _ => NAT,
+            }),
+        }
+    }
+    /// Convert the values into a Series and add them to the DataFrame
+    #[allow(clippy::cast_precision_loss)]
+    fn add_to(self, df: &mut DataFrame, name: &str) -> Result<(), DataFrameErrors> {
+        match self {
+            Self::Bool(values) => {
+                if values.iter().any(Option::is_none) {
+                    let values = values
+                        .iter()
+                        .map(|f| f.map_or(f64::NAN, |v| if v { 1.0 } else { 0.0 }))
+                        .collect::<Vec<f64>>();
+                    add_named_series(df, Series::from(values), name)
+                } else {
+                    let values = values.into_iter().flatten().collect::<Vec<bool>>();
+                    add_named_series(df, Series::from(values), name)
+                }
+            }
+            Self::I32(values) => {
+                if values.iter().any(Option::is_none) {
+                    let values = values
+                        .iter()
+                        .map(|f| f.map_or(f64::NAN, f64::from))
+                        .collect::<Vec<f64>>();
+                    add_named_series(df, Series::from(values), name)
+                } else {
+                    let values = values.into_iter().flatten().collect::<Vec<i32>>();
+                    add_named_series(df, Series::from(values), name)
+                }
+            }
+            Self::I64(values) => {
+                if values.iter().any(Option::is_none) {
+                    let values = values
+                        .iter()
+                        .map(|f| f.map_or(f64::NAN, |v| v as f64))
+                        .collect::<Vec<f64>>();
+                    add_named_series(df, Series::from(values), name)
+                } else {
+                    let values = values.into_iter().flatten().collect::<Vec<i64>>();
+                    add_named_series(df, Series::from(values), name)
+                }
+            }
+            Self::F32(values) => add_named_series(df, Series::from(values), name),
+            Self::F64(values) => add_named_series(df, Series::from(values), name),
+            Self::Str(values) => add_named_series(df, Series::from(values), name),
+            Self::DateTime(values) => {
+                let mut series = Series::from(values);
+                series.set_dtype(DataTypes::DATETIME);
+                add_named_series(df, series, name)
+            }
+        }
+    }
+}
+fn push_row(columns: &mut [ColumnBuilder], row: &Row) {
+    for (column, (_, field)) in columns.iter_mut().zip(row.get_column_iter()) {
+        column.push(field);
+    }
+}
+/// Convert the big endian unscaled value of a decimal into a float
+#[allow(clippy::cast_precision_loss)]
+fn decimal_to_f64(decimal: &Decimal) -> f64 {
+    let bytes = decimal.data();
+    if bytes.is_empty() || bytes.len() > 16 {
+        return f64::NAN;
+    }
+    // Sign extend to 16 bytes
+    let fill = if bytes[0] & 0x80 == 0 { 0 } else { 0xff };
+    let mut buf = [fill; 16];
+    buf[16 - bytes.len()..].copy_from_slice(bytes);
+    i128::from_be_bytes(buf) as f64 / 10_f64.powi(decimal.scale())
+}
+/// Map a top level parquet column onto a dami DataType
+///
+/// Returns `None` for nested columns
+fn parquet_to_dtype(field: &Type) -> Option<DataTypes> {
+    if !field.is_primitive() || field.get_basic_info().repetition() == Repetition::REPEATED {
+        return None;
+    }
+    let info = field.get_basic_info();
+    let logical = info.logical_type();
+    let converted = info.converted_type();
+    let dtype = match field.get_physical_type() {
+        PhysicalType::BOOLEAN => DataTypes::BOOL,
+        PhysicalType::INT32 => match (logical, converted) {
+            (Some(LogicalType::Date), _) | (_, ConvertedType::DATE) => DataTypes::DATETIME,
+            (Some(LogicalType::Decimal { .. }), _) | (_, ConvertedType::DECIMAL) => DataTypes::F64,
+            (
+                Some(LogicalType::Integer {
+                    is_signed: false,
+                    bit_width: 32,
+                }),
+                _,
+            )
+            | (_, ConvertedType::UINT_32) => DataTypes::I64,
+            _ => DataTypes::I32,
+        },
+        PhysicalType::INT64 => match (logical, converted) {
+            (Some(LogicalType::Timestamp { .. }), _)
+            | (_, ConvertedType::TIMESTAMP_MILLIS)
+            | (_, ConvertedType::TIMESTAMP_MICROS) => DataTypes::DATETIME,
+            (Some(LogicalType::Decimal { .. }), _) | (_, ConvertedType::DECIMAL) => DataTypes::F64,
+            _ => DataTypes::I64,
+        },
+        PhysicalType::INT96 => DataTypes::DATETIME,
+        PhysicalType::FLOAT => DataTypes::F32,
+        PhysicalType::DOUBLE => DataTypes::F64,
+        PhysicalType::BYTE_ARRAY | PhysicalType::FIXED_LEN_BYTE_ARRAY => {
+            match (logical, converted) {
+                (Some(LogicalType::Decimal { .. }), _) | (_, ConvertedType::DECIMAL) => {
+                    DataTypes::F64
+                }
+                _ => DataTypes::STRING,
+            }
+        }
+    };
+    Some(dtype)
+}
+
+impl DataFrame {
+    /// # Requires Feature
+    ///  > * `parquet`
+    ///
+    /// Write the DataFrame to a parquet file
+    ///
+    /// The whole DataFrame is written as a single row group.
+    ///
+    /// # Arguments
+    /// * `path`: The file to write to, it will be created if it doesn't exist and truncated if it does
+    /// * `compression`: The compression codec to use for the column chunks eg `Compression::SNAPPY`
+    ///
+    /// # Notes
+    /// * `datetime` columns are written as `TIMESTAMP(MICROS)` and [`NAT`] values are written as nulls
+    /// * `str` columns are written as `STRING` columns
+    ///
+    /// # Errors
+    /// * `IOError`: If the file cannot be created
+    /// * `Parquet`: If the underlying writer fails, a column has dtype `object` or a datetime
+    ///  is too far from the epoch to be stored in microseconds
+    ///
+    /// [`NAT`]: ../core/index/date_time/constant.NAT.html
+    pub fn to_parquet<P: AsRef<Path>>(
+        &self,
+        path: P,
+        compression: Compression,
+    ) -> Result<(), ParquetErrors> {
+        let names = self.columns();
+        let dtypes = self.dtypes();
+        let mut fields = vec![];
+        for name in &names {
+            fields.push(Arc::new(dtype_to_parquet(name, &dtypes[name])?));
+        }
+        let schema = Arc::new(
+            Type::group_type_builder("schema")
+                .with_fields(fields)
+                .build()?,
+        );
+        let properties = Arc::new(
+            WriterProperties::builder()
+                .set_compression(compression)
+                .build(),
+        );
+        let mut writer = SerializedFileWriter::new(File::create(path)?, schema, properties)?;
+        let mut row_group = writer.next_row_group()?;
+        for name in &names {
+            let mut column = row_group.next_column()?.unwrap();
+            match dtypes[name] {
+                DataTypes::BOOL => {
+                    let values = self.get::<bool>(name).unwrap().to_vec();
+                    column
+                        .typed::<BoolType>()
+                        .write_batch(&values, None, None)?;
+                }
+                DataTypes::I32 => {
+                    let values = self.get::<i32>(name).unwrap().to_vec();
+                    column
+                        .typed::<Int32Type>()
+                        .write_batch(&values, None, None)?;
+                }
+                DataTypes::I64 => {
+                    let values = self.get::<i64>(name).unwrap().to_vec();
+                    column
+                        .typed::<Int64Type>()
+                        .write_batch(&values, None, None)?;
+                }
+                DataTypes::F32 => {
+                    let values = self.get::<f32>(name).unwrap().to_vec();
+                    column
+                        .typed::<FloatType>()
+                        .write_batch(&values, None, None)?;
+                }
+                DataTypes::F64 => {
+                    let values = self.get::<f64>(name).unwrap().to_vec();
+                    column
+                        .typed::<DoubleType>()
+                        .write_batch(&values, None, None)?;
+                }
+                DataTypes::STRING => {
+                    let values = self
+                        .get::<String>(name)
+                        .unwrap()
+                        .to_vec()
+                        .into_iter()
+                        .map(|f| ByteArray::from(f.into_bytes()))
+                        .collect::<Vec<ByteArray>>();
+                    column
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, None, None)?;
+                }
+                DataTypes::STR => {
+                    let values = self
+                        .get::<&'static str>(name)
+                        .unwrap()
+                        .to_vec()
+                        .into_iter()
+                        .map(ByteArray::from)
+                        .collect::<Vec<ByteArray>>();
+                    column
+                        .typed::<ByteArrayType>()
+                        .write_batch(&values, None, None)?;
+                }
+                DataTypes::DATETIME => {
+                    let series = self.get::<i64>(name).unwrap().to_vec();
+                    // Definition level 0 marks a null
+                    let levels = series
+                        .iter()
+                        .map(|f| if *f == NAT { 0 } else { 1 })
+                        .collect::<Vec<i16>>();
+                    let values = series
+                        .into_iter()
+                        .filter(|f| *f != NAT)
+                        .map(|f| {
+                            f.checked_mul(1_000_000).ok_or_else(|| {
+                                ParquetError::General(format!(
+                                    "Timestamp {} in column {} is out of range for microseconds",
+                                    f, name
+                                ))
+                            })
+                        })
+                        .collect::<Result<Vec<i64>, ParquetError>>()?;
+                    column
+                        .typed::<Int64Type>()
+                        .write_batch(&values, Some(&levels), None)?;
+                }
+                DataTypes::OBJECT => return Err(unsupported_object(name).into()),
+            }
+            column.close()?;
+        }
+        row_group.close()?;
+        writer.close()?;
+        Ok(())
+    }
+}
+/// Create the parquet schema type for a column
+fn dtype_to_parquet(name: &str, dtype: &DataTypes) -> Result<Type, ParquetError> {
+    let builder = match dtype {
+        DataTypes::BOOL => Type::primitive_type_builder(name, PhysicalType::BOOLEAN),
+        DataTypes::I32 => Type::primitive_type_builder(name, PhysicalType::INT32),
+        DataTypes::I64 => Type::primitive_type_builder(name, PhysicalType::INT64),
+        DataTypes::F32 => Type::primitive_type_builder(name, PhysicalType::FLOAT),
+        DataTypes::F64 => Type::primitive_type_builder(name, PhysicalType::DOUBLE),
+        DataTypes::STRING | DataTypes::STR => {
+            Type::primitive_type_builder(name, PhysicalType::BYTE_ARRAY)
+                .with_logical_type(Some(LogicalType::String))
+        }
+        DataTypes::DATETIME => {
+            return Type::primitive_type_builder(name, PhysicalType::INT64)
+                .with_repetition(Repetition::OPTIONAL)
+                .with_logical_type(Some(LogicalType::Timestamp {
+                    is_adjusted_to_u_t_c: false,
+                    unit: TimeUnit::MICROS(MicroSeconds {}),
+                }))
+                .build()
+        }
+        DataTypes::OBJECT => return Err(unsupported_object(name)),
+    };
+    builder.with_repetition(Repetition::REQUIRED).build()
+}
+fn unsupported_object(name: &str) -> ParquetError {
+    ParquetError::General(format!(
+        "Column {} has dtype object which cannot be written to parquet",
+        name
+    ))
+}
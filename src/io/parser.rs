@@ -7,8 +7,9 @@
 //! as it performs error checking conventions the the underlying modules do not consider
 extern crate lazy_static;
 
+pub use crate::io::csv::CsvChunks;
 use crate::io::csv::Reader;
-use crate::io::fwf::FWFReader;
+use crate::io::fwf::{ColSpecs, FWFError, FWFReader};
 #[allow(unused_imports)]
 use crate::prelude::Series;
 
@@ -19,13 +20,21 @@ use std::iter::FromIterator;
 use std::path::Path;
 
 use crate::core::dataframe::DataFrame;
+#[cfg(feature = "arrow")]
+use crate::io::arrow::{read_ipc_file, ArrowErrors};
 #[cfg(feature = "clipboard")]
-use crate::io::clipboard::ClipReader;
+use crate::io::clipboard::{ClipReader, ClipboardErrors};
 #[cfg(feature = "hdf5")]
 use crate::io::hdf5::read_dataset_to_series;
 use crate::io::json::JsonReader;
+#[cfg(feature = "parquet")]
+use crate::io::parquet::{ParquetErrors, ParquetReader};
+#[cfg(feature = "sql")]
+use crate::io::sql::{read_sql_query, SqlErrors};
 #[cfg(feature = "hdf5")]
 use hdf5::H5Type;
+#[cfg(feature = "sql")]
+use rusqlite::Connection;
 
 lazy_static! {
     static ref CSV_PARSER_DEFAULTS: HashMap<&'static str, &'static str> = {
@@ -82,10 +91,11 @@ impl fmt::Debug for Errors {
 /// # Panics
 /// * If the names argument in the `options` settings contains duplicates
 /// # Notes
-/// For local non-zipped files this function is much faster as it uses
-/// buffered io to parse the files as it is being read.
+/// Local files are parsed as they are read and remote files as they are downloaded, `gzip` and `deflate`
+/// responses are decompressed on the fly. Compressed local files and `zip`, `xz` and `lzma` responses
+/// are read to memory before being parsed.
 ///
-/// For remote files/zipped files. The files are first read to memory before being parsed
+/// To read large files in pieces use [`read_csv_chunked`](fn.read_csv_chunked.html)
 pub fn read_csv<'a, P: AsRef<Path> + Debug + Clone>(
     path: P,
     options: Option<HashMap<&'a str, &'a str>>,
@@ -97,6 +107,35 @@ pub fn read_csv<'a, P: AsRef<Path> + Debug + Clone>(
     let mut new_reader = Reader::new();
     new_reader.parse_csv(path, settings)
 }
+/// Read a CSV file/url in chunks of at most `chunksize` rows
+///
+/// Returns an iterator yielding a DataFrame for each chunk, rows are only read (or downloaded for
+/// remote files) when the next chunk is requested so the first chunks are available before the whole
+/// file is read. Rows are labelled by their position in the file.
+///
+/// `options` are the same as for [`read_csv`](fn.read_csv.html)
+///
+/// # Panics
+/// * If `chunksize` is zero
+/// * If the names argument in the `options` settings contains duplicates
+///
+/// # Example
+/// ```no_run
+/// use dami::io::parser::read_csv_chunked;
+/// for chunk in read_csv_chunked("https://example.com/large.csv", 10_000, None) {
+///     println!("{}", chunk.len());
+/// }
+/// ```
+pub fn read_csv_chunked<'a, P: AsRef<Path> + Debug + Clone>(
+    path: P,
+    chunksize: usize,
+    options: Option<HashMap<&'a str, &'a str>>,
+) -> CsvChunks<'a> {
+    let options = options.unwrap_or_default();
+    let settings = update_kwargs(options);
+    validate_names(settings.get("names").unwrap()).unwrap();
+    Reader::new().parse_csv_chunked(path, settings, chunksize)
+}
 /// Read a JSON file to a DataFrame.
 ///
 /// # Arguments
@@ -127,30 +166,42 @@ pub fn read_json<P: AsRef<Path> + Debug + Clone>(path_or_buffer: P, lines: bool)
 ///  Where columns are separated by a fixed number od characters.
 /// # Arguments
 /// > * `path`: A string pointing to a fixed width file
-/// > * colspecs: A Vec containing tuples of usize that indicates the column widths
+/// > * colspecs: How to locate the fields of each line, either as `(from, to)` extents, as field widths,
+/// or inferred from the first lines of the file. See [`ColSpecs`]
 /// > * `options`: A HashMap containing options. Currently this does nothing but the behavior will change in the future.
 ///
 /// If the two latter functions are not needed for your case you can use:
 /// ```ignore
 /// read_fwf("a_well_parsed_file.csv",None,None);
 /// ```
+/// To infer the extents of the fields from the first [`INFER_NROWS`] lines use
+/// ```ignore
+/// use dami::io::fwf::{ColSpecs, INFER_NROWS};
+/// read_fwf("a_well_parsed_file.csv",Some(ColSpecs::Infer(INFER_NROWS)),None);
+/// ```
 ///
-/// By Default leading and trailing whitespace characters will be `trim()`ed
+/// By Default leading and trailing whitespace characters will be `trim()`ed, when reading with
+/// `colspecs` blank fields are read as missing values
+/// # Errors
+/// * `ShortLine`: If `colspecs` is given and a line ends before the start of one of the fields
+/// * `IOError`: If the file cannot be opened or read
 ///
+/// [`ColSpecs`]: ../fwf/enum.ColSpecs.html
+/// [`INFER_NROWS`]: ../fwf/constant.INFER_NROWS.html
 pub fn read_fwf<'a, P: AsRef<Path> + Debug + Clone>(
     path: P,
-    colspecs: Option<Vec<(usize, usize)>>,
+    colspecs: Option<ColSpecs>,
     options: Option<HashMap<&'a str, &'a str>>,
-) -> DataFrame {
+) -> Result<DataFrame, FWFError> {
     let options = options.unwrap_or_default();
     let settings = update_kwargs(options);
     if let Some(specs) = colspecs {
         let mut a = FWFReader::new();
 
-        a.read_with_colspecs(path, &specs, settings).to_dataframe()
+        Ok(a.read_with(path, &specs, settings)?.to_dataframe())
     } else {
         let mut a = FWFReader::new();
-        a.read(path, settings).to_dataframe()
+        Ok(a.read(path, settings).to_dataframe())
     }
 }
 #[cfg(feature = "clipboard")]
@@ -159,20 +210,32 @@ pub fn read_fwf<'a, P: AsRef<Path> + Debug + Clone>(
 ///
 /// Read text from the system clipboard and pass it to `CSVReader`
 /// # Arguments
-/// >    `sep`:`A string used to separate record
+/// >    `sep`: A string used to separate records, if `None` tabs, commas or whitespace aligned
+///      columns are detected from the data
 ///
 /// >    `options`: See [`update_kwargs_method`](/dami/io/csv/struct.Reader.html#method.update_kwargs)
 ///
+/// # Errors
+/// * `Clipboard`: If the clipboard cannot be read
+/// * `Empty`: If the clipboard contains no data
+///
+/// To read from somewhere other than the system clipboard see [`ClipReader::with_backend`]
+///
 /// # Notes
 ///  On linux platforms, run
 /// ```bash
 /// sudo apt install xorg-dev libxcb-shape0-dev libxcb-xfixes0-de
 /// ```
 /// To install needed dependencies
-pub fn read_clipboard<'a>(sep: &'a str, options: Option<HashMap<&'a str, &'a str>>) -> DataFrame {
+///
+/// [`ClipReader::with_backend`]: ../clipboard/struct.ClipReader.html#method.with_backend
+pub fn read_clipboard<'a>(
+    sep: Option<&'a str>,
+    options: Option<HashMap<&'a str, &'a str>>,
+) -> Result<DataFrame, ClipboardErrors> {
     let mut clip_reader = ClipReader::new();
     let options = update_kwargs(options.unwrap_or_default());
-    clip_reader.read(sep, Some(options)).to_dataframe()
+    clip_reader.read(sep, Some(options))
 }
 /// Read a HDF5 dataSet to a dami [`Series`]
 /// # Arguments
@@ -194,6 +257,71 @@ pub fn read_clipboard<'a>(sep: &'a str, options: Option<HashMap<&'a str, &'a str
 pub fn read_hdf5_to_series<T: Clone + H5Type + Default>(path: &str, dataset: &str) -> Series<T> {
     read_dataset_to_series(path, dataset)
 }
+/// Read a parquet file into a DataFrame
+///
+/// # Arguments
+/// * `path`: The path to the parquet file
+///
+/// * `columns`: If `Some` only the named columns are read from the file, otherwise all columns are read
+///
+/// # Errors
+/// * `IOError`: If the file cannot be opened
+///
+/// * `ColumnNotFound`: If a column in `columns` doesn't exist in the file
+///
+/// * `Parquet`: If the file is not a valid parquet file
+///
+/// For reading large files one row group at a time see [`ParquetReader`]
+///
+/// [`ParquetReader`]: ../parquet/struct.ParquetReader.html
+#[cfg(feature = "parquet")]
+pub fn read_parquet<P: AsRef<Path>>(
+    path: P,
+    columns: Option<&[&str]>,
+) -> Result<DataFrame, ParquetErrors> {
+    let mut reader = ParquetReader::new(path)?;
+    if let Some(columns) = columns {
+        reader.set_columns(columns)?;
+    }
+    reader.read()
+}
+/// Read an Arrow IPC file into a DataFrame
+///
+/// Feather V2 files are Arrow IPC files and can also be read using this function
+/// # Arguments
+/// * `path`: The path to the IPC file
+///
+/// # Errors
+/// * `IOError`: If the file cannot be opened
+///
+/// * `Arrow`: If the file is not a valid IPC file
+///
+/// * `UnsupportedType`: If a column has a type that cannot be converted to a Series
+///
+/// * `DataFrame`: If two columns have the same name
+#[cfg(feature = "arrow")]
+pub fn read_ipc<P: AsRef<Path>>(path: P) -> Result<DataFrame, ArrowErrors> {
+    read_ipc_file(path)
+}
+/// Read the result of a SQLite query into a DataFrame
+///
+/// # Arguments
+/// * `conn`: An open SQLite connection
+///
+/// * `query`: The query to run, it can be any statement returning rows
+///
+/// # Errors
+/// * `Sqlite`: If the query is invalid or fails to run
+///
+/// * `DataFrame`: If two columns have the same name
+///
+/// For reading large results in chunks see [`read_sql_query_chunked`]
+///
+/// [`read_sql_query_chunked`]: ../sql/fn.read_sql_query_chunked.html
+#[cfg(feature = "sql")]
+pub fn read_sql(conn: &Connection, query: &str) -> Result<DataFrame, SqlErrors> {
+    read_sql_query(conn, query)
+}
 /// Updates keyword arguments
 ///
 /// It takes a `HashMap` and iterates over its values and updates the default
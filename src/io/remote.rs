@@ -0,0 +1,756 @@
+//! Fetching remote files
+//!
+//! Every reader that accepts a url fetches it using the options set by [`set_remote_options`],
+//! [`RemoteOptions`] adds headers, authentication, timeouts and retries to requests and can keep the
+//! files it downloads in an on-disk cache so they are only downloaded again when they change on the
+//! server.
+//!
+//! Compressed bodies are detected from the `Content-Encoding` and `Content-Type` headers of the
+//! response, falling back to the extension of the url. `gzip`, `deflate`, `zip`, `xz` and `lzma`
+//! bodies are decompressed when read.
+//!
+//! [`set_remote_options`]: fn.set_remote_options.html
+//! [`RemoteOptions`]: struct.RemoteOptions.html
+use flate2::read::{MultiGzDecoder, ZlibDecoder};
+use lazy_static::lazy_static;
+use lzma_rs::{lzma_decompress, xz_decompress};
+use serde::{Deserialize, Serialize};
+use std::env::temp_dir;
+use std::fmt;
+use std::fs::{create_dir_all, File, OpenOptions};
+use std::io::{self, BufReader, Cursor, Read, Write};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+use std::thread::sleep;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use url::Url;
+use zip::ZipArchive;
+
+lazy_static! {
+    static ref OPTIONS: RwLock<RemoteOptions> = RwLock::new(RemoteOptions::default());
+}
+/// Number of temporary files created by this process, keeps their names unique
+static TEMP_FILES: AtomicUsize = AtomicUsize::new(0);
+
+/// Errors that may occur when fetching remote files
+pub enum RemoteErrors {
+    /// The url could not be parsed
+    InvalidUrl(url::ParseError),
+    /// The request failed before a response was received eg the connection was refused or timed out
+    Transport(ureq::Error),
+    /// The server responded with an error status, holds the status and the url
+    Status(u16, String),
+    /// An error occurred reading the response or reading and writing the cache
+    IOError(io::Error),
+    /// The body could not be decompressed
+    Decompress(String),
+}
+impl fmt::Debug for RemoteErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidUrl(err) => write!(f, "Invalid url: {}", err),
+            Self::Transport(err) => write!(f, "Request failed: {}", err),
+            Self::Status(status, url) => write!(f, "{} responded with status {}", url, status),
+            Self::IOError(err) => write!(f, "{}", err),
+            Self::Decompress(err) => write!(f, "Could not decompress the response: {}", err),
+        }
+    }
+}
+impl From<url::ParseError> for RemoteErrors {
+    fn from(err: url::ParseError) -> Self {
+        Self::InvalidUrl(err)
+    }
+}
+impl From<io::Error> for RemoteErrors {
+    fn from(err: io::Error) -> Self {
+        Self::IOError(err)
+    }
+}
+
+/// How requests are authenticated
+#[derive(Clone)]
+enum Auth {
+    Basic(String, String),
+    Bearer(String),
+}
+// Keep credentials out of logs
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Basic(user, _) => write!(f, "Basic({}, ***)", user),
+            Self::Bearer(_) => write!(f, "Bearer(***)"),
+        }
+    }
+}
+
+/// Compression of a response body
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Encoding {
+    Identity,
+    Gzip,
+    Deflate,
+    Zip,
+    Xz,
+    Lzma,
+}
+impl Encoding {
+    /// Detect the compression from the headers of a response, then from the extension of the url
+    fn detect(content_encoding: Option<&str>, content_type: Option<&str>, url: &Url) -> Encoding {
+        let content_encoding = content_encoding.unwrap_or("").trim().to_lowercase();
+        match content_encoding.as_str() {
+            "gzip" | "x-gzip" => return Encoding::Gzip,
+            "deflate" => return Encoding::Deflate,
+            _ => (),
+        }
+        let content_type = content_type
+            .unwrap_or("")
+            .split(';')
+            .next()
+            .unwrap_or("")
+            .trim()
+            .to_lowercase();
+        match content_type.as_str() {
+            "application/gzip" | "application/x-gzip" => return Encoding::Gzip,
+            "application/zip" | "application/x-zip-compressed" => return Encoding::Zip,
+            "application/x-xz" => return Encoding::Xz,
+            "application/x-lzma" => return Encoding::Lzma,
+            _ => (),
+        }
+        match extension(url).as_deref() {
+            Some("gz") | Some("gzip") => Encoding::Gzip,
+            Some("zip") => Encoding::Zip,
+            Some("xz") => Encoding::Xz,
+            Some("lzma") | Some("lzma2") => Encoding::Lzma,
+            _ => Encoding::Identity,
+        }
+    }
+}
+
+/// Options used when fetching remote files
+///
+/// # Example
+/// Reading a csv file served by a local stand in for a remote server
+/// ```
+/// use dami::io::remote::RemoteOptions;
+/// use std::io::{Read, Write};
+/// use std::net::TcpListener;
+/// use std::time::Duration;
+///
+/// let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+/// let url = format!("http://{}/data.csv", listener.local_addr().unwrap());
+/// std::thread::spawn(move || {
+///     let (mut stream, _) = listener.accept().unwrap();
+///     let mut request = [0; 1024];
+///     let read = stream.read(&mut request).unwrap();
+///     let request = String::from_utf8_lossy(&request[..read]).to_string();
+///     assert!(request.contains("Authorization: Bearer token"));
+///     let body = "a,b\n1,2\n";
+///     write!(
+///         stream,
+///         "HTTP/1.1 200 OK\r\nContent-Type: text/csv\r\nContent-Length: {}\r\n\r\n{}",
+///         body.len(),
+///         body
+///     )
+///     .unwrap();
+/// });
+/// let mut options = RemoteOptions::new();
+/// options
+///     .set_bearer_auth("token")
+///     .set_timeout(Duration::from_secs(5));
+/// assert_eq!(options.read_to_string(&url).unwrap(), "a,b\n1,2\n");
+/// ```
+#[derive(Clone, Debug)]
+pub struct RemoteOptions {
+    headers: Vec<(String, String)>,
+    auth: Option<Auth>,
+    timeout: Option<Duration>,
+    connect_timeout: Option<Duration>,
+    retries: u32,
+    retry_delay: Duration,
+    cache_dir: Option<PathBuf>,
+}
+impl Default for RemoteOptions {
+    fn default() -> Self {
+        RemoteOptions {
+            headers: vec![],
+            auth: None,
+            timeout: None,
+            connect_timeout: None,
+            retries: 0,
+            retry_delay: Duration::from_millis(500),
+            cache_dir: None,
+        }
+    }
+}
+impl RemoteOptions {
+    /// Create new options
+    ///
+    /// By default requests have no extra headers, no authentication and no timeouts, failed requests
+    /// are not retried and downloads are not cached
+    pub fn new() -> RemoteOptions {
+        RemoteOptions::default()
+    }
+    /// Add a header sent with every request
+    pub fn set_header(&mut self, name: &str, value: &str) -> &mut Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+    /// Authenticate requests using HTTP basic authentication
+    ///
+    /// # Example
+    /// ```
+    /// use dami::io::remote::RemoteOptions;
+    /// let mut options = RemoteOptions::new();
+    /// options.set_basic_auth("user", "secret");
+    /// ```
+    pub fn set_basic_auth(&mut self, user: &str, password: &str) -> &mut Self {
+        self.auth = Some(Auth::Basic(user.to_string(), password.to_string()));
+        self
+    }
+    /// Authenticate requests using a bearer token
+    pub fn set_bearer_auth(&mut self, token: &str) -> &mut Self {
+        self.auth = Some(Auth::Bearer(token.to_string()));
+        self
+    }
+    /// Set the maximum time a request may take, including reading the response
+    pub fn set_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.timeout = Some(timeout);
+        self
+    }
+    /// Set the maximum time to wait for a connection to the server
+    pub fn set_connect_timeout(&mut self, timeout: Duration) -> &mut Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+    /// Retry requests that fail to connect or get a `429` or `5xx` status up to `retries` times
+    ///
+    /// The delay before each retry doubles starting from `delay`
+    ///
+    /// # Example
+    /// ```
+    /// use dami::io::remote::RemoteOptions;
+    /// use std::time::Duration;
+    /// let mut options = RemoteOptions::new();
+    /// // Wait 100ms, then 200ms and 400ms before giving up
+    /// options.set_retries(3, Duration::from_millis(100));
+    /// ```
+    pub fn set_retries(&mut self, retries: u32, delay: Duration) -> &mut Self {
+        self.retries = retries;
+        self.retry_delay = delay;
+        self
+    }
+    /// Keep downloads in `dir`
+    ///
+    /// Files are stored by a hash of their url and `ETag`, when a url is fetched again the server is asked
+    /// whether the file changed (using `If-None-Match`) and the cached file is used if it didn't.
+    /// Responses without an `ETag` are not cached
+    ///
+    /// # Example
+    /// ```
+    /// use dami::io::remote::RemoteOptions;
+    /// let mut options = RemoteOptions::new();
+    /// options.set_cache_dir(std::env::temp_dir().join("dami_cache"));
+    /// ```
+    pub fn set_cache_dir<P: AsRef<Path>>(&mut self, dir: P) -> &mut Self {
+        self.cache_dir = Some(dir.as_ref().to_path_buf());
+        self
+    }
+    /// Fetch `url` and return its decompressed body
+    /// # Errors
+    /// * If the request fails or the server responds with an error status
+    /// * If the body cannot be decompressed or is not valid UTF-8
+    pub fn read_to_string(&self, url: &str) -> Result<String, RemoteErrors> {
+        let mut body = String::new();
+        self.open(url)?.read_to_string(&mut body)?;
+        Ok(body)
+    }
+    /// Fetch `url` and return a reader over its decompressed body
+    ///
+    /// Without a cache directory the body is read straight from the connection as it arrives,
+    /// `gzip` and `deflate` bodies are decompressed while they are read so no temporary file is
+    /// written and readers can parse the start of the body before the download finishes. `zip`, `xz` and
+    /// `lzma` bodies are read to memory and decompressed first
+    /// # Errors
+    /// * If the request fails or the server responds with an error status
+    /// * If the body cannot be decompressed
+    /// # Example
+    /// ```no_run
+    /// use dami::io::remote::RemoteOptions;
+    /// use std::io::Read;
+    /// let mut text = String::new();
+    /// RemoteOptions::new()
+    ///     .open("https://example.com/data.csv.gz")
+    ///     .unwrap()
+    ///     .read_to_string(&mut text)
+    ///     .unwrap();
+    /// ```
+    pub fn open(&self, url: &str) -> Result<Box<dyn Read>, RemoteErrors> {
+        let parsed = Url::parse(url)?;
+        if self.cache_dir.is_none() {
+            let response = self.call(&parsed, None)?;
+            let encoding = Encoding::detect(
+                response.header("content-encoding"),
+                response.header("content-type"),
+                &parsed,
+            );
+            return decompress(response.into_reader(), encoding);
+        }
+        let (download, encoding) = self.fetch(&parsed)?;
+        let file = BufReader::new(File::open(&download)?);
+        // Keep temporary downloads until the body has been read
+        decompress(DownloadReader(file, download), encoding)
+    }
+    /// Download `url` to a file
+    ///
+    /// The body is written as it was received so compressed files stay compressed. If a cache
+    /// directory is set the file is in the cache, otherwise it is a new file in the system's
+    /// temporary directory named after the extension of the url (or of the `Content-Type`) which is
+    /// removed when the returned [`Download`](struct.Download.html) is dropped
+    /// # Errors
+    /// * If the request fails or the server responds with an error status
+    /// * If the file cannot be written
+    pub fn download(&self, url: &str) -> Result<Download, RemoteErrors> {
+        let parsed = Url::parse(url)?;
+        Ok(self.fetch(&parsed)?.0)
+    }
+    /// Fetch `url` to a file, from the cache if the server says it didn't change
+    fn fetch(&self, url: &Url) -> Result<(Download, Encoding), RemoteErrors> {
+        let cached = self.cache_dir.as_ref().and_then(|dir| {
+            let entry = dir.join(format!("{:016x}.json", fnv1a(url.as_str())));
+            let entry: CacheEntry = serde_json::from_reader(File::open(entry).ok()?).ok()?;
+            // Index files are named by a hash of the url, another url may share it
+            if entry.url != url.as_str() {
+                return None;
+            }
+            let path = dir.join(&entry.file);
+            if path.exists() {
+                Some((entry, path))
+            } else {
+                None
+            }
+        });
+        let response = self.call(url, cached.as_ref().map(|f| f.0.etag.as_str()))?;
+        if let Some((entry, path)) = cached {
+            if response.status() == 304 {
+                let encoding = Encoding::detect(
+                    entry.content_encoding.as_deref(),
+                    entry.content_type.as_deref(),
+                    url,
+                );
+                return Ok((Download::cached(path), encoding));
+            }
+        }
+        let content_encoding = response.header("content-encoding").map(ToString::to_string);
+        let content_type = response.header("content-type").map(ToString::to_string);
+        let encoding = Encoding::detect(content_encoding.as_deref(), content_type.as_deref(), url);
+        let extension = extension(url)
+            .or_else(|| type_extension(content_type.as_deref()))
+            .unwrap_or_else(|| "bin".to_string());
+        let etag = response.header("etag").map(ToString::to_string);
+        match (&self.cache_dir, etag) {
+            (Some(dir), Some(etag)) => {
+                create_dir_all(dir)?;
+                let file = format!(
+                    "{:016x}.{}",
+                    fnv1a(&format!("{}\n{}", url.as_str(), etag)),
+                    extension
+                );
+                let path = dir.join(&file);
+                // Write to a temporary name first so readers never see half written files
+                let (partial, mut fd) = unique_file(dir, &extension)?;
+                io::copy(&mut response.into_reader(), &mut fd)?;
+                fd.flush()?;
+                std::fs::rename(&partial, &path)?;
+                let entry = CacheEntry {
+                    url: url.as_str().to_string(),
+                    etag,
+                    file,
+                    content_type,
+                    content_encoding,
+                };
+                let index = dir.join(format!("{:016x}.json", fnv1a(url.as_str())));
+                // Like the body, the index is renamed into place once it is fully written
+                let (partial, mut fd) = unique_file(dir, "json")?;
+                serde_json::to_writer(&mut fd, &entry)
+                    .map_err(|err| RemoteErrors::IOError(err.into()))?;
+                fd.flush()?;
+                std::fs::rename(&partial, &index)?;
+                Ok((Download::cached(path), encoding))
+            }
+            _ => {
+                let (path, mut fd) = unique_file(&temp_dir(), &extension)?;
+                // Created before writing so a failed download is removed too
+                let download = Download {
+                    path,
+                    temporary: true,
+                };
+                io::copy(&mut response.into_reader(), &mut fd)?;
+                fd.flush()?;
+                Ok((download, encoding))
+            }
+        }
+    }
+    /// Send a GET request, retrying failures
+    fn call(&self, url: &Url, etag: Option<&str>) -> Result<ureq::Response, RemoteErrors> {
+        let mut delay = self.retry_delay;
+        let mut attempt = 0;
+        loop {
+            let response = self.request(url, etag).call();
+            let retry = response.synthetic() || response.status() == 429 || response.server_error();
+            if retry && attempt < self.retries {
+                attempt += 1;
+                sleep(delay);
+                delay *= 2;
+                continue;
+            }
+            if response.synthetic() {
+                return Err(RemoteErrors::Transport(
+                    response.into_synthetic_error().unwrap(),
+                ));
+            }
+            if response.error() {
+                return Err(RemoteErrors::Status(
+                    response.status(),
+                    url.as_str().to_string(),
+                ));
+            }
+            return Ok(response);
+        }
+    }
+    fn request(&self, url: &Url, etag: Option<&str>) -> ureq::Request {
+        let mut request = ureq::get(url.as_str());
+        for (name, value) in &self.headers {
+            request.set(name, value);
+        }
+        match &self.auth {
+            Some(Auth::Basic(user, password)) => {
+                request.auth(user, password);
+            }
+            Some(Auth::Bearer(token)) => {
+                request.auth_kind("Bearer", token);
+            }
+            None => (),
+        }
+        if let Some(timeout) = self.timeout {
+            request.timeout(timeout);
+        }
+        if let Some(timeout) = self.connect_timeout {
+            request.timeout_connect(timeout.as_millis() as u64);
+        }
+        if let Some(etag) = etag {
+            request.set("If-None-Match", etag);
+        }
+        request
+    }
+}
+
+/// Set the options used by every reader when it fetches a url
+///
+/// # Example
+/// ```no_run
+/// use dami::io::remote::{set_remote_options, RemoteOptions};
+/// use dami::prelude::*;
+///
+/// let mut options = RemoteOptions::new();
+/// options.set_cache_dir("/tmp/dami_cache").set_header("User-Agent", "dami");
+/// set_remote_options(options);
+/// // Downloaded once, later reads are served from the cache while the file doesn't change
+/// let df = read_csv("https://example.com/data.csv", None);
+/// ```
+pub fn set_remote_options(options: RemoteOptions) {
+    *OPTIONS.write().unwrap() = options;
+}
+/// The options set by [`set_remote_options`](fn.set_remote_options.html)
+pub fn remote_options() -> RemoteOptions {
+    OPTIONS.read().unwrap().clone()
+}
+
+/// A file downloaded by [`RemoteOptions::download`](struct.RemoteOptions.html#method.download)
+///
+/// Files downloaded to the temporary directory are removed when this is dropped, files in the cache
+/// directory are kept
+#[derive(Debug)]
+pub struct Download {
+    path: PathBuf,
+    temporary: bool,
+}
+impl Download {
+    fn cached(path: PathBuf) -> Download {
+        Download {
+            path,
+            temporary: false,
+        }
+    }
+    /// Path of the downloaded file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+    /// Keep the file after this is dropped and return its path
+    pub fn keep(mut self) -> PathBuf {
+        self.temporary = false;
+        self.path.clone()
+    }
+}
+impl Deref for Download {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.path
+    }
+}
+impl AsRef<Path> for Download {
+    fn as_ref(&self) -> &Path {
+        &self.path
+    }
+}
+impl Drop for Download {
+    fn drop(&mut self) {
+        if self.temporary {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+/// Reads a downloaded file, removing it once the reader is dropped
+struct DownloadReader<R>(R, Download);
+
+impl<R: Read> Read for DownloadReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.0.read(buf)
+    }
+}
+
+/// What the cache knows about a url
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    /// The url the entry is for, compared on lookup as index files are named by its hash
+    url: String,
+    etag: String,
+    /// Name of the cached body inside the cache directory
+    file: String,
+    content_type: Option<String>,
+    content_encoding: Option<String>,
+}
+
+/// Wrap `reader` in a decoder for `encoding`
+fn decompress<R: Read + 'static>(
+    mut reader: R,
+    encoding: Encoding,
+) -> Result<Box<dyn Read>, RemoteErrors> {
+    match encoding {
+        Encoding::Identity => Ok(Box::new(reader)),
+        Encoding::Gzip => Ok(Box::new(MultiGzDecoder::new(reader))),
+        Encoding::Deflate => Ok(Box::new(ZlibDecoder::new(reader))),
+        Encoding::Zip => {
+            let mut bytes = vec![];
+            reader.read_to_end(&mut bytes)?;
+            let mut archive = ZipArchive::new(Cursor::new(bytes))
+                .map_err(|err| RemoteErrors::Decompress(err.to_string()))?;
+            if archive.len() != 1 {
+                return Err(RemoteErrors::Decompress(format!(
+                    "Zip archives should contain only one file, {} files were found",
+                    archive.len()
+                )));
+            }
+            let mut file = archive
+                .by_index(0)
+                .map_err(|err| RemoteErrors::Decompress(err.to_string()))?;
+            let mut decompressed = vec![];
+            file.read_to_end(&mut decompressed)?;
+            Ok(Box::new(Cursor::new(decompressed)))
+        }
+        Encoding::Xz | Encoding::Lzma => {
+            let mut reader = BufReader::new(reader);
+            let mut decompressed = vec![];
+            let result = if encoding == Encoding::Xz {
+                xz_decompress(&mut reader, &mut decompressed)
+            } else {
+                lzma_decompress(&mut reader, &mut decompressed)
+            };
+            result.map_err(|err| RemoteErrors::Decompress(format!("{:?}", err)))?;
+            Ok(Box::new(Cursor::new(decompressed)))
+        }
+    }
+}
+/// Extension of the last segment of the url path, lower cased
+fn extension(url: &Url) -> Option<String> {
+    Path::new(url.path())
+        .extension()
+        .and_then(|f| f.to_str())
+        .map(str::to_lowercase)
+}
+/// Extension for files of a `Content-Type`
+fn type_extension(content_type: Option<&str>) -> Option<String> {
+    let content_type = content_type?.split(';').next()?.trim().to_lowercase();
+    let extension = match content_type.as_str() {
+        "text/csv" => "csv",
+        "application/json" => "json",
+        "text/html" => "html",
+        "text/plain" => "txt",
+        "application/gzip" | "application/x-gzip" => "gz",
+        "application/zip" | "application/x-zip-compressed" => "zip",
+        "application/x-xz" => "xz",
+        "application/x-lzma" => "lzma",
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => "xlsx",
+        "application/vnd.ms-excel" => "xls",
+        "application/vnd.oasis.opendocument.spreadsheet" => "ods",
+        _ => return None,
+    };
+    Some(extension.to_string())
+}
+/// Create a new file in `dir` whose name no other reader (in this or another process) uses
+fn unique_file(dir: &Path, extension: &str) -> io::Result<(PathBuf, File)> {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|f| f.subsec_nanos())
+        .unwrap_or(0);
+    loop {
+        let path = dir.join(format!(
+            "dami_temp_{}_{}_{}.{}",
+            std::process::id(),
+            nanos,
+            TEMP_FILES.fetch_add(1, Ordering::SeqCst),
+            extension
+        ));
+        match OpenOptions::new().write(true).create_new(true).open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(err) => return Err(err),
+        }
+    }
+}
+/// 64 bit FNV-1a hash, stable across runs and Rust versions unlike `DefaultHasher`
+fn fnv1a(text: &str) -> u64 {
+    text.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fnv1a, RemoteOptions};
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread::JoinHandle;
+    use std::time::Duration;
+
+    /// Serve one response per connection, returning the url and the requests received
+    fn serve(responses: Vec<Vec<u8>>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/data.csv", listener.local_addr().unwrap());
+        let server = std::thread::spawn(move || {
+            let mut requests = vec![];
+            for response in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut request = vec![];
+                let mut buf = [0; 1024];
+                while !request.ends_with(b"\r\n\r\n") {
+                    let read = stream.read(&mut buf).unwrap();
+                    if read == 0 {
+                        break;
+                    }
+                    request.extend_from_slice(&buf[..read]);
+                }
+                stream.write_all(&response).unwrap();
+                requests.push(String::from_utf8_lossy(&request).to_string());
+            }
+            requests
+        });
+        (url, server)
+    }
+    fn response(status: &str, headers: &str, body: &[u8]) -> Vec<u8> {
+        let mut response = format!(
+            "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n",
+            status,
+            headers,
+            body.len()
+        )
+        .into_bytes();
+        response.extend_from_slice(body);
+        response
+    }
+
+    #[test]
+    fn basic_auth_header() {
+        let (url, server) = serve(vec![response("200 OK", "", b"a\n1\n")]);
+        let mut options = RemoteOptions::new();
+        options.set_basic_auth("user", "secret");
+        assert_eq!(options.read_to_string(&url).unwrap(), "a\n1\n");
+        let requests = server.join().unwrap();
+        assert!(requests[0].contains("Authorization: Basic dXNlcjpzZWNyZXQ="));
+    }
+
+    #[test]
+    fn retries_unavailable_and_rate_limited() {
+        let (url, server) = serve(vec![
+            response("503 Service Unavailable", "", b""),
+            response("429 Too Many Requests", "", b""),
+            response("200 OK", "", b"a\n1\n"),
+        ]);
+        let mut options = RemoteOptions::new();
+        options.set_retries(2, Duration::from_millis(10));
+        assert_eq!(options.read_to_string(&url).unwrap(), "a\n1\n");
+        assert_eq!(server.join().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn cache_hit_on_not_modified() {
+        let (url, server) = serve(vec![
+            response("200 OK", "ETag: \"v1\"\r\n", b"a\n1\n"),
+            response("304 Not Modified", "ETag: \"v1\"\r\n", b""),
+        ]);
+        let dir = std::env::temp_dir().join(format!("dami_cache_test_{}", std::process::id()));
+        let mut options = RemoteOptions::new();
+        options.set_cache_dir(&dir);
+        assert_eq!(options.read_to_string(&url).unwrap(), "a\n1\n");
+        // The server answers 304 so the body is read from the cache
+        assert_eq!(options.read_to_string(&url).unwrap(), "a\n1\n");
+        let requests = server.join().unwrap();
+        assert!(!requests[0].contains("If-None-Match"));
+        assert!(requests[1].contains("If-None-Match: \"v1\""));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn cache_entry_for_another_url_is_a_miss() {
+        let (url, server) = serve(vec![response("200 OK", "", b"a\n1\n")]);
+        let dir = std::env::temp_dir().join(format!("dami_cache_miss_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        // An entry stored under the same hash by another url
+        std::fs::write(dir.join("stale.csv"), "b\n2\n").unwrap();
+        std::fs::write(
+            dir.join(format!("{:016x}.json", fnv1a(&url))),
+            r#"{"url":"http://other/data.csv","etag":"\"v1\"","file":"stale.csv","content_type":null,"content_encoding":null}"#,
+        )
+        .unwrap();
+        let mut options = RemoteOptions::new();
+        options.set_cache_dir(&dir);
+        assert_eq!(options.read_to_string(&url).unwrap(), "a\n1\n");
+        let requests = server.join().unwrap();
+        assert!(!requests[0].contains("If-None-Match"));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn gzip_from_headers() {
+        let mut encoder = GzEncoder::new(vec![], Compression::default());
+        encoder.write_all(b"a,b\n1,2\n").unwrap();
+        let body = encoder.finish().unwrap();
+        let (url, server) = serve(vec![
+            response(
+                "200 OK",
+                "Content-Encoding: gzip\r\nContent-Type: text/csv\r\n",
+                &body,
+            ),
+            response("200 OK", "Content-Type: application/gzip\r\n", &body),
+        ]);
+        let options = RemoteOptions::new();
+        for _ in 0..2 {
+            assert_eq!(options.read_to_string(&url).unwrap(), "a,b\n1,2\n");
+        }
+        server.join().unwrap();
+    }
+}
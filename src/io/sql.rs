@@ -0,0 +1,557 @@
+//! Read and write SQLite tables
+//!
+//! Requires feature `sql`
+//!
+//! # Reading
+//! Columns are mapped to a dtype using the declared type of the column following
+//! [SQLite affinity rules](https://www.sqlite.org/datatype3.html#determination_of_column_affinity)
+//!
+//! | Declared type contains                  | DataType   |
+//! |-----------------------------------------|------------|
+//! | `BOOL`                                  | `BOOL`     |
+//! | `DATE` or `TIME`                        | `DATETIME` |
+//! | `INT`                                   | `I64`      |
+//! | `CHAR`, `CLOB` or `TEXT`                | `STRING`   |
+//! | `REAL`, `FLOA` or `DOUB`                | `F64`      |
+//!
+//! Columns without a declared type (eg expressions) or whose values don't match the declared type
+//! are inferred from their values, integers become `I64`, a mix of integers and reals
+//! become `F64` and anything containing text or blobs become `STRING`. Datetime columns holding
+//! text that isn't a date or time are read as `STRING` so no values are lost.
+//!
+//! ## Nulls
+//! * Integer and boolean columns containing nulls are promoted to `f64` with nulls becoming `NaN`
+//! * Null reals become `NaN`
+//! * Null strings become empty strings
+//! * Null datetimes become [`NAT`]
+//!
+//! # Writing
+//! Tables are created with column types inferred from the dtypes of the DataFrame
+//!
+//! | DataType          | SQLite type |
+//! |-------------------|-------------|
+//! | `BOOL`            | `BOOLEAN`   |
+//! | `I32`, `I64`      | `INTEGER`   |
+//! | `F32`, `F64`      | `REAL`      |
+//! | `STRING`, `STR`   | `TEXT`      |
+//! | `DATETIME`        | `TIMESTAMP` |
+//!
+//! `NaN` and [`NAT`] values are written as `NULL` and datetimes are stored as
+//! `YYYY-MM-DD HH:MM:SS` text, which is what SQLite date functions understand.
+//!
+//! [`NAT`]: ../../core/index/date_time/constant.NAT.html
+#![cfg(feature = "sql")]
+use crate::core::dataframe::DataFrame;
+use crate::core::index::date_time::{format_timestamp, parse_timestamp, NAT};
+use crate::core::series::Series;
+use crate::enums::{DataFrameErrors, DataTypes};
+use crate::io::dtypes::add_named_series;
+
+use rusqlite::types::Value;
+use rusqlite::{Connection, Rows, Statement, NO_PARAMS};
+
+use std::fmt;
+
+/// Errors that may occur when reading or writing SQLite tables
+pub enum SqlErrors {
+    /// An error originating from SQLite
+    Sqlite(rusqlite::Error),
+    /// The table already exists and `IfExists::Fail` was passed
+    TableExists(String),
+    /// The DataFrame contains a dtype that cannot be stored in SQLite
+    UnsupportedDtype(DataTypes),
+    /// A value in a later chunk doesn't fit the dtype inferred for its column from the first chunk
+    ChunkDtype(String, DataTypes),
+    /// The columns could not be put together in a DataFrame, eg two columns share a name
+    DataFrame(DataFrameErrors),
+}
+impl fmt::Debug for SqlErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Sqlite(ref err) => write!(f, "{}", err),
+            Self::TableExists(ref table) => write!(f, "Table {} already exists", table),
+            Self::UnsupportedDtype(ref dtype) => {
+                write!(f, "dtype {:?} cannot be written to SQLite", dtype)
+            }
+            Self::ChunkDtype(ref column, ref dtype) => write!(
+                f,
+                "Column {} has a value that doesn't fit its dtype {:?} inferred from the first chunk",
+                column, dtype
+            ),
+            Self::DataFrame(ref err) => write!(f, "{:?}", err),
+        }
+    }
+}
+impl From<rusqlite::Error> for SqlErrors {
+    fn from(err: rusqlite::Error) -> Self {
+        Self::Sqlite(err)
+    }
+}
+impl From<DataFrameErrors> for SqlErrors {
+    fn from(err: DataFrameErrors) -> Self {
+        Self::DataFrame(err)
+    }
+}
+
+/// What [`to_sql`](../../core/dataframe/struct.DataFrame.html#method.to_sql) does if the table
+/// already exists
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum IfExists {
+    /// Return a `TableExists` error
+    Fail,
+    /// Drop the table and create it again
+    Replace,
+    /// Insert the rows into the existing table
+    Append,
+}
+
+/// Read the result of a query into a DataFrame
+///
+/// # Errors
+/// * `Sqlite`: If the query is invalid or fails
+/// * `DataFrame`: If two columns have the same name
+/// # Example
+/// ```
+/// use dami::io::sql::read_sql_query;
+/// use rusqlite::Connection;
+/// let conn = Connection::open_in_memory().unwrap();
+/// let df = read_sql_query(&conn, "SELECT 1 AS a, 'b' AS b").unwrap();
+/// assert_eq!(df.columns(), vec!["a".to_string(), "b".to_string()]);
+/// ```
+pub fn read_sql_query(conn: &Connection, query: &str) -> Result<DataFrame, SqlErrors> {
+    let mut stmt = conn.prepare(query)?;
+    let columns = column_info(&stmt);
+    let mut rows = stmt.query(NO_PARAMS)?;
+    let (_, values) = take_rows(&mut rows, columns.len(), usize::MAX)?;
+    let mut df = DataFrame::new();
+    for ((name, decl_type), column) in columns.iter().zip(values) {
+        let dtype = infer_dtype(decl_type.as_deref(), &column);
+        add_column(&mut df, name, &dtype, column)?;
+    }
+    Ok(df)
+}
+/// Read the rows of a prepared statement in chunks of `chunksize` rows
+///
+/// The statement is run once and its rows are streamed, so only one chunk is held in memory
+/// at a time.
+///
+/// Dtypes are inferred once from the declared column types and the first chunk, every
+/// later chunk has the same dtypes. Integer and boolean columns without nulls in the first
+/// chunk stay integers and booleans, so a later null in them is an error instead of a silent
+/// promotion to `f64`.
+/// # Errors
+/// * `Sqlite`: If the statement fails to start
+///
+/// Errors while reading rows are returned by the iterator, which returns `ChunkDtype` if a
+/// value in a later chunk doesn't fit the dtype inferred for its column. The iterator stops
+/// after the first error.
+/// # Panics
+/// If `chunksize` is zero
+/// # Example
+/// ```
+/// use dami::io::sql::read_sql_query_chunked;
+/// use rusqlite::Connection;
+/// let conn = Connection::open_in_memory().unwrap();
+/// conn.execute_batch("CREATE TABLE t (a INTEGER); INSERT INTO t VALUES (1), (2), (3);")
+///     .unwrap();
+/// let mut stmt = conn.prepare("SELECT a FROM t").unwrap();
+/// let lengths = read_sql_query_chunked(&mut stmt, 2)
+///     .unwrap()
+///     .map(|chunk| chunk.unwrap().len())
+///     .collect::<Vec<usize>>();
+/// assert_eq!(lengths, vec![2, 1]);
+/// ```
+pub fn read_sql_query_chunked<'a>(
+    stmt: &'a mut Statement<'_>,
+    chunksize: usize,
+) -> Result<SqlChunks<'a>, SqlErrors> {
+    assert_ne!(chunksize, 0, "chunksize should be greater than zero");
+    let columns = column_info(stmt);
+    Ok(SqlChunks {
+        rows: stmt.query(NO_PARAMS)?,
+        columns,
+        dtypes: None,
+        chunksize,
+        done: false,
+    })
+}
+/// An iterator that yields the result of a query in DataFrames of at most `chunksize` rows
+///
+/// Created by [`read_sql_query_chunked`](fn.read_sql_query_chunked.html)
+pub struct SqlChunks<'a> {
+    rows: Rows<'a>,
+    columns: Vec<(String, Option<String>)>,
+    dtypes: Option<Vec<DataTypes>>,
+    chunksize: usize,
+    done: bool,
+}
+impl SqlChunks<'_> {
+    fn read_chunk(&mut self) -> Result<Option<DataFrame>, SqlErrors> {
+        let (count, values) = take_rows(&mut self.rows, self.columns.len(), self.chunksize)?;
+        if count < self.chunksize {
+            self.done = true;
+        }
+        if count == 0 {
+            return Ok(None);
+        }
+        let mut df = DataFrame::new();
+        if let Some(dtypes) = &self.dtypes {
+            for (((name, _), dtype), column) in self.columns.iter().zip(dtypes).zip(values) {
+                let has_nulls = column.contains(&Value::Null);
+                let nullable = !matches!(dtype, DataTypes::BOOL | DataTypes::I64);
+                if !fits(dtype, &column) || (has_nulls && !nullable) {
+                    return Err(SqlErrors::ChunkDtype(name.clone(), dtype.clone()));
+                }
+                add_column(&mut df, name, dtype, column)?;
+            }
+        } else {
+            let mut dtypes = Vec::with_capacity(self.columns.len());
+            for ((name, decl_type), column) in self.columns.iter().zip(values) {
+                let dtype = infer_dtype(decl_type.as_deref(), &column);
+                dtypes.push(add_column(&mut df, name, &dtype, column)?);
+            }
+            self.dtypes = Some(dtypes);
+        }
+        Ok(Some(df))
+    }
+}
+impl Iterator for SqlChunks<'_> {
+    type Item = Result<DataFrame, SqlErrors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let chunk = self.read_chunk();
+        if chunk.is_err() {
+            self.done = true;
+        }
+        chunk.transpose()
+    }
+}
+/// Get the names and upper cased declared types of the columns a statement returns
+fn column_info(stmt: &Statement<'_>) -> Vec<(String, Option<String>)> {
+    stmt.columns()
+        .iter()
+        .map(|f| (f.name().to_string(), f.decl_type().map(str::to_uppercase)))
+        .collect()
+}
+/// Read at most `limit` rows, returning the number of rows read and the values of each column
+fn take_rows(
+    rows: &mut Rows<'_>,
+    width: usize,
+    limit: usize,
+) -> Result<(usize, Vec<Vec<Value>>), SqlErrors> {
+    let mut values: Vec<Vec<Value>> = vec![vec![]; width];
+    let mut count = 0;
+    while count < limit {
+        match rows.next()? {
+            Some(row) => {
+                for (i, column) in values.iter_mut().enumerate() {
+                    column.push(Value::from(row.get_raw(i)));
+                }
+                count += 1;
+            }
+            None => break,
+        }
+    }
+    Ok((count, values))
+}
+/// Determine the dtype of a column from its declared type falling back to its values
+fn infer_dtype(decl_type: Option<&str>, values: &[Value]) -> DataTypes {
+    let declared = decl_type.map(|decl| {
+        if decl.contains("BOOL") {
+            DataTypes::BOOL
+        } else if decl.contains("DATE") || decl.contains("TIME") {
+            DataTypes::DATETIME
+        } else if decl.contains("INT") {
+            DataTypes::I64
+        } else if decl.contains("CHAR") || decl.contains("CLOB") || decl.contains("TEXT") {
+            DataTypes::STRING
+        } else if decl.contains("REAL") || decl.contains("FLOA") || decl.contains("DOUB") {
+            DataTypes::F64
+        } else {
+            // BLOB and NUMERIC affinity
+            DataTypes::OBJECT
+        }
+    });
+    match declared {
+        Some(dtype) if dtype != DataTypes::OBJECT && fits(&dtype, values) => dtype,
+        _ => {
+            if fits(&DataTypes::I64, values) {
+                DataTypes::I64
+            } else if fits(&DataTypes::F64, values) {
+                DataTypes::F64
+            } else {
+                DataTypes::STRING
+            }
+        }
+    }
+}
+/// Check whether every value of a column can be stored as `dtype`
+fn fits(dtype: &DataTypes, values: &[Value]) -> bool {
+    values.iter().all(|value| match (dtype, value) {
+        (_, Value::Null)
+        | (DataTypes::STRING, _)
+        | (DataTypes::DATETIME, Value::Integer(_))
+        | (DataTypes::I64, Value::Integer(_))
+        | (DataTypes::F64, Value::Integer(_))
+        | (DataTypes::F64, Value::Real(_)) => true,
+        (DataTypes::BOOL, Value::Integer(v)) => *v == 0 || *v == 1,
+        // Text that isn't a date would be lost as NaT, read such columns as strings
+        (DataTypes::DATETIME, Value::Text(v)) => parse_timestamp(v) != NAT,
+        _ => false,
+    })
+}
+/// Convert the values of a column to a Series of `dtype` and add it to the DataFrame
+///
+/// Returns the dtype of the added Series, which is `F64` for integer and boolean columns
+/// with nulls, or an error if the Series cannot be added
+#[allow(clippy::cast_precision_loss)]
+fn add_column(
+    df: &mut DataFrame,
+    name: &str,
+    dtype: &DataTypes,
+    values: Vec<Value>,
+) -> Result<DataTypes, DataFrameErrors> {
+    let has_nulls = values.iter().any(|f| *f == Value::Null);
+    match dtype {
+        DataTypes::BOOL if !has_nulls => {
+            let values = values
+                .iter()
+                .map(|f| *f == Value::Integer(1))
+                .collect::<Vec<bool>>();
+            add_named_series(df, Series::from(values), name)?;
+            Ok(DataTypes::BOOL)
+        }
+        DataTypes::I64 if !has_nulls => {
+            let values = values
+                .iter()
+                .map(|f| match f {
+                    Value::Integer(v) => *v,
+                    _ => 0,
+                })
+                .collect::<Vec<i64>>();
+            add_named_series(df, Series::from(values), name)?;
+            Ok(DataTypes::I64)
+        }
+        DataTypes::BOOL | DataTypes::I64 | DataTypes::F64 => {
+            let values = values
+                .iter()
+                .map(|f| match f {
+                    Value::Integer(v) => *v as f64,
+                    Value::Real(v) => *v,
+                    _ => f64::NAN,
+                })
+                .collect::<Vec<f64>>();
+            add_named_series(df, Series::from(values), name)?;
+            Ok(DataTypes::F64)
+        }
+        DataTypes::DATETIME => {
+            let values = values
+                .iter()
+                .map(|f| match f {
+                    Value::Integer(v) => *v,
+                    Value::Text(v) => parse_timestamp(v),
+                    _ => NAT,
+                })
+                .collect::<Vec<i64>>();
+            let mut series = Series::from(values);
+            series.set_dtype(DataTypes::DATETIME);
+            add_named_series(df, series, name)?;
+            Ok(DataTypes::DATETIME)
+        }
+        _ => {
+            let values = values
+                .into_iter()
+                .map(|f| match f {
+                    Value::Null => String::new(),
+                    Value::Integer(v) => v.to_string(),
+                    Value::Real(v) => v.to_string(),
+                    Value::Text(v) => v,
+                    Value::Blob(v) => String::from_utf8_lossy(&v).to_string(),
+                })
+                .collect::<Vec<String>>();
+            add_named_series(df, Series::from(values), name)?;
+            Ok(DataTypes::STRING)
+        }
+    }
+}
+/// Quote an identifier so that names with spaces or keywords can be used as table and column names
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+/// Convert the values of a column into SQLite values
+fn column_values(df: &DataFrame, name: &str, dtype: &DataTypes) -> Result<Vec<Value>, SqlErrors> {
+    let values = match dtype {
+        DataTypes::BOOL => df
+            .get::<bool>(name)
+            .unwrap()
+            .into_iter()
+            .map(|f| Value::Integer(i64::from(f)))
+            .collect(),
+        DataTypes::I32 => df
+            .get::<i32>(name)
+            .unwrap()
+            .into_iter()
+            .map(|f| Value::Integer(i64::from(f)))
+            .collect(),
+        DataTypes::I64 => df
+            .get::<i64>(name)
+            .unwrap()
+            .into_iter()
+            .map(Value::Integer)
+            .collect(),
+        DataTypes::F32 => df
+            .get::<f32>(name)
+            .unwrap()
+            .into_iter()
+            .map(|f| {
+                if f.is_nan() {
+                    Value::Null
+                } else {
+                    Value::Real(f64::from(f))
+                }
+            })
+            .collect(),
+        DataTypes::F64 => df
+            .get::<f64>(name)
+            .unwrap()
+            .into_iter()
+            .map(|f| {
+                if f.is_nan() {
+                    Value::Null
+                } else {
+                    Value::Real(f)
+                }
+            })
+            .collect(),
+        DataTypes::STRING => df
+            .get::<String>(name)
+            .unwrap()
+            .into_iter()
+            .map(Value::Text)
+            .collect(),
+        DataTypes::STR => df
+            .get::<&str>(name)
+            .unwrap()
+            .into_iter()
+            .map(|f| Value::Text(f.to_string()))
+            .collect(),
+        DataTypes::DATETIME => df
+            .get::<i64>(name)
+            .unwrap()
+            .into_iter()
+            .map(|f| {
+                if f == NAT {
+                    Value::Null
+                } else {
+                    Value::Text(format_timestamp(f))
+                }
+            })
+            .collect(),
+        DataTypes::OBJECT => return Err(SqlErrors::UnsupportedDtype(DataTypes::OBJECT)),
+    };
+    Ok(values)
+}
+/// Get the SQLite column type used when creating a table
+fn sql_type(dtype: &DataTypes) -> &'static str {
+    match dtype {
+        DataTypes::BOOL => "BOOLEAN",
+        DataTypes::I32 | DataTypes::I64 => "INTEGER",
+        DataTypes::F32 | DataTypes::F64 => "REAL",
+        DataTypes::DATETIME => "TIMESTAMP",
+        DataTypes::STRING | DataTypes::STR | DataTypes::OBJECT => "TEXT",
+    }
+}
+fn table_exists(conn: &Connection, table: &str) -> Result<bool, SqlErrors> {
+    let mut stmt = conn.prepare("SELECT 1 FROM sqlite_master WHERE type='table' AND name=?")?;
+    Ok(stmt.exists(&[table])?)
+}
+
+impl DataFrame {
+    /// # Requires Feature
+    ///  > * `sql`
+    ///
+    /// Write the DataFrame to a SQLite table
+    ///
+    /// The table is created with column types inferred from the dtypes of the DataFrame
+    /// if it doesn't exist, all rows are inserted in one transaction.
+    ///
+    /// The DataFrame index is not written.
+    /// # Arguments
+    /// * `conn`: The database connection
+    /// * `table`: The name of the table
+    /// * `if_exists`: What to do if the table already exists
+    /// # Errors
+    /// * `TableExists`: If the table exists and `if_exists` is `IfExists::Fail`
+    /// * `UnsupportedDtype`: If the DataFrame contains `object` columns
+    /// * `Sqlite`: If any statement fails, in which case no rows are inserted
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// use dami::io::sql::{read_sql_query, IfExists};
+    /// use rusqlite::Connection;
+    /// let conn = Connection::open_in_memory().unwrap();
+    /// let mut df = DataFrame::new();
+    /// df.add_series(Series::from(vec![1_i64, 2, 3]), true).unwrap();
+    /// df.to_sql(&conn, "numbers", IfExists::Fail).unwrap();
+    /// let read = read_sql_query(&conn, "SELECT * FROM numbers").unwrap();
+    /// assert_eq!(read.get::<i64>("series").unwrap().to_vec(), vec![1, 2, 3]);
+    /// ```
+    pub fn to_sql(
+        &self,
+        conn: &Connection,
+        table: &str,
+        if_exists: IfExists,
+    ) -> Result<(), SqlErrors> {
+        let names = self.columns();
+        let dtypes = self.dtypes();
+        let mut columns = vec![];
+        for name in &names {
+            columns.push(column_values(self, name, &dtypes[name])?);
+        }
+        let transaction = conn.unchecked_transaction()?;
+        let exists = table_exists(&transaction, table)?;
+        if exists && if_exists == IfExists::Fail {
+            return Err(SqlErrors::TableExists(table.to_string()));
+        }
+        if exists && if_exists == IfExists::Replace {
+            transaction.execute(
+                &format!("DROP TABLE {}", quote_identifier(table)),
+                NO_PARAMS,
+            )?;
+        }
+        if !exists || if_exists == IfExists::Replace {
+            let definitions = names
+                .iter()
+                .map(|name| format!("{} {}", quote_identifier(name), sql_type(&dtypes[name])))
+                .collect::<Vec<String>>();
+            transaction.execute(
+                &format!(
+                    "CREATE TABLE {} ({})",
+                    quote_identifier(table),
+                    definitions.join(", ")
+                ),
+                NO_PARAMS,
+            )?;
+        }
+        {
+            let mut stmt = transaction.prepare(&format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                quote_identifier(table),
+                names
+                    .iter()
+                    .map(|f| quote_identifier(f))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                vec!["?"; names.len()].join(", ")
+            ))?;
+            for row in 0..self.len() {
+                stmt.execute(columns.iter().map(|column| &column[row]))?;
+            }
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
@@ -5,26 +5,21 @@
 //! This module provides common functions for IO operations like reading local
 //! and remote files
 //!
-//! For remote binary files eg excel and compressed files
-//! They are written to the system's temporary directory and then read from there.
+//! Remote files are fetched using the options set in the [`remote`](../remote/index.html) module
 extern crate lzma_rs;
-#[cfg(feature = "remote")]
-extern crate ureq;
-#[cfg(feature = "remote")]
-extern crate url;
 extern crate zip;
 
 use std::env::temp_dir;
 use std::fmt;
 use std::fmt::Formatter;
 use std::fs::{File, OpenOptions};
-use std::io::{BufReader, BufWriter, Read, Write};
-use std::path::Path;
+use std::io::{self, BufRead, BufReader, BufWriter, Cursor, ErrorKind, Read, Write};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use lzma_rs::{lzma_decompress, xz_decompress};
 #[cfg(feature = "remote")]
-use url::Url;
+use crate::io::remote::{remote_options, Download};
+use lzma_rs::{lzma_decompress, xz_decompress};
 use zip::ZipArchive;
 
 /// Main enum for Error types
@@ -44,6 +39,11 @@ impl<'a> std::fmt::Debug for IOError {
         }
     }
 }
+impl From<IOError> for io::Error {
+    fn from(err: IOError) -> Self {
+        io::Error::new(ErrorKind::InvalidData, format!("{:?}", err))
+    }
+}
 
 /// Open a file and return the string representation of it
 ///
@@ -56,36 +56,39 @@ impl<'a> std::fmt::Debug for IOError {
 /// You may run out of memory
 ///
 /// [`AsRef<Path>`]: https://doc.rust-lang.org/std/convert/trait.AsRef.html
-fn read_file<P: AsRef<Path> + fmt::Debug + Clone>(path: P) -> String {
+fn read_file<P: AsRef<Path> + fmt::Debug + Clone>(path: P) -> io::Result<String> {
     let mut temp = String::new();
-    let fd =
-        File::open(path.clone()).unwrap_or_else(|_| panic!("Could not open {:?}", path.as_ref()));
-    let mut reader = BufReader::new(fd);
-    reader.read_to_string(&mut temp).unwrap();
-    temp
+    let mut reader = BufReader::new(File::open(path)?);
+    reader.read_to_string(&mut temp)?;
+    Ok(temp)
 }
 /// Fetch data from a remote url
 ///
-/// This uses [`curl`](/curl/0.4.29/curl/) library to fetch data
+/// The request uses the options set by [`set_remote_options`] and compressed bodies are
+/// decompressed
 ///
 /// # Arguments
 /// * `url`: The remote website to fetch data
 /// > `type`:[`&str`]
-/// # Panics
-/// - This function is safe when fetching [UTF-8](https://en.wikipedia.org/wiki/UTF-8) data
-/// if data is in another encoding it is converted to � (UTF-8 replacement character)
-/// - If the curl library fails for some reason
+/// # Errors
+/// - If the request fails or the server responds with an error status
+/// - If the body cannot be decompressed or is not valid [UTF-8](https://en.wikipedia.org/wiki/UTF-8)
 ///
-/// [`FromUtf8Error`]: /std/string/struct.FromUtf8Error.html
+/// [`set_remote_options`]: ../remote/fn.set_remote_options.html
 /// [`&str`]: https://doc.rust-lang.org/nightly/std/primitive.str.html
 #[cfg(feature = "remote")]
-fn get_remote(url: &str) -> String {
-    ureq::get(url).call().into_string().unwrap()
+fn get_remote(url: &str) -> io::Result<String> {
+    remote_options().read_to_string(url).map_err(|err| {
+        io::Error::new(
+            ErrorKind::Other,
+            format!("Could not fetch {}: {:?}", url, err),
+        )
+    })
 }
 /// Determines whether the resulting path is to be opened as a url or opened as a file
 /// And calls the underlying function to fetch data
 /// * `path`: The path to the file, can be a [URL](https://en.wikipedia.org/wiki/Uniform_Resource_Locator)
-///    or a String pointing to a local file name
+///    or a String pointing to a local file name, `file://` urls are read as local files
 /// # Returns
 /// * A [`String`] containing data in the file/url
 /// # Panics
@@ -93,125 +96,137 @@ fn get_remote(url: &str) -> String {
 ///  Passing binary data will cause it to PANIC and you will be presented with [`FromUtf8Error`]
 ///
 /// [`FromUtf8Error`]: /std/string/struct.FromUtf8Error.html
-#[allow(unreachable_code)]
 pub fn read<P: AsRef<Path> + fmt::Debug + Clone>(path: P) -> String {
-    // TODO: These functions are wrangled it would be nice if they were done better
-    if is_url(path.as_ref().to_str().unwrap()) {
+    try_read(path.clone()).unwrap_or_else(|err| panic!("Could not read {:?}: {:?}", path, err))
+}
+/// Read a file or url to a string like [`read`](fn.read.html) returning an error instead of panicking
+/// # Errors
+/// - If the file cannot be opened or the request fails
+/// - If a compressed file cannot be decompressed
+/// - If the data is not valid [UTF-8](https://en.wikipedia.org/wiki/UTF-8)
+/// - If `path` is a url and the `remote` feature is disabled
+pub fn try_read<P: AsRef<Path> + fmt::Debug + Clone>(path: P) -> io::Result<String> {
+    read_resolved(to_local_path(path))
+}
+#[allow(unreachable_code)]
+fn read_resolved<P: AsRef<Path> + fmt::Debug + Clone>(path: P) -> io::Result<String> {
+    let name = path.as_ref().to_string_lossy().to_string();
+    if is_url(&name) {
         #[cfg(feature = "remote")]
-        if url_is_compressed(path.as_ref().to_str().unwrap()) {
-            let path = write_remote_to_file(path.as_ref().to_str().unwrap());
-            if path.ends_with(".zip") {
-                return open_zip(path).unwrap();
-            } else if path.ends_with(".lzma") || path.ends_with(".lzma2") || path.ends_with(".xz") {
-                return open_lzma(path);
-            } else {
-                // TODO: Instead of panic see if we can use compile error marco here
-                panic!("Remote feature not implemented cannot fetch remote files , enable it with feature=[\"remote\"] on \
-                your Cargo.toml");
-            }
-        } else {
-            return get_remote(path.as_ref().to_str().unwrap());
-        }
+        return get_remote(&name);
+        Err(io::Error::new(
+            ErrorKind::Other,
+            "Remote feature not implemented cannot fetch remote files , enable it with feature=[\"remote\"] on \
+                your Cargo.toml",
+        ))
+    } else if name.ends_with(".zip") {
+        open_zip(path)
+    } else if name.ends_with(".lzma") || name.ends_with(".lzma2") || name.ends_with(".xz") {
+        open_lzma(path)
+    } else {
+        read_file(path)
+    }
+}
+
+/// Open a file or url for reading line by line
+///
+/// Local files are read as they are parsed and remote files as they are downloaded, compressed local
+/// files are decompressed to memory first
+/// # Panics
+/// * If the file cannot be opened or the request fails
+#[allow(unreachable_code)]
+pub fn open_reader<P: AsRef<Path> + fmt::Debug + Clone>(path: P) -> Box<dyn BufRead> {
+    let path = to_local_path(path);
+    let name = path.to_str().unwrap();
+    if is_url(name) {
+        #[cfg(feature = "remote")]
+        return Box::new(BufReader::new(
+            remote_options()
+                .open(name)
+                .unwrap_or_else(|err| panic!("Could not fetch {}: {:?}", name, err)),
+        ));
         panic!("Remote feature not implemented cannot fetch remote files , enable it with feature=[\"remote\"] on \
                 your Cargo.toml");
-    } else if path.as_ref().to_str().unwrap().ends_with(".zip") {
-        open_zip(path).unwrap()
-    } else if path.as_ref().to_str().unwrap().ends_with(".lzma")
-        || path.as_ref().to_str().unwrap().ends_with(".lzma2")
-        || path.as_ref().to_str().unwrap().ends_with(".xz")
+    } else if [".zip", ".lzma", ".lzma2", ".xz"]
+        .iter()
+        .any(|f| name.ends_with(f))
     {
-        open_lzma(path)
+        Box::new(Cursor::new(read(path).into_bytes()))
     } else {
-        read_file(path)
+        let fd = File::open(name).unwrap_or_else(|e| panic!("Could not open {}: {:?}", name, e));
+        Box::new(BufReader::new(fd))
     }
 }
 
 ///Open a [`LZMA`](https://en.wikipedia.org/wiki/LZMA) compressed file
 /// # Arguments
 /// * `file`:The path to the compressed file
-/// # Panics
+/// # Errors
 /// - If the file doesn't exist and if the file contains characters not in UTF-8
 /// - If the underlying decompressor cannot decompress data
-fn open_lzma<P: AsRef<Path> + fmt::Debug + Clone>(file: P) -> String {
+fn open_lzma<P: AsRef<Path> + fmt::Debug + Clone>(file: P) -> io::Result<String> {
     let mut decompose: Vec<u8> = Vec::new();
-    let fd = File::open(file.as_ref())
-        .unwrap_or_else(|e| panic!("Could not open {:?},reason {:?}", file.clone(), e));
-    let mut fd = BufReader::new(fd);
+    let mut fd = BufReader::new(File::open(file.as_ref())?);
     // LZMA version 1
-    if file.as_ref().to_str().unwrap().ends_with(".lzma") {
-        lzma_decompress(&mut fd, &mut decompose).unwrap();
+    let decompressed = if file.as_ref().to_str().unwrap().ends_with(".lzma") {
+        lzma_decompress(&mut fd, &mut decompose)
     }
     // xz file
     else {
-        xz_decompress(&mut fd, &mut decompose).unwrap();
-    }
-    String::from_utf8(decompose).unwrap()
+        xz_decompress(&mut fd, &mut decompose)
+    };
+    decompressed.map_err(|err| io::Error::new(ErrorKind::InvalidData, format!("{:?}", err)))?;
+    String::from_utf8(decompose).map_err(|err| io::Error::new(ErrorKind::InvalidData, err))
 }
 /// Open a zip file
 /// # Arguments
 /// * `file`:The path to the compressed file
-/// # Panics
+/// # Errors
 /// - If the file does not exist
 /// - If the zip reader cannot be initialized
 /// - If there are multiple files in the zip archive
 /// - If there is a directory in the zip archive
 /// - If resulting data in the archive cannot be read to string
-fn open_zip<P: AsRef<Path> + fmt::Debug + Clone>(file: P) -> Result<String, IOError> {
-    let buf = File::open(file.as_ref()).unwrap();
+fn open_zip<P: AsRef<Path> + fmt::Debug + Clone>(file: P) -> io::Result<String> {
+    let buf = File::open(file.as_ref())?;
     let mut temp = String::new();
     let fd = BufReader::new(buf);
-    let mut zip = ZipArchive::new(fd).unwrap();
+    let mut zip = ZipArchive::new(fd)?;
     if zip.len() != 1 {
-        return Err(IOError::ZIPError(zip.len()));
+        return Err(IOError::ZIPError(zip.len()).into());
     }
-    let mut only_file = zip.by_index(0).unwrap();
+    let mut only_file = zip.by_index(0)?;
     if only_file.is_dir() {
-        return Err(IOError::DirectoryError);
+        return Err(IOError::DirectoryError.into());
     }
-    only_file.read_to_string(&mut temp).unwrap();
+    only_file.read_to_string(&mut temp)?;
     Ok(temp)
 }
-/// Read a remote filename into a temporary directory and return a string pointing to the path
+/// Read a remote filename into a temporary directory and return the downloaded file
 ///
-/// This defers from [`read`] as it returns a path to the file and not the file itself
+/// This defers from [`read`] as it returns a path to the file and not the file itself,
+/// the temporary file is removed when the returned [`Download`] is dropped
 ///
 /// This is used in modules like [`excel`](/dami/io/excel/index.html) if the file is a remote url
+///
+/// [`Download`]: ../remote/struct.Download.html
 #[cfg(feature = "remote")]
-pub fn read_remote(url: &str) -> String {
+pub fn read_remote(url: &str) -> Download {
     write_remote_to_file(url)
 }
-/// Write contents in a remote server to a file in the temporary directory
+/// Download a remote file to a new file in the temporary directory (or the cache)
 /// # Arguments:
 /// - `url`: Url to fetch data from
 /// # Returns
-///  A string containing the path to the  temporary file
+///  The downloaded file
 ///  # Panics
-///  - If the file cannot be created/opened
-///  - If there is a problem with writing to the temporary file
+///  - If the file cannot be created
 /// -  If the transfer fails for some reason
 #[cfg(feature = "remote")]
-fn write_remote_to_file(url: &str) -> String {
-    let mut temp_dir = temp_dir();
-    let mut vec_ = Vec::new();
-    let x = Url::from_str(url).unwrap();
-    let extension: Vec<&str> = x.path().split('.').collect();
-    temp_dir.push(format!(
-        "dami_temp.{}",
-        extension.get(extension.len()).unwrap_or(&"zip")
-    ));
-    let fd = OpenOptions::new()
-        .create(true)
-        .write(true)
-        .open(temp_dir.clone())
-        .unwrap_or_else(|_| panic!("Could not create {:?}", temp_dir));
-    let mut fd = BufWriter::new(fd);
-    let req = ureq::get(url).call();
-    let mut reader = req.into_reader();
-    reader
-        .read_to_end(&mut vec_)
-        .expect("Could not read to the buffer\n");
-    fd.write_all(&vec_).unwrap();
-    temp_dir.to_str().unwrap().to_string()
+fn write_remote_to_file(url: &str) -> Download {
+    remote_options()
+        .download(url)
+        .unwrap_or_else(|err| panic!("Could not fetch {}: {:?}", url, err))
 }
 
 pub fn is_compressed(filename: &str) -> bool {
@@ -220,21 +235,12 @@ pub fn is_compressed(filename: &str) -> bool {
     }
     true
 }
-/// Check if the file is compressed
-#[cfg(feature = "remote")]
-fn url_is_compressed(file_name: &str) -> bool {
-    let x = Url::from_str(file_name).unwrap();
-    let path = x.path();
-    if path.ends_with(".csv") || path.ends_with(".json") || path.ends_with(".html") {
-        return false;
-    }
-    true
-}
-/// Check if the string is a url
+/// Check if the string is a url of a remote file
+///
+/// `file://` urls point to local files and are not remote, see [`to_local_path`](fn.to_local_path.html)
 pub fn is_url(path: &str) -> bool {
     if path.starts_with("http://")
         || path.starts_with("https://")
-        || path.starts_with("file://")
         || path.starts_with("ftp://")
         || path.starts_with("ftps://")
     {
@@ -242,3 +248,65 @@ pub fn is_url(path: &str) -> bool {
     }
     false
 }
+/// Turn a `file://` url into a local path, other paths are returned unchanged
+///
+/// Percent-encoded characters are decoded and the host must be empty or `localhost`, so
+/// `file:///home/user/my%20data.csv` becomes `/home/user/my data.csv`. On Windows
+/// `file:///C:/data.csv` becomes `C:/data.csv` and urls with another host become UNC paths
+pub fn to_local_path<P: AsRef<Path>>(path: P) -> PathBuf {
+    let is_file_url = |text: &str| {
+        text.get(..7)
+            .map_or(false, |f| f.eq_ignore_ascii_case("file://"))
+    };
+    let text = match path.as_ref().to_str() {
+        Some(text) if is_file_url(text) => text,
+        _ => return path.as_ref().to_path_buf(),
+    };
+    let mut rest = &text[7..];
+    // The query and fragment are not part of the path
+    if let Some(end) = rest.find(|c| c == '?' || c == '#') {
+        rest = &rest[..end];
+    }
+    if rest
+        .get(..9)
+        .map_or(false, |f| f.eq_ignore_ascii_case("localhost"))
+    {
+        rest = &rest[9..];
+    }
+    let decoded = percent_decode(rest);
+    if !decoded.starts_with('/') {
+        // A url with a host, file://server/share/data.csv
+        return if cfg!(windows) {
+            PathBuf::from(format!("\\\\{}", decoded.replace('/', "\\")))
+        } else {
+            PathBuf::from(format!("//{}", decoded))
+        };
+    }
+    let bytes = decoded.as_bytes();
+    if cfg!(windows) && bytes.len() >= 3 && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+        // file:///C:/data.csv
+        return PathBuf::from(&decoded[1..]);
+    }
+    PathBuf::from(decoded)
+}
+/// Decode `%XX` sequences, invalid sequences are kept as they are
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3])
+                .ok()
+                .and_then(|f| u8::from_str_radix(f, 16).ok());
+            if let Some(byte) = hex {
+                decoded.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        decoded.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
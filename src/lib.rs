@@ -12,7 +12,7 @@ pub mod core;
 pub mod enums;
 pub mod io;
 mod marcos;
-mod plots;
+pub mod plots;
 pub mod prelude;
 #[macro_use]
 extern crate lazy_static;
@@ -1,2 +1,24 @@
-#![cfg(feature="stats")]
-pub mod series;
+#![cfg(feature = "stats")]
+//! Plotting of Series and DataFrames
+//!
+//! `DataFrame::to_plot` and `Series::to_plot` build a [`Plot`] following [`PlotOptions`], like the
+//! [`PlotKind`], the plotted columns, subplots, titles, axis labels and log scales. The plot can be
+//! customized further and then opened in a browser, shown in an evcxr notebook or saved as a
+//! self-contained HTML page backed by [plotly.js](https://plot.ly/javascript/) or as an SVG image
+//! drawn natively, so plots can be made on machines without a browser.
+//!
+//! Besides lines, bars, markers, histograms and box plots there are stacked areas, pies, kernel
+//! density estimates, hexbins, heatmaps and scatter matrices. `DataFrame::corr_plot` draws the
+//! correlation matrix of a DataFrame as a heatmap.
+//!
+//! [`Plot`]: struct.Plot.html
+//! [`PlotKind`]: enum.PlotKind.html
+//! [`PlotOptions`]: struct.PlotOptions.html
+mod dataframe;
+mod options;
+mod plot;
+mod series;
+mod svg;
+
+pub use options::{PlotKind, PlotOptions};
+pub use plot::{Plot, PlotErrors, Trace, Values};
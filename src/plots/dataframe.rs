@@ -1,235 +1,181 @@
 use crate::enums::DataTypes;
+use crate::plots::options::{PlotKind, PlotOptions};
+use crate::plots::plot::{Plot, PlotErrors, Trace, Values};
 use crate::prelude::DataFrame;
-use plotly::common::Mode;
-use plotly::{BoxPlot, Plot};
-use std::env::temp_dir;
-use std::fs::File;
-use std::io::{BufReader, Read};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::path::Path;
 
 impl DataFrame {
-    /// Requires feature
-    /// * `stats`
+    /// # Requires Feature
+    ///  > * `stats`
     ///
-    /// Plot the series in the DataFrame
+    /// Build a [`Plot`] of the DataFrame following `options`, the plot can be customized further
+    /// before it is shown or saved
     ///
-    /// The types that will be plotted include f64,f32,i128,i64 and i32 type Series
+    /// Each plotted column becomes a trace named after it, by default every f64,f32,i64 and i32
+    /// column is plotted against the index. Datetime x columns and indexes whose labels are all
+    /// dates are drawn on a date axis. See [`PlotOptions`] for selecting the x and y columns,
+    /// subplots, a secondary y axis, titles, labels, log scales, colors and sizes
     ///
-    /// # Arguments
-    /// * `kind`: The type of plot to draw.
-    /// > Supported plots are
-    /// > > * "hist"-> histogram
+    /// Heatmaps and scatter matrices draw all the plotted columns in a single trace and a grid of
+    /// traces respectively
     ///
-    /// > > * "h_hist" -> horizontal histogram
+    /// # Errors
+    /// * `KeyError`: If the x column, a y column or the group by column doesn't exist
+    /// * `DtypeError`: If a y column isn't numeric
     ///
-    /// > > * "bar"->bar graph,
+    /// [`Plot`]: ../plots/struct.Plot.html
+    /// [`PlotOptions`]: ../plots/struct.PlotOptions.html
+    /// # Example
+    /// ```
+    /// use dami::plots::{PlotKind, PlotOptions};
+    /// use dami::prelude::*;
+    /// let df = DataFrame::from(ndarray::arr2(&[[1.0, 20.0], [3.0, 10.0]]));
+    /// let mut options = PlotOptions::new(PlotKind::Bar);
+    /// options.set_subplots(true).set_log_y(true);
+    /// let mut plot = df.to_plot(&options).unwrap();
+    /// plot.set_title("Subplots");
+    /// assert_eq!(plot.traces().len(), 2);
+    /// ```
+    pub fn to_plot(&self, options: &PlotOptions) -> Result<Plot, PlotErrors> {
+        let columns = self.columns();
+        let dtypes = self.dtypes();
+        let x = match options.x() {
+            Some(name) if dtypes.get(name) == Some(&DataTypes::DATETIME) => Values::Dates(
+                self.get::<i64>(name)
+                    .ok_or_else(|| missing_or_dtype(&columns, name))?
+                    .into_iter()
+                    .collect(),
+            ),
+            Some(name) => match self.get_floats(name) {
+                Some(values) => Values::Numbers(values),
+                None => Values::Labels(
+                    self.get_strings(name)
+                        .ok_or_else(|| missing_or_dtype(&columns, name))?,
+                ),
+            },
+            None => Values::from_index(self.get_index()),
+        };
+        let groups = match options.group_by() {
+            Some(name) => Some(
+                self.get_strings(name)
+                    .ok_or_else(|| PlotErrors::KeyError(name.to_string()))?,
+            ),
+            None => None,
+        };
+        let y = match options.y() {
+            Some(y) => y.to_vec(),
+            None => columns
+                .iter()
+                .filter(|f| {
+                    Some(f.as_str()) != options.x()
+                        && Some(f.as_str()) != options.group_by()
+                        && is_numeric(&dtypes[*f])
+                })
+                .cloned()
+                .collect(),
+        };
+        if let Some(name) = options.secondary_y().iter().find(|f| !y.contains(*f)) {
+            return Err(PlotErrors::KeyError(name.clone()));
+        }
+        let values = y
+            .into_iter()
+            .map(|name| match self.get_floats(&name) {
+                Some(values) => Ok((name, values)),
+                None => Err(missing_or_dtype(&columns, &name)),
+            })
+            .collect::<Result<Vec<_>, PlotErrors>>()?;
+        Ok(options.build(&x, values, groups.as_deref()))
+    }
+    /// # Requires Feature
+    ///  > * `stats`
+    ///
+    /// Build a heatmap of the Pearson correlation coefficients between the numeric columns
+    /// returned by [`corr`](#method.corr), labeled with the names of the columns
     ///
-    /// > > * "marks"|"points" -> scatter graph
+    /// # Panics
+    /// * If there is no f64, f32 or i32 column, see [`corr`](#method.corr)
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// let df = DataFrame::from(ndarray::arr2(&[[1.0, 2.0], [2.0, 4.5], [3.0, 5.0]]));
+    /// let svg = df.corr_plot().to_svg();
+    /// assert!(svg.contains("<linearGradient"));
+    /// ```
+    pub fn corr_plot(&self) -> Plot {
+        let dtypes = self.dtypes();
+        // The columns kept by `corr`, in the same order
+        let names = self
+            .columns()
+            .into_iter()
+            .filter(|f| matches!(dtypes[f], DataTypes::F64 | DataTypes::F32 | DataTypes::I32))
+            .collect::<Vec<String>>();
+        let corr = self.corr();
+        let z = corr
+            .columns()
+            .iter()
+            .map(|f| corr.get_floats(f).unwrap_or_default())
+            .collect();
+        let mut plot = Plot::new();
+        plot.add_trace(Trace::heatmap("correlation", names.clone(), names, z))
+            .set_title("Correlation");
+        plot
+    }
+    /// # Requires Feature
+    ///  > * `stats`
     ///
-    /// > > * "line" -> line graph
+    /// Plot the numeric columns of the DataFrame
     ///
-    /// > > * "box" - > box graph,
+    /// Every numeric column is plotted, `kind` is one of the names listed in [`PlotKind`], use
+    /// [`to_plot`](#method.to_plot) for more options
     ///
-    /// If the string passed to `kind` argument doesn't match the above values. A line plot is drown
+    /// # Errors
+    /// * `UnknownKind`: If `kind` is not a known kind of plot
+    /// * `IOError`: If the plot cannot be saved or the browser cannot be opened
     ///
     /// # Note
     /// This is backed by [plotly.js] using the [plotly] crate, so the resulting graph is opened in
-    /// your default browser.
+    /// your default browser. Use [`plot_to_file`](#method.plot_to_file) on machines without a browser
     ///
     /// [plotly.js]: https://plot.ly/javascript/
     /// [plotly]: https://docs.rs/plotly
-    pub fn plot(&self, kind: &str) {
-        let mut plot = Plot::new();
-        match kind {
-            "bar" => self.plot_bar(&mut plot),
-            "line" => self.plot_line(&mut plot),
-            "marks" | "points" => self.plot_marks(&mut plot),
-            "h_hist" => self.plot_horizontal_hist(&mut plot),
-            "hist" => self.plot_hist(&mut plot),
-            "box" => self.plot_box(&mut plot),
-            _ => {
-                eprintln!("Method {} not known defaulting to line graph", kind);
-                self.plot_line(&mut plot)
-            }
-        }
-        plot.show()
-    }
-    /// Plot a bar plot
-    fn plot_bar(&self, plot: &mut Plot) {
-        for i in &self.get_order() {
-            let dtype = self.get_dtype_at_key(i).unwrap();
-            match dtype {
-                DataTypes::F64 => {
-                    let series = self.get_series::<f64>(i).unwrap();
-                    plot.add_trace(series.plot_bar(i.as_str()));
-                }
-                DataTypes::F32 => {
-                    let series = self.get_series::<f32>(i).unwrap();
-                    plot.add_trace(series.plot_bar(i.as_str()));
-                }
-                DataTypes::I64 => {
-                    let series = self.get_series::<i64>(i).unwrap();
-                    plot.add_trace(series.plot_bar(i.as_str()));
-                }
-                DataTypes::I128 => {
-                    let series = self.get_series::<i128>(i).unwrap();
-                    plot.add_trace(series.plot_bar(i.as_str()));
-                }
-                DataTypes::I32 => {
-                    let series = self.get_series::<i32>(i).unwrap();
-                    plot.add_trace(series.plot_bar(i.as_str()));
-                }
-
-                _ => continue,
-            }
-        }
-    }
-    /// Plot a line plot
-    fn plot_line(&self, plot: &mut Plot) {
-        for i in &self.get_order() {
-            let dtype = self.get_dtype_at_key(i).unwrap();
-            match dtype {
-                DataTypes::F64 => {
-                    let series = self.get_series::<f64>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Lines, i.as_str()));
-                }
-                DataTypes::F32 => {
-                    let series = self.get_series::<f32>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Lines, i.as_str()));
-                }
-                DataTypes::I64 => {
-                    let series = self.get_series::<i64>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Lines, i.as_str()));
-                }
-                DataTypes::I128 => {
-                    let series = self.get_series::<i128>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Lines, i.as_str()));
-                }
-                DataTypes::I32 => {
-                    let series = self.get_series::<i32>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Lines, i.as_str()));
-                }
-
-                _ => continue,
-            }
-        }
-    }
-    /// Plot a scatter plot
-    fn plot_marks(&self, plot: &mut Plot) {
-        for i in &self.get_order() {
-            let dtype = self.get_dtype_at_key(i).unwrap();
-            match dtype {
-                DataTypes::F64 => {
-                    let series = self.get_series::<f64>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Markers, i.as_str()));
-                }
-                DataTypes::F32 => {
-                    let series = self.get_series::<f32>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Markers, i.as_str()));
-                }
-                DataTypes::I64 => {
-                    let series = self.get_series::<i64>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Markers, i.as_str()));
-                }
-                DataTypes::I128 => {
-                    let series = self.get_series::<i128>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Markers, i.as_str()));
-                }
-                DataTypes::I32 => {
-                    let series = self.get_series::<i32>(i).unwrap();
-                    plot.add_trace(series.plot_line(Mode::Markers, i.as_str()));
-                }
-
-                _ => continue,
-            }
-        }
+    /// [`PlotKind`]: ../plots/enum.PlotKind.html
+    pub fn plot(&self, kind: &str) -> Result<(), PlotErrors> {
+        self.to_plot(&PlotOptions::new(kind.parse::<PlotKind>()?))?
+            .show()
     }
-    fn plot_hist(&self, plot: &mut Plot) {
-        for i in &self.get_order() {
-            let dtype = self.get_dtype_at_key(i).unwrap();
-            match dtype {
-                DataTypes::F64 => {
-                    let series = self.get_series::<f64>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-                DataTypes::F32 => {
-                    let series = self.get_series::<f32>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-                DataTypes::I64 => {
-                    let series = self.get_series::<i64>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-                DataTypes::I128 => {
-                    let series = self.get_series::<i128>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-                DataTypes::I32 => {
-                    let series = self.get_series::<i32>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-
-                _ => continue,
-            }
-        }
-    }
-    fn plot_horizontal_hist(&self, plot: &mut Plot) {
-        for i in &self.get_order() {
-            let dtype = self.get_dtype_at_key(i).unwrap();
-            match dtype {
-                DataTypes::F64 => {
-                    let series = self.get_series::<f64>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-                DataTypes::F32 => {
-                    let series = self.get_series::<f32>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-                DataTypes::I64 => {
-                    let series = self.get_series::<i64>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-                DataTypes::I128 => {
-                    let series = self.get_series::<i128>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-                DataTypes::I32 => {
-                    let series = self.get_series::<i32>(i).unwrap();
-                    plot.add_trace(series.plot_histogram(i.as_str()));
-                }
-
-                _ => continue,
-            }
-        }
-    }
-    fn plot_box(&self, plot: &mut Plot) {
-        for i in &self.get_order() {
-            let dtype = self.get_dtype_at_key(i).unwrap();
-            match dtype {
-                DataTypes::F64 => {
-                    let series = self.get_series::<f64>(i).unwrap();
-                    let box_plot = BoxPlot::new(series.to_vec()).name(&series.get_name());
-                    plot.add_trace(box_plot);
-                }
-                DataTypes::F32 => {
-                    let series = self.get_series::<f32>(i).unwrap().as_type::<f64>();
-                    let box_plot = BoxPlot::new(series.to_vec()).name(&series.get_name());
-                    plot.add_trace(box_plot);
-                }
-                DataTypes::I32 => {
-                    let series = self.get_series::<i32>(i).unwrap().as_type::<f64>();
-                    let box_plot = BoxPlot::new(series.to_vec()).name(&series.get_name());
-                    plot.add_trace(box_plot);
-                }
-                _ => continue,
-            }
-        }
+    /// # Requires Feature
+    ///  > * `stats`
+    ///
+    /// Save a plot of the numeric columns to `path` and return the saved contents
+    ///
+    /// Paths ending with `.svg` are saved as an SVG image drawn without a browser, other paths as
+    /// an HTML page with plotly.js inlined so it can be opened offline. Kinds supported are the
+    /// same as the [`plot`](#method.plot) method, use [`to_plot`](#method.to_plot) and
+    /// `Plot::write` for more options
+    /// # Errors
+    /// * `UnknownKind`: If `kind` is not a known kind of plot
+    /// * `IOError`: If the file cannot be written
+    /// # Example
+    /// ```
+    /// use dami::prelude::*;
+    /// let df = DataFrame::from(ndarray::arr2(&[[1.0, 2.0], [3.0, 1.0]]));
+    /// let path = std::env::temp_dir().join("dami_plot_doc.svg");
+    /// let svg = df.plot_to_file("line", &path).unwrap();
+    /// assert!(svg.contains("<polyline"));
+    /// ```
+    pub fn plot_to_file<P: AsRef<Path>>(&self, kind: &str, path: P) -> Result<String, PlotErrors> {
+        self.to_plot(&PlotOptions::new(kind.parse::<PlotKind>()?))?
+            .write(path)
     }
     /// Plot a graph into a jupyter notebook using rust repl environment which can be downloaded and installed
     /// from  [here](https://github.com/google/evcxr)
+    ///
+    /// Plots supported are the same as `plot()` method
+    ///
     /// # READ THIS!
     /// You should have already `set up a conda environment` this [website](https://shahinrostami.com/posts/programming/rust-notebooks/setup-anaconda-jupyter-and-rust/)
     /// has some nice instructions on how to do this.
+    ///
     /// > This embeds a whole HTML file ( about 10kb) to the notebook. And this grows linearly for every graph rendered.
     ///
     /// To make it work run
@@ -241,36 +187,27 @@ impl DataFrame {
     /// This is a large library and pulls in a lot of dependencies. It takes long to compile
     /// so it is recommended that you use your jupyter environment configured to use sccache.
     /// Instructions for setting that up can be found [here](https://github.com/google/evcxr/tree/master/evcxr_jupyter)
-    pub fn plot_evcxr(&self, kind: &str) {
-        let mut plot = Plot::new();
-        let mut temp_dir = temp_dir();
-        let time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        temp_dir.push(format!("dami{}.html", time));
-        match kind {
-            "bar" => self.plot_bar(&mut plot),
-            "line" => self.plot_line(&mut plot),
-            "scatter" => self.plot_marks(&mut plot),
-            "h_hist" => self.plot_horizontal_hist(&mut plot),
-            "hist" => self.plot_hist(&mut plot),
-            "box" => self.plot_box(&mut plot),
-            _ => {
-                eprintln!("Method {} not known defaulting to line graph", kind);
-                self.plot_line(&mut plot)
-            }
-        }
-        plot.to_html(temp_dir.clone());
-        let mut fs = BufReader::new(File::open(temp_dir).unwrap());
-        let mut contents = String::new();
-        fs.read_to_string(&mut contents).unwrap();
-        println!(
-            "EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT",
-            format!(
-                "<div>{}</div>",
-                &contents.replace("plotly-html-element", &format!("dami_{}", time))
-            )
-        )
+    ///
+    /// # Errors
+    /// * `UnknownKind`: If `kind` is not a known kind of plot
+    /// * `IOError`: If the plot cannot be rendered
+    pub fn plot_evcxr(&self, kind: &str) -> Result<(), PlotErrors> {
+        self.to_plot(&PlotOptions::new(kind.parse::<PlotKind>()?))?
+            .evcxr_display()
+    }
+}
+/// Whether columns of `dtype` are plotted by default
+fn is_numeric(dtype: &DataTypes) -> bool {
+    matches!(
+        dtype,
+        DataTypes::I32 | DataTypes::I64 | DataTypes::F32 | DataTypes::F64
+    )
+}
+/// The error for column `name` which couldn't be plotted
+fn missing_or_dtype(columns: &[String], name: &str) -> PlotErrors {
+    if columns.iter().any(|f| f == name) {
+        PlotErrors::DtypeError(name.to_string())
+    } else {
+        PlotErrors::KeyError(name.to_string())
     }
 }
@@ -0,0 +1,433 @@
+//! The kinds of plots and the options used to build them
+use crate::plots::plot::{Plot, PlotErrors, Trace, Values};
+use std::fmt;
+use std::str::FromStr;
+
+/// The kind of plot drawn for each column
+///
+/// Kinds can be parsed from the names used by `DataFrame::plot` and `Series::plot`
+/// # Example
+/// ```
+/// use dami::plots::PlotKind;
+/// assert_eq!("h_hist".parse::<PlotKind>().unwrap(), PlotKind::HorizontalHist);
+/// assert!("pies".parse::<PlotKind>().is_err());
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlotKind {
+    /// A line joining the values, `"line"`
+    Line,
+    /// A bar for each value, `"bar"`
+    Bar,
+    /// A marker for each value, `"scatter"`, `"points"` or `"marks"`
+    Scatter,
+    /// A histogram with vertical bars, `"hist"`
+    Hist,
+    /// A histogram with horizontal bars, `"h_hist"`
+    HorizontalHist,
+    /// A box plot, `"box"`
+    Box,
+    /// Areas stacked on top of each other, `"area"`
+    Area,
+    /// A pie for each column with a slice for each label on the x axis, `"pie"`
+    Pie,
+    /// A kernel density estimate, `"kde"` or `"density"`
+    Kde,
+    /// Hexagonal bins of the points of each column, `"hexbin"`
+    Hexbin,
+    /// The columns as the columns of a heatmap with a row for each label on the x axis,
+    /// `"heatmap"`
+    Heatmap,
+    /// A grid of scatter plots of each pair of columns with histograms of the columns on the
+    /// diagonal, `"scatter_matrix"`
+    ScatterMatrix,
+}
+impl Default for PlotKind {
+    fn default() -> Self {
+        PlotKind::Line
+    }
+}
+impl FromStr for PlotKind {
+    type Err = PlotErrors;
+
+    fn from_str(kind: &str) -> Result<Self, Self::Err> {
+        match kind {
+            "line" => Ok(PlotKind::Line),
+            "bar" => Ok(PlotKind::Bar),
+            "scatter" | "points" | "marks" => Ok(PlotKind::Scatter),
+            "hist" => Ok(PlotKind::Hist),
+            "h_hist" => Ok(PlotKind::HorizontalHist),
+            "box" => Ok(PlotKind::Box),
+            "area" => Ok(PlotKind::Area),
+            "pie" => Ok(PlotKind::Pie),
+            "kde" | "density" => Ok(PlotKind::Kde),
+            "hexbin" => Ok(PlotKind::Hexbin),
+            "heatmap" => Ok(PlotKind::Heatmap),
+            "scatter_matrix" => Ok(PlotKind::ScatterMatrix),
+            _ => Err(PlotErrors::UnknownKind(kind.to_string())),
+        }
+    }
+}
+impl fmt::Display for PlotKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            PlotKind::Line => "line",
+            PlotKind::Bar => "bar",
+            PlotKind::Scatter => "scatter",
+            PlotKind::Hist => "hist",
+            PlotKind::HorizontalHist => "h_hist",
+            PlotKind::Box => "box",
+            PlotKind::Area => "area",
+            PlotKind::Pie => "pie",
+            PlotKind::Kde => "kde",
+            PlotKind::Hexbin => "hexbin",
+            PlotKind::Heatmap => "heatmap",
+            PlotKind::ScatterMatrix => "scatter_matrix",
+        };
+        write!(f, "{}", name)
+    }
+}
+impl PlotKind {
+    /// The trace drawn for `values` at positions `x`
+    ///
+    /// Heatmaps and scatter matrices of more than one column are built by
+    /// [`PlotOptions::build`](struct.PlotOptions.html#method.build)
+    pub(crate) fn trace(self, name: &str, x: Values, values: Vec<f64>) -> Trace {
+        match self {
+            PlotKind::Line => Trace::line(name, x, values),
+            PlotKind::Bar => Trace::bar(name, x, values),
+            PlotKind::Scatter => Trace::scatter(name, x, values),
+            PlotKind::Hist | PlotKind::ScatterMatrix => Trace::histogram(name, values),
+            PlotKind::HorizontalHist => Trace::horizontal_histogram(name, values),
+            PlotKind::Box => Trace::box_plot(name, values),
+            PlotKind::Area => Trace::area(name, x, values),
+            PlotKind::Pie => Trace::pie(name, x, values),
+            PlotKind::Kde => Trace::kde(name, values),
+            PlotKind::Hexbin => Trace::hexbin(name, x.numbers(), values),
+            PlotKind::Heatmap => Trace::heatmap(
+                name,
+                vec![name.to_string()],
+                x.labels(),
+                values.iter().map(|f| vec![*f]).collect(),
+            ),
+        }
+    }
+}
+
+/// Options used to build a plot of a DataFrame or a Series
+///
+/// The defaults draw a line for each numeric column against the index on a plot of 800x500
+/// pixels, the legend is shown when there is more than one line
+/// # Example
+/// ```
+/// use dami::plots::{PlotKind, PlotOptions};
+/// use dami::prelude::*;
+/// use std::collections::HashMap;
+/// use std::convert::TryFrom;
+///
+/// let values: HashMap<&str, Vec<f64>> = [
+///     ("year", vec![2018.0, 2019.0, 2020.0]),
+///     ("sales", vec![10.0, 30.0, 20.0]),
+///     ("margin", vec![0.1, 0.3, 0.2]),
+/// ]
+/// .iter()
+/// .cloned()
+/// .collect();
+/// let df = DataFrame::try_from(values).unwrap();
+/// let mut options = PlotOptions::new(PlotKind::Line);
+/// options
+///     .set_x("year")
+///     .set_y(&["sales", "margin"])
+///     .set_secondary_y(&["margin"])
+///     .set_title("Sales")
+///     .set_y_label("Units")
+///     .set_colors(&["black", "red"]);
+/// let plot = df.to_plot(&options).unwrap();
+/// assert_eq!(plot.traces().len(), 2);
+/// assert!(plot.to_svg().contains("Units"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlotOptions {
+    kind: PlotKind,
+    x: Option<String>,
+    y: Option<Vec<String>>,
+    secondary_y: Vec<String>,
+    subplots: bool,
+    title: Option<String>,
+    x_label: Option<String>,
+    y_label: Option<String>,
+    log_x: bool,
+    log_y: bool,
+    colors: Vec<String>,
+    legend: Option<bool>,
+    size: (usize, usize),
+    group_by: Option<String>,
+}
+impl Default for PlotOptions {
+    fn default() -> Self {
+        PlotOptions::new(PlotKind::default())
+    }
+}
+impl From<PlotKind> for PlotOptions {
+    fn from(kind: PlotKind) -> Self {
+        PlotOptions::new(kind)
+    }
+}
+impl PlotOptions {
+    /// Create the default options for plots of `kind`
+    pub fn new(kind: PlotKind) -> PlotOptions {
+        PlotOptions {
+            kind,
+            x: None,
+            y: None,
+            secondary_y: vec![],
+            subplots: false,
+            title: None,
+            x_label: None,
+            y_label: None,
+            log_x: false,
+            log_y: false,
+            colors: vec![],
+            legend: None,
+            size: (800, 500),
+            group_by: None,
+        }
+    }
+    /// The kind of plot drawn
+    pub const fn kind(&self) -> PlotKind {
+        self.kind
+    }
+    /// Set the kind of plot drawn
+    pub fn set_kind(&mut self, kind: PlotKind) -> &mut Self {
+        self.kind = kind;
+        self
+    }
+    /// The column used for the x axis, `None` uses the index
+    pub fn x(&self) -> Option<&str> {
+        self.x.as_deref()
+    }
+    /// Plot the columns against column `x` instead of the index
+    ///
+    /// Numeric columns give a linear axis and other columns are used as labels
+    pub fn set_x(&mut self, x: &str) -> &mut Self {
+        self.x = Some(x.to_string());
+        self
+    }
+    /// The columns plotted, `None` plots every numeric column but `x`
+    pub fn y(&self) -> Option<&[String]> {
+        self.y.as_deref()
+    }
+    /// Set the columns plotted, in order
+    pub fn set_y(&mut self, y: &[&str]) -> &mut Self {
+        self.y = Some(y.iter().map(ToString::to_string).collect());
+        self
+    }
+    /// The columns drawn against a second y axis on the right
+    pub fn secondary_y(&self) -> &[String] {
+        &self.secondary_y
+    }
+    /// Draw `columns` against a second y axis on the right, they must also be plotted
+    ///
+    /// The second axis is not used with [`subplots`](#method.set_subplots)
+    pub fn set_secondary_y(&mut self, columns: &[&str]) -> &mut Self {
+        self.secondary_y = columns.iter().map(ToString::to_string).collect();
+        self
+    }
+    /// The column whose values group the rows of the plotted columns
+    pub fn group_by(&self) -> Option<&str> {
+        self.group_by.as_deref()
+    }
+    /// Split each plotted column into a trace for each value of column `group_by`, like the
+    /// categories of a string column, so each group gets its own color and legend entry
+    ///
+    /// Groups are ordered by their first row. Heatmaps and scatter matrices are not grouped and
+    /// series have no columns to group by
+    /// # Example
+    /// ```
+    /// use dami::plots::{PlotKind, PlotOptions};
+    /// use dami::prelude::*;
+    /// let mut sales = Series::from(vec![3.0, 5.0, 4.0, 6.0]);
+    /// sales.set_name("sales");
+    /// let mut region = Series::from(vec!["east", "west", "east", "west"]);
+    /// region.set_name("region");
+    /// let mut df = DataFrame::new();
+    /// df.add_series(sales, true).unwrap();
+    /// df.add_series(region, true).unwrap();
+    /// let mut options = PlotOptions::new(PlotKind::Line);
+    /// options.set_group_by("region");
+    /// let plot = df.to_plot(&options).unwrap();
+    /// let names = plot.traces().iter().map(|f| f.name()).collect::<Vec<&str>>();
+    /// assert_eq!(names, vec!["east", "west"]);
+    /// ```
+    pub fn set_group_by(&mut self, group_by: &str) -> &mut Self {
+        self.group_by = Some(group_by.to_string());
+        self
+    }
+    /// Whether each column is drawn on its own plot
+    pub const fn subplots(&self) -> bool {
+        self.subplots
+    }
+    /// Set whether each column is drawn on its own plot, the plots are stacked vertically
+    pub fn set_subplots(&mut self, subplots: bool) -> &mut Self {
+        self.subplots = subplots;
+        self
+    }
+    /// Set the title shown above the plot
+    pub fn set_title(&mut self, title: &str) -> &mut Self {
+        self.title = Some(title.to_string());
+        self
+    }
+    /// Set the label of the x axis
+    pub fn set_x_label(&mut self, label: &str) -> &mut Self {
+        self.x_label = Some(label.to_string());
+        self
+    }
+    /// Set the label of the y axis
+    pub fn set_y_label(&mut self, label: &str) -> &mut Self {
+        self.y_label = Some(label.to_string());
+        self
+    }
+    /// Set whether the x axis uses a log scale, it is ignored when the x axis holds labels
+    pub fn set_log_x(&mut self, log: bool) -> &mut Self {
+        self.log_x = log;
+        self
+    }
+    /// Set whether the y axis uses a log scale
+    pub fn set_log_y(&mut self, log: bool) -> &mut Self {
+        self.log_y = log;
+        self
+    }
+    /// The colors given to the plotted columns in turn
+    pub fn colors(&self) -> &[String] {
+        &self.colors
+    }
+    /// Set the colors given to the plotted columns in turn, as CSS colors like `"red"` or
+    /// `"#1f77b4"`. They are reused when there are more columns than colors
+    pub fn set_colors(&mut self, colors: &[&str]) -> &mut Self {
+        self.colors = colors.iter().map(ToString::to_string).collect();
+        self
+    }
+    /// Set whether the legend is shown
+    pub fn set_legend(&mut self, show: bool) -> &mut Self {
+        self.legend = Some(show);
+        self
+    }
+    /// Set the size of the plot in pixels
+    pub fn set_size(&mut self, width: usize, height: usize) -> &mut Self {
+        self.size = (width, height);
+        self
+    }
+    /// The color of the `i`th plotted column, if colors were set
+    pub(crate) fn color(&self, i: usize) -> Option<&str> {
+        if self.colors.is_empty() {
+            None
+        } else {
+            Some(&self.colors[i % self.colors.len()])
+        }
+    }
+    /// Build the plot of `columns`, pairs of names and values, at positions `x`
+    ///
+    /// Each column is split into a trace for each of the `groups` of its rows, if any
+    pub(crate) fn build(
+        &self,
+        x: &Values,
+        columns: Vec<(String, Vec<f64>)>,
+        groups: Option<&[String]>,
+    ) -> Plot {
+        let mut plot = Plot::new();
+        match self.kind {
+            PlotKind::Heatmap if columns.len() > 1 => {
+                let rows = x.labels();
+                let z = (0..rows.len())
+                    .map(|j| {
+                        columns
+                            .iter()
+                            .map(|(_, values)| values.get(j).copied().unwrap_or(f64::NAN))
+                            .collect()
+                    })
+                    .collect();
+                let names = columns.into_iter().map(|(name, _)| name).collect();
+                plot.add_trace(Trace::heatmap("", names, rows, z));
+                self.apply(&mut plot);
+            }
+            PlotKind::ScatterMatrix if columns.len() > 1 => {
+                for (row, y) in &columns {
+                    for (column, x) in &columns {
+                        let mut trace = if row == column {
+                            Trace::histogram(row, y.clone())
+                        } else {
+                            Trace::scatter(&format!("{} vs {}", row, column), x.clone(), y.clone())
+                        };
+                        if let Some(color) = self.color(0) {
+                            trace.set_color(color);
+                        }
+                        plot.add_trace(trace);
+                    }
+                }
+                self.apply(&mut plot);
+                plot.set_subplots(true)
+                    .set_subplot_columns(columns.len())
+                    .set_legend(false);
+            }
+            _ => {
+                let single = columns.len() == 1;
+                for (name, values) in columns {
+                    let secondary = self.secondary_y.contains(&name);
+                    let traces = match groups {
+                        Some(groups) => group_rows(groups)
+                            .into_iter()
+                            .map(|(group, rows)| {
+                                let label = if single {
+                                    group
+                                } else {
+                                    format!("{} ({})", name, group)
+                                };
+                                let values = rows.iter().map(|f| values[*f]).collect();
+                                self.kind.trace(&label, x.select(&rows), values)
+                            })
+                            .collect(),
+                        None => vec![self.kind.trace(&name, x.clone(), values)],
+                    };
+                    for mut trace in traces {
+                        if let Some(color) = self.color(plot.traces().len()) {
+                            trace.set_color(color);
+                        }
+                        trace.set_secondary_y(secondary);
+                        plot.add_trace(trace);
+                    }
+                }
+                self.apply(&mut plot);
+            }
+        }
+        plot
+    }
+    /// Apply the layout options to `plot`
+    pub(crate) fn apply(&self, plot: &mut Plot) {
+        plot.set_size(self.size.0, self.size.1)
+            .set_subplots(self.subplots)
+            .set_log_x(self.log_x)
+            .set_log_y(self.log_y);
+        if let Some(title) = &self.title {
+            plot.set_title(title);
+        }
+        if let Some(label) = &self.x_label {
+            plot.set_x_label(label);
+        }
+        if let Some(label) = &self.y_label {
+            plot.set_y_label(label);
+        }
+        if let Some(show) = self.legend {
+            plot.set_legend(show);
+        }
+    }
+}
+/// The positions of the rows of each group in `groups`, in the order the groups first appear
+fn group_rows(groups: &[String]) -> Vec<(String, Vec<usize>)> {
+    let mut rows: Vec<(String, Vec<usize>)> = vec![];
+    for (i, group) in groups.iter().enumerate() {
+        match rows.iter_mut().find(|(f, _)| f == group) {
+            Some((_, positions)) => positions.push(i),
+            None => rows.push((group.clone(), vec![i])),
+        }
+    }
+    rows
+}
@@ -0,0 +1,755 @@
+//! The [`Plot`] builder shared by Series and DataFrames
+//!
+//! A plot is a list of [`Trace`]s and layout settings, it is rendered to a self-contained HTML page
+//! using [plotly.js](https://plot.ly/javascript/) or to an SVG image drawn natively, so plots can
+//! be saved on machines without a browser.
+//!
+//! [`Plot`]: struct.Plot.html
+//! [`Trace`]: struct.Trace.html
+use crate::core::index::date_time::{format_timestamp, parse_timestamp, DateTimeIndex, NAT};
+use crate::plots::svg;
+use serde_json::{json, Value};
+use std::env::temp_dir;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The id plotly gives the `div` holding the plot
+const PLOTLY_DIV: &str = "plotly-html-element";
+
+/// Errors returned when building, rendering or saving plots
+pub enum PlotErrors {
+    /// The plot could not be written
+    IOError(std::io::Error),
+    /// The kind of plot is not known
+    UnknownKind(String),
+    /// The column doesn't exist
+    KeyError(String),
+    /// The column cannot be plotted
+    DtypeError(String),
+}
+impl fmt::Debug for PlotErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IOError(err) => write!(f, "{:?}", err),
+            Self::UnknownKind(kind) => write!(
+                f,
+                "Plot kind {} not known, expected one of line, bar, scatter, hist, h_hist, box, area, \
+                 pie, kde, hexbin, heatmap or scatter_matrix",
+                kind
+            ),
+            Self::KeyError(column) => write!(f, "Column {} not found in the DataFrame", column),
+            Self::DtypeError(column) => write!(f, "Column {} is not numeric", column),
+        }
+    }
+}
+impl From<std::io::Error> for PlotErrors {
+    fn from(err: std::io::Error) -> Self {
+        PlotErrors::IOError(err)
+    }
+}
+
+/// The values along the x axis of a [`Trace`](struct.Trace.html)
+#[derive(Clone, Debug, PartialEq)]
+pub enum Values {
+    /// Labels drawn as evenly spaced categories
+    Labels(Vec<String>),
+    /// Numbers drawn on a linear axis
+    Numbers(Vec<f64>),
+    /// Seconds elapsed since the unix epoch drawn on a date axis, [`NAT`] values are missing
+    ///
+    /// [`NAT`]: ../core/index/date_time/constant.NAT.html
+    Dates(Vec<i64>),
+}
+impl Values {
+    /// The values of an index, dates if every label is a date or time and labels otherwise
+    ///
+    /// # Example
+    /// ```
+    /// use dami::plots::Values;
+    /// let index = vec!["2020-01-01".to_string(), "2020-01-02 12:00".to_string()];
+    /// assert_eq!(Values::from_index(index), Values::Dates(vec![1577836800, 1577966400]));
+    /// ```
+    pub fn from_index(labels: Vec<String>) -> Values {
+        let dates = labels
+            .iter()
+            .map(|f| parse_timestamp(f))
+            .collect::<Vec<i64>>();
+        if dates.is_empty() || dates.contains(&NAT) {
+            Values::Labels(labels)
+        } else {
+            Values::Dates(dates)
+        }
+    }
+    /// The number of values
+    pub fn len(&self) -> usize {
+        match self {
+            Values::Labels(labels) => labels.len(),
+            Values::Numbers(numbers) => numbers.len(),
+            Values::Dates(dates) => dates.len(),
+        }
+    }
+    /// Whether there are no values
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// The values as category labels
+    pub(crate) fn labels(&self) -> Vec<String> {
+        match self {
+            Values::Labels(labels) => labels.clone(),
+            Values::Numbers(numbers) => numbers.iter().map(|f| svg::format_number(*f)).collect(),
+            Values::Dates(dates) => {
+                // Dates without a time of day are labeled with the date only
+                let midnight = dates.iter().all(|f| *f == NAT || f.rem_euclid(86400) == 0);
+                dates
+                    .iter()
+                    .map(|f| {
+                        let label = format_timestamp(*f);
+                        match label.strip_suffix(" 00:00:00") {
+                            Some(date) if midnight => date.to_string(),
+                            _ => label,
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+    /// The values as numbers, labels are replaced by their positions
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn numbers(&self) -> Vec<f64> {
+        match self {
+            Values::Labels(labels) => (0..labels.len()).map(|f| f as f64).collect(),
+            Values::Numbers(numbers) => numbers.clone(),
+            Values::Dates(dates) => dates
+                .iter()
+                .map(|f| if *f == NAT { f64::NAN } else { *f as f64 })
+                .collect(),
+        }
+    }
+    /// The values at positions `rows`
+    pub(crate) fn select(&self, rows: &[usize]) -> Values {
+        match self {
+            Values::Labels(labels) => {
+                Values::Labels(rows.iter().map(|f| labels[*f].clone()).collect())
+            }
+            Values::Numbers(numbers) => Values::Numbers(rows.iter().map(|f| numbers[*f]).collect()),
+            Values::Dates(dates) => Values::Dates(rows.iter().map(|f| dates[*f]).collect()),
+        }
+    }
+    fn to_json(&self) -> Value {
+        match self {
+            Values::Labels(labels) => json!(labels),
+            Values::Numbers(numbers) => json!(numbers),
+            Values::Dates(dates) => Value::Array(
+                dates
+                    .iter()
+                    .map(|f| {
+                        if *f == NAT {
+                            Value::Null
+                        } else {
+                            json!(format_timestamp(*f))
+                        }
+                    })
+                    .collect(),
+            ),
+        }
+    }
+}
+impl From<Vec<String>> for Values {
+    fn from(labels: Vec<String>) -> Self {
+        Values::Labels(labels)
+    }
+}
+impl From<Vec<&str>> for Values {
+    fn from(labels: Vec<&str>) -> Self {
+        Values::Labels(labels.iter().map(ToString::to_string).collect())
+    }
+}
+impl From<Vec<f64>> for Values {
+    fn from(numbers: Vec<f64>) -> Self {
+        Values::Numbers(numbers)
+    }
+}
+impl From<&DateTimeIndex> for Values {
+    fn from(index: &DateTimeIndex) -> Self {
+        Values::Dates((0..index.len()).map(|f| index[f]).collect())
+    }
+}
+
+/// How a trace is drawn
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TraceKind {
+    Line,
+    Scatter,
+    Bar,
+    Histogram,
+    HorizontalHistogram,
+    Box,
+    Area,
+    Pie,
+    Hexbin,
+    Heatmap,
+}
+
+/// A named series of values drawn on a [`Plot`](struct.Plot.html)
+///
+/// `NaN`s are missing values, lines are broken and no marker or bar is drawn where they appear
+#[derive(Clone, Debug, PartialEq)]
+pub struct Trace {
+    pub(crate) kind: TraceKind,
+    pub(crate) name: String,
+    pub(crate) x: Values,
+    pub(crate) y: Vec<f64>,
+    pub(crate) color: Option<String>,
+    pub(crate) secondary_y: bool,
+    /// The labels of the rows of a heatmap
+    pub(crate) rows: Vec<String>,
+    /// The values of the cells of a heatmap, row by row
+    pub(crate) z: Vec<Vec<f64>>,
+}
+impl Trace {
+    fn new(kind: TraceKind, name: &str, x: Values, y: Vec<f64>) -> Trace {
+        Trace {
+            kind,
+            name: name.to_string(),
+            x,
+            y,
+            color: None,
+            secondary_y: false,
+            rows: vec![],
+            z: vec![],
+        }
+    }
+    /// A line joining the points (`x`,`y`)
+    pub fn line<X: Into<Values>>(name: &str, x: X, y: Vec<f64>) -> Trace {
+        Trace::new(TraceKind::Line, name, x.into(), y)
+    }
+    /// A marker at each of the points (`x`,`y`)
+    pub fn scatter<X: Into<Values>>(name: &str, x: X, y: Vec<f64>) -> Trace {
+        Trace::new(TraceKind::Scatter, name, x.into(), y)
+    }
+    /// A bar of height `y` for each label in `x`, bars of traces sharing labels are grouped
+    pub fn bar<X: Into<Values>>(name: &str, x: X, y: Vec<f64>) -> Trace {
+        Trace::new(TraceKind::Bar, name, x.into(), y)
+    }
+    /// A histogram of `values` with vertical bars
+    pub fn histogram(name: &str, values: Vec<f64>) -> Trace {
+        Trace::new(TraceKind::Histogram, name, Values::Numbers(vec![]), values)
+    }
+    /// A histogram of `values` with horizontal bars
+    pub fn horizontal_histogram(name: &str, values: Vec<f64>) -> Trace {
+        Trace::new(
+            TraceKind::HorizontalHistogram,
+            name,
+            Values::Numbers(vec![]),
+            values,
+        )
+    }
+    /// A box showing the quartiles of `values`, whiskers extend to the furthest values within
+    /// 1.5 times the interquartile range and values past them are drawn as outliers
+    pub fn box_plot(name: &str, values: Vec<f64>) -> Trace {
+        Trace::new(TraceKind::Box, name, Values::Numbers(vec![]), values)
+    }
+    /// A line filled down to the area traces below it, area traces on a plot are stacked in the
+    /// order they are added
+    pub fn area<X: Into<Values>>(name: &str, x: X, y: Vec<f64>) -> Trace {
+        Trace::new(TraceKind::Area, name, x.into(), y)
+    }
+    /// A pie with a slice for each label sized by its share of the total of `values`
+    ///
+    /// Pies are not drawn on axes, pies on the same plot are drawn side by side
+    pub fn pie<X: Into<Values>>(name: &str, labels: X, values: Vec<f64>) -> Trace {
+        Trace::new(TraceKind::Pie, name, labels.into(), values)
+    }
+    /// A line of the density of `values` estimated with a gaussian kernel, the bandwidth follows
+    /// Scott's rule
+    pub fn kde(name: &str, values: Vec<f64>) -> Trace {
+        let (x, y) = density(values);
+        Trace::new(TraceKind::Line, name, Values::Numbers(x), y)
+    }
+    /// Hexagonal bins colored by the number of points (`x`,`y`) falling in them, a replacement
+    /// for scatter plots with too many points to tell apart
+    ///
+    /// plotly.js draws the bins as rectangles
+    pub fn hexbin(name: &str, x: Vec<f64>, y: Vec<f64>) -> Trace {
+        Trace::new(TraceKind::Hexbin, name, Values::Numbers(x), y)
+    }
+    /// A grid of cells colored by their value, `z[j][i]` is the value of column `x[i]` in row
+    /// `y[j]`. The first row is drawn at the top
+    pub fn heatmap(name: &str, x: Vec<String>, y: Vec<String>, z: Vec<Vec<f64>>) -> Trace {
+        let mut trace = Trace::new(TraceKind::Heatmap, name, Values::Labels(x), vec![]);
+        trace.rows = y;
+        trace.z = z;
+        trace
+    }
+    /// The name shown in the legend
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+    /// Set the color of the trace, a CSS color like `"red"` or `"#1f77b4"`
+    pub fn set_color(&mut self, color: &str) -> &mut Self {
+        self.color = Some(color.to_string());
+        self
+    }
+    /// Set whether the trace is drawn against a second y axis on the right of the plot
+    pub fn set_secondary_y(&mut self, secondary: bool) -> &mut Self {
+        self.secondary_y = secondary;
+        self
+    }
+    /// The trace in the format used by plotly.js
+    fn to_json(&self) -> Value {
+        let mut trace = match self.kind {
+            TraceKind::Line => json!({"type": "scatter", "mode": "lines"}),
+            TraceKind::Scatter => json!({"type": "scatter", "mode": "markers"}),
+            TraceKind::Bar => json!({"type": "bar"}),
+            TraceKind::Histogram => json!({"type": "histogram", "x": self.y}),
+            TraceKind::HorizontalHistogram => json!({"type": "histogram", "y": self.y}),
+            TraceKind::Box => json!({"type": "box", "y": self.y}),
+            TraceKind::Area => json!({"type": "scatter", "mode": "lines", "stackgroup": "area"}),
+            TraceKind::Pie => json!({"type": "pie", "labels": self.x.to_json(), "values": self.y}),
+            TraceKind::Hexbin => json!({
+                "type": "histogram2d",
+                "nbinsx": svg::HEXBIN_GRID,
+                "nbinsy": svg::HEXBIN_GRID,
+                "colorscale": "Viridis"
+            }),
+            TraceKind::Heatmap => json!({
+                "type": "heatmap",
+                "x": self.x.to_json(),
+                "y": self.rows,
+                "z": self.z,
+                "colorscale": "Viridis"
+            }),
+        };
+        if matches!(
+            self.kind,
+            TraceKind::Line
+                | TraceKind::Scatter
+                | TraceKind::Bar
+                | TraceKind::Area
+                | TraceKind::Hexbin
+        ) {
+            trace["x"] = self.x.to_json();
+            // serde_json writes NaN as null, which plotly.js treats as a gap
+            trace["y"] = json!(self.y);
+        }
+        trace["name"] = json!(self.name);
+        match &self.color {
+            // Slices and cells are colored by their values
+            Some(_) if self.is_colormapped() => {}
+            Some(color) => {
+                if matches!(
+                    self.kind,
+                    TraceKind::Line | TraceKind::Scatter | TraceKind::Area
+                ) {
+                    trace["line"] = json!({ "color": color });
+                }
+                trace["marker"] = json!({ "color": color });
+            }
+            None => {}
+        }
+        trace
+    }
+    /// Whether the trace is drawn in many colors, its own color is not used
+    pub(crate) fn is_colormapped(&self) -> bool {
+        matches!(
+            self.kind,
+            TraceKind::Pie | TraceKind::Hexbin | TraceKind::Heatmap
+        )
+    }
+}
+/// Points of a gaussian kernel density estimate of `values`, using Scott's rule for the bandwidth
+#[allow(clippy::cast_precision_loss)]
+fn density(values: Vec<f64>) -> (Vec<f64>, Vec<f64>) {
+    const POINTS: usize = 200;
+    let values = values
+        .into_iter()
+        .filter(|f| f.is_finite())
+        .collect::<Vec<f64>>();
+    if values.is_empty() {
+        return (vec![], vec![]);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    let deviation = if values.len() > 1 {
+        (values.iter().map(|f| (f - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+    } else {
+        0.0
+    };
+    let bandwidth = if deviation > 0.0 {
+        deviation * n.powf(-0.2)
+    } else {
+        1.0
+    };
+    let min = values.iter().copied().fold(f64::INFINITY, f64::min) - 3.0 * bandwidth;
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max) + 3.0 * bandwidth;
+    let step = (max - min) / (POINTS - 1) as f64;
+    let norm = n * bandwidth * (2.0 * std::f64::consts::PI).sqrt();
+    (0..POINTS)
+        .map(|i| {
+            let x = min + step * i as f64;
+            let sum = values
+                .iter()
+                .map(|f| (-0.5 * ((x - f) / bandwidth).powi(2)).exp())
+                .sum::<f64>();
+            (x, sum / norm)
+        })
+        .unzip()
+}
+
+/// A plot of one or more traces
+///
+/// Plots are built by `DataFrame::to_plot` and `Series::to_plot` or trace by trace, and can be
+/// customized further before they are shown or saved
+///
+/// # Example
+/// ```
+/// use dami::plots::{Plot, Trace};
+/// let mut plot = Plot::new();
+/// plot.set_title("Sales")
+///     .add_trace(Trace::bar("2020", vec!["Q1", "Q2"], vec![3.0, 5.0]));
+/// let svg = plot.to_svg();
+/// assert!(svg.starts_with("<svg"));
+/// assert!(svg.contains("Sales"));
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plot {
+    pub(crate) traces: Vec<Trace>,
+    pub(crate) title: Option<String>,
+    pub(crate) width: usize,
+    pub(crate) height: usize,
+    pub(crate) x_label: Option<String>,
+    pub(crate) y_label: Option<String>,
+    pub(crate) log_x: bool,
+    pub(crate) log_y: bool,
+    pub(crate) show_legend: Option<bool>,
+    pub(crate) subplots: bool,
+    pub(crate) subplot_columns: usize,
+}
+impl Default for Plot {
+    fn default() -> Self {
+        Plot {
+            traces: vec![],
+            title: None,
+            width: 800,
+            height: 500,
+            x_label: None,
+            y_label: None,
+            log_x: false,
+            log_y: false,
+            show_legend: None,
+            subplots: false,
+            subplot_columns: 1,
+        }
+    }
+}
+impl Plot {
+    /// Create an empty plot of 800x500 pixels
+    pub fn new() -> Plot {
+        Self::default()
+    }
+    /// Add `trace` on top of the traces already on the plot
+    pub fn add_trace(&mut self, trace: Trace) -> &mut Self {
+        self.traces.push(trace);
+        self
+    }
+    /// The traces drawn on the plot
+    pub fn traces(&self) -> &[Trace] {
+        &self.traces
+    }
+    /// Set the title shown above the plot
+    pub fn set_title(&mut self, title: &str) -> &mut Self {
+        self.title = Some(title.to_string());
+        self
+    }
+    /// Set the size of the plot in pixels
+    pub fn set_size(&mut self, width: usize, height: usize) -> &mut Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+    /// Set the label of the x axis
+    pub fn set_x_label(&mut self, label: &str) -> &mut Self {
+        self.x_label = Some(label.to_string());
+        self
+    }
+    /// Set the label of the y axis
+    pub fn set_y_label(&mut self, label: &str) -> &mut Self {
+        self.y_label = Some(label.to_string());
+        self
+    }
+    /// Set whether the x axis uses a log scale, it is ignored when the x axis holds labels
+    pub fn set_log_x(&mut self, log: bool) -> &mut Self {
+        self.log_x = log;
+        self
+    }
+    /// Set whether the y axis uses a log scale
+    pub fn set_log_y(&mut self, log: bool) -> &mut Self {
+        self.log_y = log;
+        self
+    }
+    /// Set whether the legend is shown, by default it is shown when there is more than one trace
+    pub fn set_legend(&mut self, show: bool) -> &mut Self {
+        self.show_legend = Some(show);
+        self
+    }
+    /// Set whether each trace is drawn on its own plot, the plots are stacked vertically
+    ///
+    /// Traces on subplots don't use the secondary y axis
+    pub fn set_subplots(&mut self, subplots: bool) -> &mut Self {
+        self.subplots = subplots;
+        self
+    }
+    /// Set the number of columns subplots are arranged in, subplots fill each row from the left
+    /// starting at the top. The default is one column
+    pub fn set_subplot_columns(&mut self, columns: usize) -> &mut Self {
+        self.subplot_columns = columns.max(1);
+        self
+    }
+    /// The number of rows and columns of the subplots
+    pub(crate) fn grid(&self) -> (usize, usize) {
+        let count = self.traces.len().max(1);
+        let columns = self.subplot_columns.min(count);
+        ((count + columns - 1) / columns, columns)
+    }
+    /// The part of the figure taken by the `i`th subplot, as `([x0, x1], [y0, y1])` fractions
+    #[allow(clippy::cast_precision_loss)]
+    fn domain(&self, i: usize) -> ([f64; 2], [f64; 2]) {
+        let (rows, columns) = self.grid();
+        let (x_gap, y_gap) = (0.08, 0.12);
+        let width = (1.0 - x_gap * (columns - 1) as f64) / columns as f64;
+        let height = (1.0 - y_gap * (rows - 1) as f64) / rows as f64;
+        let x0 = (width + x_gap) * (i % columns) as f64;
+        let y1 = 1.0 - (height + y_gap) * (i / columns) as f64;
+        ([x0, x0 + width], [(y1 - height).max(0.0), y1])
+    }
+    /// The plot as a plotly.js figure, a JSON object holding the `data` and `layout` of the plot
+    pub fn to_json(&self) -> String {
+        json!({"data": self.data(), "layout": self.layout()}).to_string()
+    }
+    #[allow(clippy::cast_precision_loss)]
+    fn data(&self) -> Value {
+        let subplots = self.subplots && self.traces.len() > 1;
+        let pies = self
+            .traces
+            .iter()
+            .filter(|f| f.kind == TraceKind::Pie)
+            .count();
+        let mut pie = 0_usize;
+        let traces = self.traces.iter().enumerate().map(|(i, trace)| {
+            let mut json = trace.to_json();
+            if trace.kind == TraceKind::Pie {
+                // Pies are placed in their subplot or side by side
+                let (x, y) = if subplots {
+                    self.domain(i)
+                } else {
+                    pie += 1;
+                    (
+                        [(pie - 1) as f64 / pies as f64, pie as f64 / pies as f64],
+                        [0.0, 1.0],
+                    )
+                };
+                json["domain"] = json!({"x": x, "y": y});
+            } else if subplots && i > 0 {
+                json["xaxis"] = json!(format!("x{}", i + 1));
+                json["yaxis"] = json!(format!("y{}", i + 1));
+            } else if !subplots && trace.secondary_y {
+                json["yaxis"] = json!("y2");
+            }
+            json
+        });
+        Value::Array(traces.collect())
+    }
+    fn layout(&self) -> Value {
+        let axis = |label: &Option<String>, log: bool| {
+            let mut axis = json!({});
+            if let Some(label) = label {
+                axis["title"] = json!({ "text": label });
+            }
+            if log {
+                axis["type"] = json!("log");
+            }
+            axis
+        };
+        let mut layout = json!({"width": self.width, "height": self.height});
+        if let Some(title) = &self.title {
+            layout["title"] = json!({ "text": title });
+        }
+        if let Some(show) = self.show_legend {
+            layout["showlegend"] = json!(show);
+        }
+        if self.subplots && self.traces.len() > 1 {
+            let mut titles = vec![];
+            for (i, trace) in self.traces.iter().enumerate() {
+                let suffix = if i == 0 {
+                    String::new()
+                } else {
+                    (i + 1).to_string()
+                };
+                let (x, y) = self.domain(i);
+                let mut x_axis = axis(&self.x_label, self.log_x);
+                if matches!(trace.x, Values::Dates(_)) {
+                    x_axis["type"] = json!("date");
+                }
+                x_axis["domain"] = json!(x);
+                x_axis["anchor"] = json!(format!("y{}", suffix));
+                let mut y_axis = axis(&self.y_label, self.log_y);
+                y_axis["domain"] = json!(y);
+                y_axis["anchor"] = json!(format!("x{}", suffix));
+                if trace.kind == TraceKind::Heatmap {
+                    y_axis["autorange"] = json!("reversed");
+                }
+                layout[format!("xaxis{}", suffix)] = x_axis;
+                layout[format!("yaxis{}", suffix)] = y_axis;
+                titles.push(json!({
+                    "text": trace.name,
+                    "x": x[0] + (x[1] - x[0]) / 2.0,
+                    "y": y[1],
+                    "xref": "paper",
+                    "yref": "paper",
+                    "xanchor": "center",
+                    "yanchor": "bottom",
+                    "showarrow": false
+                }));
+            }
+            layout["annotations"] = Value::Array(titles);
+        } else if self.traces.iter().all(|f| f.kind == TraceKind::Pie) {
+            // Pies are not drawn on axes
+        } else {
+            layout["xaxis"] = axis(&self.x_label, self.log_x);
+            if self.traces.iter().any(|f| matches!(f.x, Values::Dates(_))) {
+                layout["xaxis"]["type"] = json!("date");
+            }
+            layout["yaxis"] = axis(&self.y_label, self.log_y);
+            if self.traces.iter().any(|f| f.kind == TraceKind::Heatmap) {
+                layout["yaxis"]["autorange"] = json!("reversed");
+            }
+            if self.traces.iter().any(|f| f.secondary_y) {
+                let mut secondary = axis(&None, self.log_y);
+                secondary["overlaying"] = json!("y");
+                secondary["side"] = json!("right");
+                layout["yaxis2"] = secondary;
+            }
+        }
+        layout
+    }
+    /// Render the plot as a self-contained HTML page
+    ///
+    /// plotly.js is inlined in the page so it can be opened without network access, `</` in trace
+    /// names and labels is escaped so they cannot close the script holding the figure
+    ///
+    /// # Example
+    /// ```
+    /// use dami::plots::{Plot, Trace};
+    /// let mut plot = Plot::new();
+    /// plot.add_trace(Trace::bar("</script><b>sales", vec!["Q1"], vec![3.0]));
+    /// let page = plot.to_html().unwrap();
+    /// assert!(!page.contains("</script><b>sales"));
+    /// assert!(page.contains(r"<\/script><b>sales"));
+    /// ```
+    /// # Errors
+    /// * `IOError`: If the page generated by plotly cannot be read back from the temporary directory
+    pub fn to_html(&self) -> Result<String, PlotErrors> {
+        let mut plot = plotly::Plot::new();
+        plot.use_local_plotly();
+        let path = temp_path();
+        plot.to_html(&path);
+        let page = fs::read_to_string(&path);
+        let _ = fs::remove_file(&path);
+        let mut page = page?;
+        // plotly draws an empty plot, replace it with our figure once the page loads.
+        // Column names end up in the JSON, escape `</` so they cannot close the script
+        let script = format!(
+            "<script type=\"text/javascript\">\nPlotly.react('{}', {}, {}, {{\"responsive\": true}});\n</script>\n",
+            PLOTLY_DIV,
+            self.data().to_string().replace("</", "<\\/"),
+            self.layout().to_string().replace("</", "<\\/")
+        );
+        match page.rfind("</body>") {
+            Some(end) => page.insert_str(end, &script),
+            None => page.push_str(&script),
+        }
+        Ok(page)
+    }
+    /// Render the plot as an SVG image
+    ///
+    /// The image is drawn without plotly.js or a browser so it can be embedded in reports
+    pub fn to_svg(&self) -> String {
+        svg::render(self)
+    }
+    /// Save the plot to `path` and return the saved contents
+    ///
+    /// Paths ending with `.svg` are saved as an SVG image, see [`to_svg`](#method.to_svg), other paths
+    /// as a self-contained HTML page, see [`to_html`](#method.to_html)
+    /// # Errors
+    /// * `IOError`: If the file cannot be written
+    pub fn write<P: AsRef<Path>>(&self, path: P) -> Result<String, PlotErrors> {
+        let is_svg = path
+            .as_ref()
+            .extension()
+            .map_or(false, |f| f.eq_ignore_ascii_case("svg"));
+        let contents = if is_svg {
+            self.to_svg()
+        } else {
+            self.to_html()?
+        };
+        fs::write(path, &contents)?;
+        Ok(contents)
+    }
+    /// Open the plot in the default browser
+    ///
+    /// The plot is saved as an HTML page in the temporary directory which is then opened
+    /// # Errors
+    /// * `IOError`: If the page cannot be rendered or saved or the browser cannot be started
+    pub fn show(&self) -> Result<(), PlotErrors> {
+        let path = temp_path();
+        self.write(&path)?;
+        open_browser(&path)?;
+        Ok(())
+    }
+    /// Show the plot in an evcxr notebook
+    /// # Errors
+    /// * `IOError`: If the page cannot be rendered
+    pub fn evcxr_display(&self) -> Result<(), PlotErrors> {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let page = self.to_html()?;
+        println!(
+            "EVCXR_BEGIN_CONTENT text/html\n<div>{}</div>\nEVCXR_END_CONTENT",
+            page.replace(
+                PLOTLY_DIV,
+                &format!(
+                    "dami_{}_{}",
+                    std::process::id(),
+                    COUNT.fetch_add(1, Ordering::SeqCst)
+                )
+            )
+        );
+        Ok(())
+    }
+}
+/// A new path in the temporary directory for an HTML page
+fn temp_path() -> PathBuf {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let mut path = temp_dir();
+    path.push(format!(
+        "dami_plot_{}_{}.html",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::SeqCst)
+    ));
+    path
+}
+/// Open `path` with the default application of the platform
+fn open_browser(path: &Path) -> Result<(), std::io::Error> {
+    if cfg!(target_os = "windows") {
+        std::process::Command::new("cmd")
+            .args(["/C", "start", ""])
+            .arg(path)
+            .spawn()?;
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open").arg(path).spawn()?;
+    } else {
+        std::process::Command::new("xdg-open").arg(path).spawn()?;
+    }
+    Ok(())
+}
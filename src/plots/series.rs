@@ -1,21 +1,50 @@
+use crate::plots::options::{PlotKind, PlotOptions};
+use crate::plots::plot::{Plot, PlotErrors, Values};
 use crate::prelude::Series;
-use num_traits::Num;
-use serde::Serialize;
+use num_traits::ToPrimitive;
+use std::path::Path;
 
-use plotly::common::Mode;
-use plotly::{Bar, Histogram, Plot, Scatter};
-use std::env::temp_dir;
-use std::fs;
-use std::time::{SystemTime, UNIX_EPOCH};
-
-impl<T: Clone + Default + Num + Serialize + 'static> Series<T> {
+impl<T: Clone + Default + ToPrimitive + 'static> Series<T> {
     /// # Requires Feature
     ///  > * `stats`
     ///
-    /// Plot data in the series
+    /// Build a [`Plot`] of the data in the series following `options`, the plot can be
+    /// customized further before it is shown or saved
     ///
-    /// This function provides higher order methods for plotting data in a series
-    /// The series should contain numerical data (usize, floats,ints) ie anything implementing the Num trait
+    /// The series should contain numerical data (usize, floats,ints) ie anything implementing the
+    /// ToPrimitive trait. The values are plotted against the index, on a date axis if every label
+    /// of the index is a date, so the column options `x`, `y`, `secondary_y`, `subplots` and
+    /// `group_by` of [`PlotOptions`] are not used
+    ///
+    /// [`Plot`]: ../plots/struct.Plot.html
+    /// [`PlotOptions`]: ../plots/struct.PlotOptions.html
+    /// # Example
+    /// ```
+    /// use dami::plots::{PlotKind, PlotOptions};
+    /// use dami::prelude::*;
+    /// let series = Series::from(vec![1, 4, 2]);
+    /// let mut options = PlotOptions::new(PlotKind::Bar);
+    /// options.set_title("Counts").set_colors(&["teal"]);
+    /// assert!(series.to_plot(&options).to_svg().contains("Counts"));
+    /// ```
+    pub fn to_plot(&self, options: &PlotOptions) -> Plot {
+        let values = self
+            .to_vec()
+            .iter()
+            .map(|f| f.to_f64().unwrap_or(f64::NAN))
+            .collect();
+        let mut options = options.clone();
+        options.set_subplots(false).set_secondary_y(&[]);
+        options.build(
+            &Values::from_index(self.get_index()),
+            vec![(self.get_name(), values)],
+            None,
+        )
+    }
+    /// # Requires Feature
+    ///  > * `stats`
+    ///
+    /// Plot data in the series
     ///
     /// # Arguments
     /// * `kind`: The type of plot to draw.
@@ -26,56 +55,53 @@ impl<T: Clone + Default + Num + Serialize + 'static> Series<T> {
     ///
     /// > > * "bar"->bar graph,
     ///
-    /// > > * "scatter" -> scatter graph
+    /// > > * "box"->box plot
+    ///
+    /// > > * "scatter" | "points" | "marks" -> scatter graph
     ///
     /// > > * "line" -> line graph
     ///
-    /// If the string passed to `kind` argument doesn't match the above values. A line plot is drown
+    /// > > * "area" -> area graph
+    ///
+    /// > > * "pie" -> pie chart with a slice for each label in the index
+    ///
+    /// > > * "kde" | "density" -> kernel density estimate
+    ///
+    /// > > * "hexbin" -> hexagonal bins of the values against their positions
+    ///
+    /// > > * "heatmap" -> heatmap with a row for each label in the index
+    ///
+    /// > > * "scatter_matrix" -> histogram, the scatter matrix of a single series
+    ///
+    /// Use [`to_plot`](#method.to_plot) for more options
+    /// # Errors
+    /// * `UnknownKind`: If `kind` is not one of the above values
+    /// * `IOError`: If the plot cannot be saved or the browser cannot be opened
     ///
     /// # Note
     /// This is backed by [plotly.js] using the [plotly] crate, so the resulting graph is opened in
-    /// your default browser.
+    /// your default browser. Use [`plot_to_file`](#method.plot_to_file) on machines without a browser
     ///
     /// [plotly.js]: https://plot.ly/javascript/
     /// [plotly]: https://docs.rs/plotly
-    pub fn plot(&self, kind: &str) {
-        let mut plot = Plot::new();
-        match kind {
-            "bar" => plot.add_trace(self.plot_bar(&self.get_name(), self.get_index())),
-            "line" => {
-                plot.add_trace(self.plot_line(Mode::Lines, &self.get_name(), self.get_index()))
-            }
-            "hist" => plot.add_trace(self.plot_histogram(&self.get_name())),
-            "h_hist" => plot.add_trace(self.plot_horizontal_histogram(&self.get_name())),
-            "scatter" => {
-                plot.add_trace(self.plot_line(Mode::Markers, &self.get_name(), self.get_index()))
-            }
-            _ => {
-                eprintln!("Method {} not known,defaulting to line plot", kind);
-                plot.add_trace(self.plot_line(
-                    Mode::Lines,
-                    self.get_name().as_str(),
-                    self.get_index(),
-                ));
-            }
-        };
-        plot.show();
-    }
-    #[doc(hidden)]
-    pub fn plot_bar(&self, name: &str, index: Vec<String>) -> Box<Bar<String, T>> {
-        Bar::new(index, self.to_vec()).name(name)
+    pub fn plot(&self, kind: &str) -> Result<(), PlotErrors> {
+        self.to_plot(&PlotOptions::new(kind.parse::<PlotKind>()?))
+            .show()
     }
-    #[doc(hidden)]
-    pub fn plot_line(&self, mode: Mode, name: &str, index: Vec<String>) -> Box<Scatter<String, T>> {
-        Scatter::new(index, self.to_vec()).name(name).mode(mode)
-    }
-    #[doc(hidden)]
-    pub fn plot_histogram(&self, name: &str) -> Box<Histogram<T>> {
-        Histogram::new(self.to_vec()).name(name)
-    }
-    #[doc(hidden)]
-    pub fn plot_horizontal_histogram(&self, name: &str) -> Box<Histogram<T>> {
-        Histogram::new_horizontal(self.to_vec()).name(name)
+    /// # Requires Feature
+    ///  > * `stats`
+    ///
+    /// Save a plot of the data in the series to `path` and return the saved contents
+    ///
+    /// Paths ending with `.svg` are saved as an SVG image drawn without a browser, other paths as
+    /// an HTML page with plotly.js inlined. Plots supported are the same as the
+    /// [`plot`](#method.plot) method
+    /// # Errors
+    /// * `UnknownKind`: If `kind` is not a known kind of plot
+    /// * `IOError`: If the file cannot be written
+    pub fn plot_to_file<P: AsRef<Path>>(&self, kind: &str, path: P) -> Result<String, PlotErrors> {
+        self.to_plot(&PlotOptions::new(kind.parse::<PlotKind>()?))
+            .write(path)
     }
     /// Plot a graph into a jupyter notebook using rust repl environment which can be downloaded and installed
     /// from  [here](https://github.com/google/evcxr)
@@ -96,47 +122,12 @@ impl<T: Clone + Default + Num + Serialize + 'static> Series<T> {
     /// This is a large library and pulls in a lot of dependencies. It takes long to compile
     /// so it is recommended that you use your jupyter environment configured to use sccache.
     /// Instructions for setting that up can be found [here](https://github.com/google/evcxr/tree/master/evcxr_jupyter)
-    pub fn plot_evcxr(&self, kind: &str) {
-        let mut plot = Plot::new();
-
-        let mut tempo_dir = temp_dir();
-        let time = SystemTime::now()
-            .duration_since(UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
-        tempo_dir.push(format!("dami{}.html", time));
-        match kind {
-            "bar" => plot.add_trace(self.plot_bar(self.get_name().as_str(), self.get_index())),
-            "line" => plot.add_trace(self.plot_line(
-                Mode::Lines,
-                self.get_name().as_str(),
-                self.get_index(),
-            )),
-            "hist" => plot.add_trace(self.plot_histogram(self.get_name().as_str())),
-            "h_hist" => plot.add_trace(self.plot_horizontal_histogram(self.get_name().as_str())),
-            "points" | "marks" => plot.add_trace(self.plot_line(
-                Mode::Markers,
-                self.get_name().as_str(),
-                self.get_index(),
-            )),
-            _ => {
-                eprintln!("Method {} not known,defaulting to line plot", kind);
-                plot.add_trace(self.plot_line(
-                    Mode::Lines,
-                    self.get_name().as_str(),
-                    self.get_index(),
-                ));
-            }
-        };
-        plot.to_html(tempo_dir.clone());
-        let plot_data = fs::read_to_string(tempo_dir).unwrap();
-
-        println!(
-            "EVCXR_BEGIN_CONTENT text/html\n{}\nEVCXR_END_CONTENT",
-            format!(
-                "<div>{}</div>",
-                &plot_data.replace("plotly-html-element", &format!("dami_{}", time))
-            )
-        )
+    ///
+    /// # Errors
+    /// * `UnknownKind`: If `kind` is not a known kind of plot
+    /// * `IOError`: If the plot cannot be rendered
+    pub fn plot_evcxr(&self, kind: &str) -> Result<(), PlotErrors> {
+        self.to_plot(&PlotOptions::new(kind.parse::<PlotKind>()?))
+            .evcxr_display()
     }
 }
@@ -0,0 +1,1395 @@
+//! Render plots as SVG images without a browser
+//!
+//! Traces are first turned into marks in data coordinates, categories are placed at `0.5`, `1.5`...
+//! so every axis is numeric, then the marks are scaled to the plotting area.
+use crate::io::html::escape_html;
+use crate::plots::plot::{Plot, Trace, TraceKind, Values};
+use chrono::{Datelike, NaiveDate, NaiveDateTime};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+/// Colors given to traces in turn, the default colors of plotly.js
+const PALETTE: [&str; 10] = [
+    "#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#7f7f7f",
+    "#bcbd22", "#17becf",
+];
+/// The colors of the Viridis scale used by heatmaps and hexbins, from the lowest value to the
+/// highest
+const COLORSCALE: [(u8, u8, u8); 5] = [
+    (68, 1, 84),
+    (59, 82, 139),
+    (33, 145, 140),
+    (94, 201, 98),
+    (253, 231, 37),
+];
+/// Steps between the ticks of date axes shorter than a month, in seconds, and the format of
+/// their labels
+const DATE_STEPS: [(i64, &str); 16] = [
+    (1, "%H:%M:%S"),
+    (5, "%H:%M:%S"),
+    (15, "%H:%M:%S"),
+    (30, "%H:%M:%S"),
+    (60, "%H:%M"),
+    (300, "%H:%M"),
+    (900, "%H:%M"),
+    (1800, "%H:%M"),
+    (3600, "%H:%M"),
+    (3 * 3600, "%m-%d %H:%M"),
+    (6 * 3600, "%m-%d %H:%M"),
+    (12 * 3600, "%m-%d %H:%M"),
+    (86400, "%Y-%m-%d"),
+    (2 * 86400, "%Y-%m-%d"),
+    (7 * 86400, "%Y-%m-%d"),
+    (14 * 86400, "%Y-%m-%d"),
+];
+/// Steps between the ticks of longer date axes, in months
+const MONTH_STEPS: [i64; 13] = [1, 3, 6, 12, 24, 60, 120, 240, 600, 1200, 2400, 6000, 12000];
+/// The number of hexagons across the x axis of a hexbin
+pub(crate) const HEXBIN_GRID: usize = 20;
+const LEFT: f64 = 70.0;
+const BOTTOM: f64 = 50.0;
+const LEGEND_WIDTH: f64 = 150.0;
+/// Room for the ticks of a secondary y axis
+const SECONDARY_WIDTH: f64 = 50.0;
+/// Room for the color bar of a heatmap or hexbin
+const COLORBAR_WIDTH: f64 = 70.0;
+
+/// The color of the `i`th trace
+fn color(i: usize) -> &'static str {
+    PALETTE[i % PALETTE.len()]
+}
+/// The color of `t` between `0` and `1` on the color scale
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn colormap(t: f64) -> String {
+    let position = t.clamp(0.0, 1.0) * (COLORSCALE.len() - 1) as f64;
+    let lower = (position.floor() as usize).min(COLORSCALE.len() - 2);
+    let fraction = position - lower as f64;
+    let (from, to) = (COLORSCALE[lower], COLORSCALE[lower + 1]);
+    let mix = |a: u8, b: u8| (f64::from(a) + (f64::from(b) - f64::from(a)) * fraction).round();
+    format!(
+        "rgb({},{},{})",
+        mix(from.0, to.0),
+        mix(from.1, to.1),
+        mix(from.2, to.2)
+    )
+}
+
+/// Format a number for tick labels, whole numbers have no decimal point
+pub(crate) fn format_number(value: f64) -> String {
+    if value.is_nan() {
+        return String::from("NaN");
+    }
+    let abs = value.abs();
+    if abs != 0.0 && !(1e-4..1e15).contains(&abs) {
+        return format!("{:e}", value);
+    }
+    let text = format!("{:.6}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    if text == "-0" {
+        String::from("0")
+    } else {
+        text.to_string()
+    }
+}
+
+/// A shape in data coordinates
+enum Mark {
+    /// Lines joining the points, `NaN`s break the line
+    Line(Vec<(f64, f64)>),
+    /// A marker at each point
+    Dots(Vec<(f64, f64)>),
+    /// A rectangle from `(x0,y0)` to `(x1,y1)`
+    Rect(f64, f64, f64, f64),
+    /// A box plot centered at `x`
+    Box { x: f64, width: f64, stats: BoxStats },
+    /// A filled polygon
+    Area(Vec<(f64, f64)>),
+    /// A polygon colored by `value` on the color scale, `text` shows the value inside it
+    Cell {
+        points: Vec<(f64, f64)>,
+        value: f64,
+        text: bool,
+    },
+}
+impl Mark {
+    /// Apply `fx` to the x coordinates and `fy` to the y coordinates of the mark
+    fn transform(&mut self, fx: fn(f64) -> f64, fy: fn(f64) -> f64) {
+        match self {
+            Mark::Line(points)
+            | Mark::Dots(points)
+            | Mark::Area(points)
+            | Mark::Cell { points, .. } => {
+                for point in points {
+                    *point = (fx(point.0), fy(point.1));
+                }
+            }
+            Mark::Rect(x0, y0, x1, y1) => {
+                *x0 = fx(*x0);
+                *x1 = fx(*x1);
+                *y0 = fy(*y0);
+                *y1 = fy(*y1);
+            }
+            Mark::Box { stats, .. } => {
+                stats.q1 = fy(stats.q1);
+                stats.median = fy(stats.median);
+                stats.q3 = fy(stats.q3);
+                stats.low = fy(stats.low);
+                stats.high = fy(stats.high);
+                for outlier in &mut stats.outliers {
+                    *outlier = fy(*outlier);
+                }
+            }
+        }
+    }
+}
+/// The marks of one trace
+struct Layer {
+    color: String,
+    opacity: f64,
+    secondary: bool,
+    marks: Vec<Mark>,
+    /// The lowest and highest values of the cells, if the layer has any
+    range: Option<(f64, f64)>,
+}
+
+/// The quartiles, whiskers and outliers of a box plot
+struct BoxStats {
+    q1: f64,
+    median: f64,
+    q3: f64,
+    low: f64,
+    high: f64,
+    outliers: Vec<f64>,
+}
+impl BoxStats {
+    fn new(values: &[f64]) -> Option<BoxStats> {
+        let mut sorted = values
+            .iter()
+            .copied()
+            .filter(|f| !f.is_nan())
+            .collect::<Vec<f64>>();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let q1 = quantile(&sorted, 0.25);
+        let q3 = quantile(&sorted, 0.75);
+        let fence = 1.5 * (q3 - q1);
+        let inside = sorted
+            .iter()
+            .copied()
+            .filter(|f| *f >= q1 - fence && *f <= q3 + fence)
+            .collect::<Vec<f64>>();
+        Some(BoxStats {
+            q1,
+            median: quantile(&sorted, 0.5),
+            q3,
+            low: inside.first().copied().unwrap_or(q1),
+            high: inside.last().copied().unwrap_or(q3),
+            outliers: sorted
+                .iter()
+                .copied()
+                .filter(|f| *f < q1 - fence || *f > q3 + fence)
+                .collect(),
+        })
+    }
+}
+/// The `q` quantile of `sorted` values interpolating between the closest ranks
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q * (sorted.len() - 1) as f64;
+    let lower = position.floor() as usize;
+    let upper = position.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+/// Bin edges shared by all histograms on a plot, using Sturges' rule for the number of bins
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn bin_edges(traces: &[&Trace]) -> Vec<f64> {
+    let values = || {
+        traces
+            .iter()
+            .flat_map(|f| f.y.iter().copied())
+            .filter(|f| f.is_finite())
+    };
+    let min = values().fold(f64::INFINITY, f64::min);
+    let max = values().fold(f64::NEG_INFINITY, f64::max);
+    if min > max {
+        return vec![];
+    }
+    if (max - min).abs() < f64::EPSILON {
+        return vec![min - 0.5, min + 0.5];
+    }
+    let longest = traces.iter().map(|f| f.y.len()).max().unwrap_or(1);
+    let bins = ((longest as f64).log2().ceil() as usize + 1).max(1);
+    let width = (max - min) / bins as f64;
+    (0..=bins).map(|i| min + width * i as f64).collect()
+}
+/// The number of `values` in each bin, the last bin includes its upper edge
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+fn bin_counts(values: &[f64], edges: &[f64]) -> Vec<f64> {
+    let bins = edges.len().saturating_sub(1);
+    let mut counts = vec![0.0; bins];
+    if bins == 0 {
+        return counts;
+    }
+    let width = edges[1] - edges[0];
+    for value in values.iter().filter(|f| f.is_finite()) {
+        let bin = (((value - edges[0]) / width).floor().max(0.0) as usize).min(bins - 1);
+        counts[bin] += 1.0;
+    }
+    counts
+}
+
+/// The range and labeled ticks of an axis
+struct Axis {
+    min: f64,
+    max: f64,
+    ticks: Vec<(f64, String)>,
+}
+impl Axis {
+    /// A numeric axis covering `values`, padded unless the range starts or ends at a bar's base
+    ///
+    /// The values of log axes are the logarithms of the data, ticks are labeled with the data.
+    /// Fewer ticks are used on axes shorter than `length` pixels
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn numeric(values: &[f64], baseline: bool, log: bool, length: f64) -> Axis {
+        let mut min = values
+            .iter()
+            .copied()
+            .filter(|f| f.is_finite())
+            .fold(f64::INFINITY, f64::min);
+        let mut max = values
+            .iter()
+            .copied()
+            .filter(|f| f.is_finite())
+            .fold(f64::NEG_INFINITY, f64::max);
+        if min > max {
+            min = 0.0;
+            max = 1.0;
+        }
+        if (max - min).abs() < f64::EPSILON {
+            min -= 1.0;
+            max += 1.0;
+        }
+        let pad = (max - min) * 0.05;
+        if !(baseline && min == 0.0) {
+            min -= pad;
+        }
+        if !(baseline && max == 0.0) {
+            max += pad;
+        }
+        let count = ((length / 60.0) as usize).clamp(2, 6);
+        let ticks = nice_ticks(min, max, count)
+            .into_iter()
+            .map(|f| {
+                if log {
+                    (f, format_number(round_significant(10_f64.powf(f), 3)))
+                } else {
+                    (f, format_number(f))
+                }
+            })
+            .collect();
+        Axis { min, max, ticks }
+    }
+    /// An axis covering `values`, seconds elapsed since the unix epoch, with ticks at round dates
+    /// and times
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn dates(values: &[f64], length: f64) -> Axis {
+        let mut axis = Axis::numeric(values, false, false, length);
+        // Dates take more room than numbers
+        let count = ((length / 100.0) as usize).clamp(2, 6);
+        axis.ticks = date_ticks(axis.min, axis.max, count);
+        axis
+    }
+    /// An axis with `labels` at `0.5`, `1.5`..., only some labels are shown if they are closer
+    /// than `spacing` pixels on an axis `length` pixels long
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn categorical(labels: &[String], length: f64, spacing: f64) -> Axis {
+        let fits = (length / spacing).max(1.0) as usize;
+        let step = (labels.len() + fits - 1) / fits;
+        let ticks = labels
+            .iter()
+            .enumerate()
+            .step_by(step.max(1))
+            .map(|(i, f)| (i as f64 + 0.5, shorten(f, 12)))
+            .collect();
+        Axis {
+            min: 0.0,
+            max: labels.len().max(1) as f64,
+            ticks,
+        }
+    }
+    /// The position of `value` from `0` at the start of the axis to `1` at its end
+    ///
+    /// Positions far outside the axis are clamped, so bars with a base at the logarithm of zero
+    /// are still drawn
+    fn scale(&self, value: f64) -> f64 {
+        ((value - self.min) / (self.max - self.min)).clamp(-1.0, 2.0)
+    }
+}
+/// Ticks at round dates between `min` and `max` seconds, about `count` of them
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_possible_wrap,
+    clippy::cast_sign_loss
+)]
+fn date_ticks(min: f64, max: f64, count: usize) -> Vec<(f64, String)> {
+    let format = |seconds: i64, format: &str| {
+        NaiveDateTime::from_timestamp_opt(seconds, 0)
+            .map_or_else(|| seconds.to_string(), |f| f.format(format).to_string())
+    };
+    let span = max - min;
+    let count = count as f64;
+    if let Some((step, label)) = DATE_STEPS
+        .iter()
+        .find(|(step, _)| span / *step as f64 <= count)
+    {
+        let start = (min / *step as f64).ceil() as i64 * step;
+        return (start..)
+            .step_by(*step as usize)
+            .take_while(|f| *f as f64 <= max)
+            .map(|f| (f as f64, format(f, label)))
+            .collect();
+    }
+    // Longer steps follow the calendar, months are counted from the start of year 0
+    let months = span / (30.44 * 86400.0);
+    let step = MONTH_STEPS
+        .iter()
+        .copied()
+        .find(|f| months / *f as f64 <= count)
+        .unwrap_or(MONTH_STEPS[MONTH_STEPS.len() - 1]);
+    let label = if step < 12 { "%Y-%m" } else { "%Y" };
+    let first = match NaiveDateTime::from_timestamp_opt(min.floor() as i64, 0) {
+        Some(first) => first,
+        None => return vec![],
+    };
+    let month = i64::from(first.year()) * 12 + i64::from(first.month0()) + 1;
+    let start = (month + step - 1).div_euclid(step) * step;
+    // Months past the dates chrono can represent end the ticks
+    (start..)
+        .step_by(step as usize)
+        .map(|f| {
+            i32::try_from(f.div_euclid(12))
+                .ok()
+                .and_then(|year| NaiveDate::from_ymd_opt(year, f.rem_euclid(12) as u32 + 1, 1))
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.timestamp())
+        })
+        .take_while(|f| f.map_or(false, |f| f as f64 <= max))
+        .flatten()
+        .map(|f| (f as f64, format(f, label)))
+        .collect()
+}
+/// Round `value` to `digits` significant digits
+#[allow(clippy::cast_possible_truncation)]
+fn round_significant(value: f64, digits: i32) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
+    }
+    let scale = 10_f64.powi(digits - 1 - value.abs().log10().floor() as i32);
+    (value * scale).round() / scale
+}
+/// Round tick values about `count` of which cover `min` to `max`
+#[allow(clippy::cast_precision_loss)]
+fn nice_ticks(min: f64, max: f64, count: usize) -> Vec<f64> {
+    let raw = (max - min) / count as f64;
+    let magnitude = 10_f64.powf(raw.log10().floor());
+    let step = [1.0, 2.0, 2.5, 5.0, 10.0]
+        .iter()
+        .map(|f| f * magnitude)
+        .find(|f| *f >= raw)
+        .unwrap_or(10.0 * magnitude);
+    let start = (min / step).ceil();
+    (0..=count * 3)
+        .map(|i| (start + i as f64) * step)
+        .take_while(|f| *f <= max + step * 1e-9)
+        .collect()
+}
+/// Cut `text` to `max` characters ending with `...`
+fn shorten(text: &str, max: usize) -> String {
+    if text.chars().count() > max {
+        format!("{}...", text.chars().take(max - 3).collect::<String>())
+    } else {
+        text.to_string()
+    }
+}
+
+/// The bars of a histogram of `trace` with bins between `edges`
+fn histogram_bars(trace: &Trace, edges: &[f64]) -> Vec<Mark> {
+    bin_counts(&trace.y, edges)
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0.0)
+        .map(|(j, count)| {
+            if trace.kind == TraceKind::Histogram {
+                Mark::Rect(edges[j], 0.0, edges[j + 1], *count)
+            } else {
+                Mark::Rect(0.0, edges[j], *count, edges[j + 1])
+            }
+        })
+        .collect()
+}
+/// The color of `trace`, the `i`th trace on the plot
+fn trace_color(i: usize, trace: &Trace) -> String {
+    trace.color.clone().unwrap_or_else(|| color(i).to_string())
+}
+
+/// Hexagons counting the points of `trace` that fall in them, the hexagons of the two offset grids
+/// are keyed by twice their position in units of the grid
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::similar_names
+)]
+fn hexagons(trace: &Trace) -> Vec<Mark> {
+    let points = trace
+        .x
+        .numbers()
+        .into_iter()
+        .zip(trace.y.iter().copied())
+        .filter(|(x, y)| x.is_finite() && y.is_finite())
+        .collect::<Vec<(f64, f64)>>();
+    if points.is_empty() {
+        return vec![];
+    }
+    let min_x = points.iter().map(|f| f.0).fold(f64::INFINITY, f64::min);
+    let max_x = points.iter().map(|f| f.0).fold(f64::NEG_INFINITY, f64::max);
+    let min_y = points.iter().map(|f| f.1).fold(f64::INFINITY, f64::min);
+    let max_y = points.iter().map(|f| f.1).fold(f64::NEG_INFINITY, f64::max);
+    let columns = HEXBIN_GRID as f64;
+    let rows = (columns / 3_f64.sqrt()).floor();
+    let size = |range: f64, count: f64| if range > 0.0 { range / count } else { 1.0 };
+    let (sx, sy) = (size(max_x - min_x, columns), size(max_y - min_y, rows));
+    let mut counts = BTreeMap::new();
+    for (x, y) in points {
+        let (x, y) = ((x - min_x) / sx, (y - min_y) / sy);
+        let (x1, y1) = (x.round(), y.round());
+        let (x2, y2) = (x.floor(), y.floor());
+        let d1 = (x - x1).powi(2) + 3.0 * (y - y1).powi(2);
+        let d2 = (x - x2 - 0.5).powi(2) + 3.0 * (y - y2 - 0.5).powi(2);
+        let key = if d1 < d2 {
+            (2 * x1 as i64, 2 * y1 as i64)
+        } else {
+            (2 * x2 as i64 + 1, 2 * y2 as i64 + 1)
+        };
+        *counts.entry(key).or_insert(0.0) += 1.0;
+    }
+    let corners = [
+        (0.5, -0.5),
+        (0.5, 0.5),
+        (0.0, 1.0),
+        (-0.5, 0.5),
+        (-0.5, -0.5),
+        (0.0, -1.0),
+    ];
+    counts
+        .into_iter()
+        .map(|((i, j), count)| {
+            let x = min_x + i as f64 / 2.0 * sx;
+            let y = min_y + j as f64 / 2.0 * sy;
+            Mark::Cell {
+                points: corners
+                    .iter()
+                    .map(|(dx, dy)| (x + dx * sx, y + dy * sy / 3.0))
+                    .collect(),
+                value: count,
+                text: false,
+            }
+        })
+        .collect()
+}
+/// The lowest and highest values of the cells in `marks`
+fn cell_range(marks: &[Mark]) -> Option<(f64, f64)> {
+    marks
+        .iter()
+        .filter_map(|f| match f {
+            Mark::Cell { value, .. } if value.is_finite() => Some(*value),
+            _ => None,
+        })
+        .fold(None, |range, value| match range {
+            None => Some((value, value)),
+            Some((min, max)) => Some((f64::min(min, value), f64::max(max, value))),
+        })
+}
+/// The labels of the slices of the pies in `traces`, in the order they first appear
+fn pie_labels(traces: &[Trace]) -> Vec<String> {
+    let mut labels: Vec<String> = vec![];
+    for trace in traces.iter().filter(|f| f.kind == TraceKind::Pie) {
+        for label in trace.x.labels() {
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+    }
+    labels
+}
+
+/// The marks of `traces` and the labels of the categorical axes of a panel
+struct Panel {
+    layers: Vec<Layer>,
+    /// The labels of a categorical x axis
+    columns: Option<Vec<String>>,
+    /// The labels of a categorical y axis, from the bottom
+    rows: Option<Vec<String>>,
+    /// Whether the x axis holds dates
+    dates: bool,
+}
+
+/// Turn `traces` and their positions on the plot into layers of marks and the labels of the
+/// categorical axes, if any
+#[allow(clippy::cast_precision_loss, clippy::too_many_lines)]
+fn layers(traces: &[(usize, &Trace)]) -> Panel {
+    let categorical = traces.iter().any(|(_, f)| {
+        matches!(f.kind, TraceKind::Bar | TraceKind::Box | TraceKind::Heatmap)
+            || (matches!(f.x, Values::Labels(_)) && f.kind != TraceKind::Pie)
+    });
+    // Categories in the order they first appear
+    let mut categories: Vec<String> = vec![];
+    if categorical {
+        for (_, trace) in traces {
+            let labels = match trace.kind {
+                TraceKind::Box => vec![trace.name.clone()],
+                TraceKind::Histogram
+                | TraceKind::HorizontalHistogram
+                | TraceKind::Pie
+                | TraceKind::Hexbin => vec![],
+                _ => trace.x.labels(),
+            };
+            for label in labels {
+                if !categories.contains(&label) {
+                    categories.push(label);
+                }
+            }
+        }
+    }
+    // Rows of the first heatmap from the bottom, so the first row is drawn at the top
+    let rows = traces
+        .iter()
+        .find(|(_, f)| f.kind == TraceKind::Heatmap)
+        .map(|(_, f)| f.rows.iter().rev().cloned().collect::<Vec<String>>());
+    let position = |labels: &[String], label: &str| {
+        labels
+            .iter()
+            .position(|f| f == label)
+            .map_or(f64::NAN, |f| f as f64 + 0.5)
+    };
+    let histograms = traces
+        .iter()
+        .map(|(_, f)| *f)
+        .filter(|f| {
+            matches!(
+                f.kind,
+                TraceKind::Histogram | TraceKind::HorizontalHistogram
+            )
+        })
+        .collect::<Vec<&Trace>>();
+    let edges = bin_edges(&histograms);
+    let bars = traces
+        .iter()
+        .filter(|(_, f)| f.kind == TraceKind::Bar)
+        .count();
+    let mut bar = 0_usize;
+    // The tops of the area traces stacked so far, on the primary and secondary y axes
+    let mut stacks: [Vec<f64>; 2] = [vec![], vec![]];
+    let mut result = vec![];
+    for (i, trace) in traces {
+        let xs = if categorical {
+            trace
+                .x
+                .labels()
+                .iter()
+                .map(|f| position(&categories, f))
+                .collect()
+        } else {
+            match &trace.x {
+                Values::Labels(_) => vec![],
+                values => values.numbers(),
+            }
+        };
+        let points = xs
+            .iter()
+            .copied()
+            .zip(trace.y.iter().copied())
+            .collect::<Vec<(f64, f64)>>();
+        let mut opacity = 1.0;
+        let marks = match trace.kind {
+            TraceKind::Line => vec![Mark::Line(points)],
+            TraceKind::Scatter => vec![Mark::Dots(points)],
+            TraceKind::Bar => {
+                // Bars of each category share 80% of its width
+                let width = 0.8 / bars as f64;
+                let offset = -0.4 + width * bar as f64;
+                bar += 1;
+                points
+                    .iter()
+                    .filter(|(x, y)| !x.is_nan() && !y.is_nan())
+                    .map(|(x, y)| Mark::Rect(x + offset, 0.0, x + offset + width, *y))
+                    .collect()
+            }
+            TraceKind::Histogram | TraceKind::HorizontalHistogram => {
+                if histograms.len() > 1 {
+                    opacity = 0.6;
+                }
+                histogram_bars(trace, &edges)
+            }
+            TraceKind::Box => BoxStats::new(&trace.y)
+                .map(|stats| Mark::Box {
+                    x: position(&categories, &trace.name),
+                    width: 0.5,
+                    stats,
+                })
+                .into_iter()
+                .collect(),
+            TraceKind::Area => {
+                opacity = 0.6;
+                let stack = &mut stacks[usize::from(trace.secondary_y)];
+                if stack.len() < points.len() {
+                    stack.resize(points.len(), 0.0);
+                }
+                let mut polygon = points
+                    .iter()
+                    .zip(stack.iter())
+                    .map(|((x, _), base)| (*x, *base))
+                    .collect::<Vec<(f64, f64)>>();
+                for ((_, y), base) in points.iter().zip(stack.iter_mut()) {
+                    if y.is_finite() {
+                        *base += y;
+                    }
+                }
+                polygon.reverse();
+                polygon.extend(
+                    points
+                        .iter()
+                        .zip(stack.iter())
+                        .map(|((x, _), top)| (*x, *top)),
+                );
+                polygon.retain(|(x, _)| !x.is_nan());
+                vec![Mark::Area(polygon)]
+            }
+            TraceKind::Pie => vec![],
+            TraceKind::Hexbin => hexagons(trace),
+            TraceKind::Heatmap => {
+                let rows = rows.as_deref().unwrap_or_default();
+                let mut cells = vec![];
+                for (row, values) in trace.rows.iter().zip(&trace.z) {
+                    let y = position(rows, row);
+                    for (x, value) in xs.iter().zip(values) {
+                        cells.push(Mark::Cell {
+                            points: vec![
+                                (x - 0.5, y - 0.5),
+                                (x + 0.5, y - 0.5),
+                                (x + 0.5, y + 0.5),
+                                (x - 0.5, y + 0.5),
+                            ],
+                            value: *value,
+                            text: true,
+                        });
+                    }
+                }
+                cells
+            }
+        };
+        result.push(Layer {
+            color: trace_color(*i, trace),
+            opacity,
+            secondary: trace.secondary_y,
+            range: cell_range(&marks),
+            marks,
+        });
+    }
+    Panel {
+        layers: result,
+        columns: if categorical { Some(categories) } else { None },
+        rows,
+        dates: !categorical
+            && traces
+                .iter()
+                .any(|(_, f)| matches!(f.x, Values::Dates(_)) && f.kind != TraceKind::Pie),
+    }
+}
+
+/// The plotting area in pixels
+#[derive(Clone, Copy)]
+struct Area {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+/// Scales data coordinates to pixels
+struct Frame<'a> {
+    area: Area,
+    x: &'a Axis,
+    y: &'a Axis,
+}
+impl Frame<'_> {
+    fn px(&self, value: f64) -> f64 {
+        self.area.x + self.x.scale(value) * self.area.width
+    }
+    fn py(&self, value: f64) -> f64 {
+        self.area.y + self.area.height - self.y.scale(value) * self.area.height
+    }
+}
+
+/// Draw `traces` with their axes in `area`, traces are paired with their position on `plot`
+///
+/// Pies are drawn instead when every trace is a pie
+#[allow(clippy::too_many_lines)]
+fn draw_panel(svg: &mut String, plot: &Plot, traces: &[(usize, &Trace)], area: Area) {
+    if !traces.is_empty() && traces.iter().all(|(_, f)| f.kind == TraceKind::Pie) {
+        let pies = traces.iter().map(|(_, f)| *f).collect::<Vec<&Trace>>();
+        draw_pies(svg, &pies, &pie_labels(&plot.traces), area);
+        return;
+    }
+    let Panel {
+        mut layers,
+        columns,
+        rows,
+        dates,
+    } = layers(traces);
+    let identity: fn(f64) -> f64 = |f| f;
+    let log_x = plot.log_x && columns.is_none() && !dates;
+    let log_y = plot.log_y && rows.is_none();
+    let fx = if log_x { f64::log10 } else { identity };
+    let fy = if log_y { f64::log10 } else { identity };
+    let (mut xs, mut ys, mut secondary_ys) = (vec![], vec![], vec![]);
+    let mut baseline = false;
+    for layer in &mut layers {
+        let ys = if layer.secondary {
+            &mut secondary_ys
+        } else {
+            &mut ys
+        };
+        for mark in &mut layer.marks {
+            mark.transform(fx, fy);
+            match mark {
+                Mark::Line(points) | Mark::Dots(points) | Mark::Cell { points, .. } => {
+                    for (x, y) in points.iter() {
+                        xs.push(*x);
+                        ys.push(*y);
+                    }
+                }
+                Mark::Area(points) => {
+                    for (x, y) in points.iter() {
+                        xs.push(*x);
+                        ys.push(*y);
+                    }
+                    baseline = true;
+                }
+                Mark::Rect(x0, y0, x1, y1) => {
+                    xs.extend_from_slice(&[*x0, *x1]);
+                    ys.extend_from_slice(&[*y0, *y1]);
+                    baseline = true;
+                }
+                Mark::Box { x, stats, .. } => {
+                    xs.push(*x);
+                    ys.extend_from_slice(&[stats.low, stats.high]);
+                    ys.extend_from_slice(&stats.outliers);
+                }
+            }
+        }
+    }
+    let x_axis = match &columns {
+        Some(labels) => Axis::categorical(labels, area.width, 50.0),
+        None if dates => Axis::dates(&xs, area.width),
+        None => Axis::numeric(&xs, baseline, log_x, area.width),
+    };
+    let y_axis = match &rows {
+        Some(labels) => Axis::categorical(labels, area.height, 18.0),
+        None => Axis::numeric(&ys, baseline, log_y, area.height),
+    };
+    let secondary_axis = if layers.iter().any(|f| f.secondary) {
+        Some(Axis::numeric(
+            &secondary_ys,
+            baseline,
+            plot.log_y,
+            area.height,
+        ))
+    } else {
+        None
+    };
+    let frame = Frame {
+        area,
+        x: &x_axis,
+        y: &y_axis,
+    };
+    draw_axes(svg, &frame, secondary_axis.as_ref());
+    writeln!(
+        svg,
+        "<clipPath id=\"clip-{0}-{1}\"><rect x=\"{0}\" y=\"{1}\" width=\"{2}\" height=\"{3}\"/></clipPath>",
+        area.x, area.y, area.width, area.height
+    )
+    .unwrap();
+    writeln!(svg, "<g clip-path=\"url(#clip-{}-{})\">", area.x, area.y).unwrap();
+    for layer in &layers {
+        let frame = match (&secondary_axis, layer.secondary) {
+            (Some(axis), true) => Frame {
+                area,
+                x: &x_axis,
+                y: axis,
+            },
+            _ => Frame {
+                area,
+                x: &x_axis,
+                y: &y_axis,
+            },
+        };
+        for mark in &layer.marks {
+            draw_mark(svg, &frame, layer, mark);
+        }
+    }
+    svg.push_str("</g>\n");
+    if let Some(range) = layers.iter().find_map(|f| f.range) {
+        let x = if secondary_axis.is_some() {
+            area.x + area.width + SECONDARY_WIDTH
+        } else {
+            area.x + area.width
+        };
+        draw_colorbar(svg, x + 10.0, area, range);
+    }
+}
+/// The position of `value` from `0` at the start of `range` to `1` at its end
+fn fraction(value: f64, range: (f64, f64)) -> f64 {
+    if range.1 > range.0 {
+        (value - range.0) / (range.1 - range.0)
+    } else {
+        0.5
+    }
+}
+/// Draw a color bar from the lowest to the highest value of `range` at `x`, as tall as `area`
+fn draw_colorbar(svg: &mut String, x: f64, area: Area, range: (f64, f64)) {
+    let id = format!("colorscale-{}-{}", area.x, area.y);
+    writeln!(
+        svg,
+        "<linearGradient id=\"{}\" x1=\"0\" y1=\"1\" x2=\"0\" y2=\"0\">",
+        id
+    )
+    .unwrap();
+    for (i, (r, g, b)) in COLORSCALE.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let offset = i as f64 / (COLORSCALE.len() - 1) as f64;
+        writeln!(
+            svg,
+            "<stop offset=\"{}\" stop-color=\"rgb({},{},{})\"/>",
+            offset, r, g, b
+        )
+        .unwrap();
+    }
+    svg.push_str("</linearGradient>\n");
+    writeln!(
+        svg,
+        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"12\" height=\"{:.2}\" fill=\"url(#{})\"/>",
+        x, area.y, area.height, id
+    )
+    .unwrap();
+    let ticks = if range.1 > range.0 {
+        nice_ticks(range.0, range.1, 4)
+    } else {
+        vec![range.0]
+    };
+    for value in ticks {
+        writeln!(
+            svg,
+            "<text x=\"{:.2}\" y=\"{:.2}\" dominant-baseline=\"middle\">{}</text>",
+            x + 16.0,
+            area.y + area.height * (1.0 - fraction(value, range)),
+            format_number(value)
+        )
+        .unwrap();
+    }
+}
+/// Draw `pies` side by side in `area`, slices are colored by the position of their label in
+/// `labels`
+#[allow(clippy::cast_precision_loss)]
+fn draw_pies(svg: &mut String, pies: &[&Trace], labels: &[String], area: Area) {
+    let width = area.width / pies.len() as f64;
+    for (k, trace) in pies.iter().enumerate() {
+        let cx = area.x + width * (k as f64 + 0.5);
+        let cy = area.y + area.height / 2.0;
+        let radius = (width.min(area.height) / 2.0 - 20.0).max(1.0);
+        let slices = trace
+            .x
+            .labels()
+            .iter()
+            .zip(trace.y.iter().copied())
+            .filter(|(_, value)| value.is_finite() && *value > 0.0)
+            .map(|(label, value)| {
+                let color = labels.iter().position(|f| f == label).map_or(0, |f| f);
+                (color, value)
+            })
+            .collect::<Vec<(usize, f64)>>();
+        let total = slices.iter().map(|f| f.1).sum::<f64>();
+        if pies.len() > 1 {
+            writeln!(
+                svg,
+                "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"middle\">{}</text>",
+                cx,
+                cy + radius + 16.0,
+                escape_html(&shorten(&trace.name, 30))
+            )
+            .unwrap();
+        }
+        if slices.len() == 1 {
+            writeln!(
+                svg,
+                "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"{:.2}\" fill=\"{}\"/>",
+                cx,
+                cy,
+                radius,
+                color(slices[0].0)
+            )
+            .unwrap();
+            continue;
+        }
+        // Slices go clockwise from the top
+        let point =
+            |angle: f64, length: f64| (cx + length * angle.cos(), cy + length * angle.sin());
+        let mut start = -PI / 2.0;
+        for (i, value) in slices {
+            let sweep = value / total * 2.0 * PI;
+            let (x0, y0) = point(start, radius);
+            let (x1, y1) = point(start + sweep, radius);
+            writeln!(
+                svg,
+                "<path d=\"M {:.2} {:.2} L {:.2} {:.2} A {:.2} {:.2} 0 {} 1 {:.2} {:.2} Z\" fill=\"{}\" stroke=\"#ffffff\"/>",
+                cx,
+                cy,
+                x0,
+                y0,
+                radius,
+                radius,
+                u8::from(sweep > PI),
+                x1,
+                y1,
+                color(i)
+            )
+            .unwrap();
+            // Only slices of at least 5% are labeled with their share
+            if value / total >= 0.05 {
+                let (x, y) = point(start + sweep / 2.0, radius * 0.65);
+                writeln!(
+                    svg,
+                    "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"middle\" dominant-baseline=\"middle\" fill=\"#ffffff\">{:.1}%</text>",
+                    x,
+                    y,
+                    value / total * 100.0
+                )
+                .unwrap();
+            }
+            start += sweep;
+        }
+    }
+}
+/// Draw the grid lines, ticks and frame of the plotting area and the ticks of the `secondary` y
+/// axis on its right
+fn draw_axes(svg: &mut String, frame: &Frame, secondary: Option<&Axis>) {
+    let area = frame.area;
+    let bottom = area.y + area.height;
+    for (value, label) in &frame.y.ticks {
+        let y = frame.py(*value);
+        writeln!(
+            svg,
+            "<line x1=\"{:.2}\" y1=\"{:.2}\" x2=\"{:.2}\" y2=\"{:.2}\" stroke=\"#e5e5e5\"/>",
+            area.x,
+            y,
+            area.x + area.width,
+            y
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"end\" dominant-baseline=\"middle\">{}</text>",
+            area.x - 6.0,
+            y,
+            escape_html(label)
+        )
+        .unwrap();
+    }
+    if let Some(axis) = secondary {
+        let frame = Frame {
+            area,
+            x: frame.x,
+            y: axis,
+        };
+        for (value, label) in &axis.ticks {
+            writeln!(
+                svg,
+                "<text x=\"{:.2}\" y=\"{:.2}\" dominant-baseline=\"middle\">{}</text>",
+                area.x + area.width + 6.0,
+                frame.py(*value),
+                escape_html(label)
+            )
+            .unwrap();
+        }
+    }
+    for (value, label) in &frame.x.ticks {
+        let x = frame.px(*value);
+        writeln!(
+            svg,
+            "<line x1=\"{0:.2}\" y1=\"{1:.2}\" x2=\"{0:.2}\" y2=\"{2:.2}\" stroke=\"#444444\"/>",
+            x,
+            bottom,
+            bottom + 5.0
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"middle\">{}</text>",
+            x,
+            bottom + 18.0,
+            escape_html(label)
+        )
+        .unwrap();
+    }
+    writeln!(
+        svg,
+        "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"none\" stroke=\"#444444\"/>",
+        area.x, area.y, area.width, area.height
+    )
+    .unwrap();
+}
+/// Draw one mark of `layer`
+#[allow(clippy::too_many_lines)]
+fn draw_mark(svg: &mut String, frame: &Frame, layer: &Layer, mark: &Mark) {
+    let color = escape_html(&layer.color);
+    match mark {
+        Mark::Line(points) => {
+            // A polyline for each run of points without NaNs
+            let mut run = vec![];
+            for (x, y) in points.iter().chain(std::iter::once(&(f64::NAN, f64::NAN))) {
+                if x.is_nan() || y.is_nan() {
+                    if run.len() > 1 {
+                        writeln!(
+                            svg,
+                            "<polyline points=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"2\"/>",
+                            run.join(" "),
+                            color
+                        )
+                        .unwrap();
+                    }
+                    run.clear();
+                } else {
+                    run.push(format!("{:.2},{:.2}", frame.px(*x), frame.py(*y)));
+                }
+            }
+        }
+        Mark::Dots(points) => {
+            for (x, y) in points.iter().filter(|(x, y)| !x.is_nan() && !y.is_nan()) {
+                writeln!(
+                    svg,
+                    "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"3\" fill=\"{}\"/>",
+                    frame.px(*x),
+                    frame.py(*y),
+                    color
+                )
+                .unwrap();
+            }
+        }
+        Mark::Rect(x0, y0, x1, y1) => {
+            if [x0, y0, x1, y1].iter().any(|f| f.is_nan()) {
+                return;
+            }
+            let (left, right) = (frame.px(*x0), frame.px(*x1));
+            let (top, bottom) = (frame.py(*y1), frame.py(*y0));
+            writeln!(
+                svg,
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\" fill-opacity=\"{}\"/>",
+                left.min(right),
+                top.min(bottom),
+                (right - left).abs(),
+                (bottom - top).abs(),
+                color,
+                layer.opacity
+            )
+            .unwrap();
+        }
+        Mark::Box { x, width, stats } => {
+            let left = frame.px(x - width / 2.0);
+            let right = frame.px(x + width / 2.0);
+            let center = frame.px(*x);
+            let stroke = format!("stroke=\"{}\" stroke-width=\"1.5\"", color);
+            writeln!(
+                svg,
+                "<line x1=\"{0:.2}\" y1=\"{1:.2}\" x2=\"{0:.2}\" y2=\"{2:.2}\" {3}/>",
+                center,
+                frame.py(stats.high),
+                frame.py(stats.low),
+                stroke
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"{:.2}\" height=\"{:.2}\" fill=\"{}\" fill-opacity=\"0.5\" {}/>",
+                left,
+                frame.py(stats.q3),
+                right - left,
+                frame.py(stats.q1) - frame.py(stats.q3),
+                color,
+                stroke
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                "<line x1=\"{0:.2}\" y1=\"{1:.2}\" x2=\"{2:.2}\" y2=\"{1:.2}\" {3}/>",
+                left,
+                frame.py(stats.median),
+                right,
+                stroke
+            )
+            .unwrap();
+            for outlier in &stats.outliers {
+                writeln!(
+                    svg,
+                    "<circle cx=\"{:.2}\" cy=\"{:.2}\" r=\"3\" fill=\"none\" {}/>",
+                    center,
+                    frame.py(*outlier),
+                    stroke
+                )
+                .unwrap();
+            }
+        }
+        Mark::Area(points) => {
+            if points.len() < 2 {
+                return;
+            }
+            writeln!(
+                svg,
+                "<polygon points=\"{}\" fill=\"{1}\" fill-opacity=\"{2}\" stroke=\"{1}\" stroke-width=\"1.5\"/>",
+                polygon_points(frame, points),
+                color,
+                layer.opacity
+            )
+            .unwrap();
+        }
+        Mark::Cell {
+            points,
+            value,
+            text,
+        } => {
+            if value.is_nan() {
+                return;
+            }
+            let range = layer.range.unwrap_or((*value, *value));
+            let t = fraction(*value, range);
+            writeln!(
+                svg,
+                "<polygon points=\"{}\" fill=\"{}\"/>",
+                polygon_points(frame, points),
+                colormap(t)
+            )
+            .unwrap();
+            let xs = points.iter().map(|f| frame.px(f.0)).collect::<Vec<f64>>();
+            let ys = points.iter().map(|f| frame.py(f.1)).collect::<Vec<f64>>();
+            let span = |values: &[f64]| {
+                values.iter().copied().fold(f64::NEG_INFINITY, f64::max)
+                    - values.iter().copied().fold(f64::INFINITY, f64::min)
+            };
+            // Values are written in cells large enough to hold them
+            if *text && span(&xs) >= 36.0 && span(&ys) >= 16.0 {
+                #[allow(clippy::cast_precision_loss)]
+                let count = points.len() as f64;
+                writeln!(
+                    svg,
+                    "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"middle\" dominant-baseline=\"middle\" fill=\"{}\">{}</text>",
+                    xs.iter().sum::<f64>() / count,
+                    ys.iter().sum::<f64>() / count,
+                    if t > 0.6 { "#000000" } else { "#ffffff" },
+                    format_number(round_significant(*value, 3))
+                )
+                .unwrap();
+            }
+        }
+    }
+}
+/// The points of a polygon in pixels, points with `NaN`s are left out
+fn polygon_points(frame: &Frame, points: &[(f64, f64)]) -> String {
+    points
+        .iter()
+        .filter(|(x, y)| !x.is_nan() && !y.is_nan())
+        .map(|(x, y)| format!("{:.2},{:.2}", frame.px(*x), frame.py(*y)))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+/// The colors and names shown in the legend, the slices of pies are listed after the other traces
+///
+/// Heatmaps and hexbins are described by their color bar instead
+fn legend_entries(plot: &Plot) -> Vec<(String, String)> {
+    let mut entries = plot
+        .traces
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| !f.is_colormapped())
+        .map(|(i, f)| (trace_color(i, f), f.name.clone()))
+        .collect::<Vec<(String, String)>>();
+    entries.extend(
+        pie_labels(&plot.traces)
+            .into_iter()
+            .enumerate()
+            .map(|(i, f)| (color(i).to_string(), f)),
+    );
+    entries
+}
+/// Draw each of the legend `entries` with the top left corner of the legend at (`x`,`y`)
+fn draw_legend(svg: &mut String, entries: &[(String, String)], x: f64, y: f64) {
+    for (i, (color, name)) in entries.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let top = y + 20.0 * i as f64;
+        writeln!(
+            svg,
+            "<rect x=\"{:.2}\" y=\"{:.2}\" width=\"12\" height=\"12\" fill=\"{}\"/>",
+            x,
+            top,
+            escape_html(color)
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            "<text x=\"{:.2}\" y=\"{:.2}\" dominant-baseline=\"middle\">{}</text>",
+            x + 18.0,
+            top + 6.0,
+            escape_html(&shorten(name, 18))
+        )
+        .unwrap();
+    }
+}
+
+/// Render `plot` as an SVG image
+#[allow(clippy::cast_precision_loss, clippy::too_many_lines)]
+pub(crate) fn render(plot: &Plot) -> String {
+    let (width, height) = (plot.width as f64, plot.height as f64);
+    let mut svg = String::new();
+    writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{1}\" viewBox=\"0 0 {0} {1}\" font-family=\"sans-serif\" font-size=\"12\">",
+        plot.width, plot.height
+    )
+    .unwrap();
+    svg.push_str("<rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>\n");
+    let top = if let Some(title) = &plot.title {
+        writeln!(
+            svg,
+            "<text x=\"{:.2}\" y=\"30\" text-anchor=\"middle\" font-size=\"18\">{}</text>",
+            width / 2.0,
+            escape_html(title)
+        )
+        .unwrap();
+        50.0
+    } else {
+        20.0
+    };
+    let traces = plot.traces.iter().enumerate().collect::<Vec<_>>();
+    let panels = if plot.subplots {
+        traces.iter().map(|f| vec![*f]).collect()
+    } else {
+        vec![traces]
+    };
+    let entries = legend_entries(plot);
+    let legend = plot.show_legend.unwrap_or(entries.len() > 1);
+    let left = if plot.y_label.is_some() {
+        LEFT + 20.0
+    } else {
+        LEFT
+    };
+    // Room on the right of each panel for a secondary axis and a color bar
+    let mut beside = 0.0;
+    if !plot.subplots && plot.traces.iter().any(|f| f.secondary_y) {
+        beside += SECONDARY_WIDTH;
+    }
+    if plot
+        .traces
+        .iter()
+        .any(|f| matches!(f.kind, TraceKind::Hexbin | TraceKind::Heatmap))
+    {
+        beside += COLORBAR_WIDTH;
+    }
+    let right = beside + if legend { LEGEND_WIDTH } else { 20.0 };
+    let bottom = if plot.x_label.is_some() {
+        BOTTOM + 20.0
+    } else {
+        BOTTOM
+    };
+    // Panels are spaced with room for the ticks of the panels above and to the left
+    let (rows, columns) = if plot.subplots { plot.grid() } else { (1, 1) };
+    let (x_gap, y_gap) = (LEFT + beside, 40.0);
+    let (rows, columns) = (rows as f64, columns as f64);
+    let panel_height = ((height - top - bottom - y_gap * (rows - 1.0)) / rows).max(1.0);
+    let panel_width = ((width - left - right - x_gap * (columns - 1.0)) / columns).max(1.0);
+    for (k, panel) in panels.iter().enumerate() {
+        let (row, column) = ((k as f64 / columns).floor(), k as f64 % columns);
+        let area = Area {
+            x: left + (panel_width + x_gap) * column,
+            y: top + (panel_height + y_gap) * row,
+            width: panel_width,
+            height: panel_height,
+        };
+        if panels.len() > 1 {
+            writeln!(
+                svg,
+                "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"middle\">{}</text>",
+                area.x + area.width / 2.0,
+                area.y - 6.0,
+                escape_html(&shorten(&panel[0].1.name, 40))
+            )
+            .unwrap();
+        }
+        draw_panel(&mut svg, plot, panel, area);
+    }
+    if let Some(label) = &plot.x_label {
+        writeln!(
+            svg,
+            "<text x=\"{:.2}\" y=\"{:.2}\" text-anchor=\"middle\">{}</text>",
+            left + (width - left - right) / 2.0,
+            height - 15.0,
+            escape_html(label)
+        )
+        .unwrap();
+    }
+    if let Some(label) = &plot.y_label {
+        let middle = top + (height - top - bottom) / 2.0;
+        writeln!(
+            svg,
+            "<text x=\"18\" y=\"{0:.2}\" text-anchor=\"middle\" transform=\"rotate(-90 18 {0:.2})\">{1}</text>",
+            middle,
+            escape_html(label)
+        )
+        .unwrap();
+    }
+    if legend {
+        draw_legend(&mut svg, &entries, width - LEGEND_WIDTH + 15.0, top);
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
@@ -3,9 +3,12 @@
 pub use crate::core::series::Series;
 
 pub use crate::core::dataframe::DataFrame;
+pub use crate::core::records::DataFrameRow;
 #[cfg(feature = "stats")]
 pub use crate::core::series::Describe;
-pub use crate::io::parser::{read_csv, read_fwf, read_json};
+#[cfg(feature = "stats")]
+pub use crate::plots::Plot;
+pub use crate::io::parser::{read_csv, read_csv_chunked, read_fwf, read_json};
 pub use num_traits::float::Float;
 
 #[cfg(feature = "clipboard")]
@@ -13,3 +16,21 @@ pub use crate::io::parser::read_clipboard;
 
 #[cfg(feature = "hdf5")]
 pub use crate::io::parser::read_hdf5_to_series;
+
+#[cfg(feature = "hdf5")]
+pub use crate::io::hdf5::{list_datasets, read_hdf5_group};
+
+#[cfg(feature = "parquet")]
+pub use crate::io::parser::read_parquet;
+
+#[cfg(feature = "arrow")]
+pub use crate::io::parser::read_ipc;
+
+#[cfg(feature = "sql")]
+pub use crate::io::parser::read_sql;
+
+#[cfg(feature = "html")]
+pub use crate::io::html::read_html;
+
+#[cfg(feature = "derive")]
+pub use dami_derive::DataFrameRow;
@@ -0,0 +1,170 @@
+use dami::core::index::date_time::NAT;
+use dami::enums::DataTypes;
+use dami::io::binary::{BinaryErrors, BinaryWriter, Compression, DamiFile};
+use dami::prelude::*;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+
+fn named<T: Clone + Default + 'static>(values: Vec<T>, name: &str) -> Series<T> {
+    let mut series = Series::from(values);
+    series.set_name(name);
+    series
+}
+
+fn mixed_frame() -> DataFrame {
+    let mut df = DataFrame::new();
+    df.add_series(named(vec![true, false, true], "valid"), true)
+        .unwrap();
+    df.add_series(named(vec![1_i32, -2, 3], "count"), true)
+        .unwrap();
+    df.add_series(named(vec![10_i64, 20, i64::MAX], "total"), true)
+        .unwrap();
+    df.add_series(named(vec![0.5_f32, 1.5, -2.0], "ratio"), true)
+        .unwrap();
+    df.add_series(named(vec![1.25_f64, 2.5, 1e300], "value"), true)
+        .unwrap();
+    df.add_series(
+        named(
+            vec!["Nairobi".to_string(), String::new(), "Zürich".to_string()],
+            "station",
+        ),
+        true,
+    )
+    .unwrap();
+    df.add_series(named(vec!["a", "bc", ""], "label"), true)
+        .unwrap();
+    let mut time = named(vec![0_i64, 1_600_000_000_000_000_000, NAT], "time");
+    time.set_dtype(DataTypes::DATETIME);
+    df.add_series(time, true).unwrap();
+    df
+}
+
+/// A path in the temporary directory unique to this process and test
+fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("dami-{}-{}.dami", std::process::id(), name))
+}
+
+fn assert_same(df: &DataFrame, back: &DataFrame) {
+    assert_eq!(back.columns(), df.columns());
+    assert_eq!(back.get_index(), df.get_index());
+    let dtypes = back.dtypes();
+    let expected = [
+        ("valid", DataTypes::BOOL),
+        ("count", DataTypes::I32),
+        ("total", DataTypes::I64),
+        ("ratio", DataTypes::F32),
+        ("value", DataTypes::F64),
+        ("station", DataTypes::STRING),
+        // str columns are stored as String columns
+        ("label", DataTypes::STRING),
+        ("time", DataTypes::DATETIME),
+    ];
+    for (name, dtype) in &expected {
+        assert!(
+            dtypes[*name] == *dtype,
+            "column {} has dtype {:?}",
+            name,
+            dtypes[*name]
+        );
+    }
+    assert_eq!(
+        back.get::<bool>("valid").unwrap().to_vec(),
+        vec![true, false, true]
+    );
+    assert_eq!(back.get::<i32>("count").unwrap().to_vec(), vec![1, -2, 3]);
+    assert_eq!(
+        back.get::<i64>("total").unwrap().to_vec(),
+        vec![10, 20, i64::MAX]
+    );
+    assert_eq!(
+        back.get::<f32>("ratio").unwrap().to_vec(),
+        vec![0.5, 1.5, -2.0]
+    );
+    assert_eq!(
+        back.get::<f64>("value").unwrap().to_vec(),
+        vec![1.25, 2.5, 1e300]
+    );
+    assert_eq!(
+        back.get::<String>("station").unwrap().to_vec(),
+        vec!["Nairobi", "", "Zürich"]
+    );
+    assert_eq!(
+        back.get::<String>("label").unwrap().to_vec(),
+        vec!["a", "bc", ""]
+    );
+    assert_eq!(
+        back.get::<i64>("time").unwrap().to_vec(),
+        vec![0, 1_600_000_000_000_000_000, NAT]
+    );
+}
+
+fn round_trip(compression: Compression, name: &str) {
+    let df = mixed_frame();
+    let path = temp_path(name);
+    BinaryWriter::new()
+        .set_compression(compression)
+        .write(&df, &path)
+        .unwrap();
+    let back = DataFrame::load(&path);
+    let file = DamiFile::open(&path).map(|f| f.compression());
+    fs::remove_file(&path).unwrap();
+    assert_same(&df, &back.unwrap());
+    assert_eq!(file.unwrap(), compression);
+}
+
+#[test]
+fn round_trip_uncompressed() {
+    round_trip(Compression::None, "uncompressed");
+}
+
+#[test]
+fn round_trip_lzma() {
+    round_trip(Compression::Lzma, "lzma");
+}
+
+#[test]
+fn read_single_column_lazily() {
+    let df = mixed_frame();
+    let path = temp_path("lazy");
+    BinaryWriter::new()
+        .set_compression(Compression::Lzma)
+        .write(&df, &path)
+        .unwrap();
+    let file = DamiFile::open(&path).unwrap();
+    assert_eq!(file.len(), 3);
+    assert_eq!(file.columns(), df.columns());
+    assert!(file.dtypes()["label"] == DataTypes::STRING);
+    let value = file.column::<f64>("value").unwrap();
+    assert_eq!(value.to_vec(), vec![1.25, 2.5, 1e300]);
+    assert_eq!(value.get_index(), df.get_index());
+    let time = file.column::<i64>("time").unwrap();
+    assert!(time.get_dtype() == DataTypes::DATETIME);
+    assert!(matches!(
+        file.column::<i32>("value"),
+        Err(BinaryErrors::DtypeError(..))
+    ));
+    assert!(matches!(
+        file.column::<f64>("missing"),
+        Err(BinaryErrors::KeyError(..))
+    ));
+    let selected = file.select(&["station", "count"]).unwrap();
+    assert_eq!(selected.columns(), vec!["station", "count"]);
+    drop(file);
+    fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn open_rejects_unknown_dtypes() {
+    let path = temp_path("unknown-dtype");
+    let schema = br#"{"len":0,"index":[],"compression":"None","columns":[{"name":"a","dtype":"COMPLEX","offset":0,"size":0}]}"#;
+    let mut file = fs::File::create(&path).unwrap();
+    file.write_all(b"DAMIBIN1").unwrap();
+    file.write_all(&(schema.len() as u64).to_le_bytes())
+        .unwrap();
+    file.write_all(schema).unwrap();
+    drop(file);
+    let opened = DamiFile::open(&path);
+    fs::remove_file(&path).unwrap();
+    assert!(matches!(opened, Err(BinaryErrors::InvalidFile(..))));
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "derive")]
+use dami::enums::DataTypes;
+use dami::prelude::*;
+
+#[derive(DataFrameRow)]
+struct Reading {
+    station: String,
+    label: &'static str,
+    valid: bool,
+    count: i32,
+    total: i64,
+    ratio: f32,
+    value: f64,
+}
+
+#[test]
+fn from_rows_keeps_field_order_and_dtypes() {
+    let df = DataFrame::from_rows(vec![
+        Reading {
+            station: "Nairobi".to_string(),
+            label: "a",
+            valid: true,
+            count: 1,
+            total: 10,
+            ratio: 0.5,
+            value: 1.5,
+        },
+        Reading {
+            station: "Mombasa".to_string(),
+            label: "b",
+            valid: false,
+            count: 2,
+            total: 20,
+            ratio: 0.25,
+            value: 2.5,
+        },
+    ])
+    .unwrap();
+    assert_eq!(
+        df.columns(),
+        vec!["station", "label", "valid", "count", "total", "ratio", "value"]
+    );
+    let dtypes = df.dtypes();
+    let expected = [
+        ("station", DataTypes::STRING),
+        ("label", DataTypes::STR),
+        ("valid", DataTypes::BOOL),
+        ("count", DataTypes::I32),
+        ("total", DataTypes::I64),
+        ("ratio", DataTypes::F32),
+        ("value", DataTypes::F64),
+    ];
+    for (name, dtype) in &expected {
+        assert!(
+            dtypes[*name] == *dtype,
+            "column {} has dtype {:?}",
+            name,
+            dtypes[*name]
+        );
+    }
+    assert_eq!(
+        df.get::<String>("station").unwrap().to_vec(),
+        vec!["Nairobi", "Mombasa"]
+    );
+    assert_eq!(df.get::<i64>("total").unwrap().to_vec(), vec![10, 20]);
+}
+
+#[test]
+fn from_rows_without_rows_is_empty() {
+    let df = DataFrame::from_rows(Vec::<Reading>::new()).unwrap();
+    assert!(df.is_empty());
+}